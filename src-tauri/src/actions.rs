@@ -1,9 +1,11 @@
 #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
 use crate::apple_intelligence;
 use crate::audio_feedback::{play_feedback_sound, play_feedback_sound_blocking, SoundType};
+use crate::auto_stop;
+use crate::hands_free::{self, HANDS_FREE_SHORTCUT};
 use crate::managers::audio::AudioRecordingManager;
 use crate::managers::history::HistoryManager;
-use crate::managers::transcription::TranscriptionManager;
+use crate::managers::transcription::{TranscriptionManager, TranscriptionOutput};
 use crate::settings::{get_settings, AppSettings, APPLE_INTELLIGENCE_PROVIDER_ID};
 use crate::shortcut;
 use crate::tray::{change_tray_icon, TrayIconState};
@@ -12,11 +14,11 @@ use crate::utils::{
 };
 use crate::ManagedToggleState;
 use ferrous_opencc::{config::BuiltinConfig, OpenCC};
-use log::{debug, error};
+use log::{debug, error, warn};
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
 
 // Shortcut Action Trait
@@ -30,7 +32,29 @@ struct TranscribeAction {
     post_process: bool,
 }
 
-async fn post_process_transcription(settings: &AppSettings, transcription: &str) -> Option<String> {
+async fn post_process_transcription(
+    settings: &AppSettings,
+    transcription: &str,
+    detected_language: Option<&str>,
+) -> Option<String> {
+    // A prompt mapped to the detected language wins over the selected one
+    let prompt = match settings.post_process_prompt_for(detected_language) {
+        Some(prompt) => prompt.prompt.clone(),
+        None => {
+            debug!("Post-processing skipped because no prompt is selected or it was not found");
+            return None;
+        }
+    };
+
+    post_process_with_prompt(settings, transcription, &prompt).await
+}
+
+/// Runs `prompt` over the transcription with the active post-processing provider.
+pub(crate) async fn post_process_with_prompt(
+    settings: &AppSettings,
+    transcription: &str,
+    prompt: &str,
+) -> Option<String> {
     let provider = match settings.active_post_process_provider().cloned() {
         Some(provider) => provider,
         None => {
@@ -53,29 +77,6 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         return None;
     }
 
-    let selected_prompt_id = match &settings.post_process_selected_prompt_id {
-        Some(id) => id.clone(),
-        None => {
-            debug!("Post-processing skipped because no prompt is selected");
-            return None;
-        }
-    };
-
-    let prompt = match settings
-        .post_process_prompts
-        .iter()
-        .find(|prompt| prompt.id == selected_prompt_id)
-    {
-        Some(prompt) => prompt.prompt.clone(),
-        None => {
-            debug!(
-                "Post-processing skipped because prompt '{}' was not found",
-                selected_prompt_id
-            );
-            return None;
-        }
-    };
-
     if prompt.trim().is_empty() {
         debug!("Post-processing skipped because the selected prompt is empty");
         return None;
@@ -133,8 +134,14 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
         .unwrap_or_default();
 
     // Send the chat completion request
-    match crate::llm_client::send_chat_completion(&provider, api_key, &model, processed_prompt)
-        .await
+    match crate::llm_client::send_chat_completion(
+        &provider,
+        api_key,
+        &model,
+        processed_prompt,
+        &settings.network,
+    )
+    .await
     {
         Ok(Some(content)) => {
             // Strip invisible Unicode characters that some LLMs (e.g., Qwen) may insert
@@ -165,22 +172,28 @@ async fn post_process_transcription(settings: &AppSettings, transcription: &str)
     }
 }
 
-async fn maybe_convert_chinese_variant(
+pub(crate) async fn maybe_convert_chinese_variant(
     settings: &AppSettings,
     transcription: &str,
+    detected_language: Option<&str>,
 ) -> Option<String> {
-    // Check if language is set to Simplified or Traditional Chinese
-    let is_simplified = settings.selected_language == "zh-Hans";
-    let is_traditional = settings.selected_language == "zh-Hant";
+    // Use the selected Chinese variant, or the one configured for auto-detected Chinese
+    let variant = if settings.selected_language == "auto" && detected_language == Some("zh") {
+        settings.auto_chinese_variant.as_deref().unwrap_or_default()
+    } else {
+        settings.selected_language.as_str()
+    };
+    let is_simplified = variant == "zh-Hans";
+    let is_traditional = variant == "zh-Hant";
 
     if !is_simplified && !is_traditional {
-        debug!("selected_language is not Simplified or Traditional Chinese; skipping translation");
+        debug!("Language is not Simplified or Traditional Chinese; skipping translation");
         return None;
     }
 
     debug!(
         "Starting Chinese translation using OpenCC for language: {}",
-        settings.selected_language
+        variant
     );
 
     // Use OpenCC to convert based on selected language
@@ -210,7 +223,7 @@ async fn maybe_convert_chinese_variant(
 }
 
 impl ShortcutAction for TranscribeAction {
-    fn start(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+    fn start(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str) {
         let start_time = Instant::now();
         debug!("TranscribeAction::start called for binding: {}", binding_id);
 
@@ -271,6 +284,22 @@ impl ShortcutAction for TranscribeAction {
         if recording_started {
             // Dynamically register the cancel shortcut in a separate task to avoid deadlock
             shortcut::register_cancel_shortcut(app);
+
+            // In toggle mode, optionally end the recording on trailing silence or after
+            // a maximum duration, through the same stop path as the hotkey
+            if !settings.push_to_talk {
+                let silence_timeout = settings.auto_stop_silence_ms.map(Duration::from_millis);
+                if let Some(timeout) = silence_timeout {
+                    rm.watch_silence(timeout);
+                }
+                auto_stop::begin(
+                    app,
+                    &binding_id,
+                    shortcut_str,
+                    silence_timeout,
+                    settings.max_recording_secs.map(Duration::from_secs),
+                );
+            }
         }
 
         debug!(
@@ -279,9 +308,10 @@ impl ShortcutAction for TranscribeAction {
         );
     }
 
-    fn stop(&self, app: &AppHandle, binding_id: &str, _shortcut_str: &str) {
+    fn stop(&self, app: &AppHandle, binding_id: &str, shortcut_str: &str) {
         // Unregister the cancel shortcut when transcription stops
         shortcut::unregister_cancel_shortcut(app);
+        auto_stop::end(app);
 
         let stop_time = Instant::now();
         debug!("TranscribeAction::stop called for binding: {}", binding_id);
@@ -302,6 +332,7 @@ impl ShortcutAction for TranscribeAction {
 
         let binding_id = binding_id.to_string(); // Clone binding_id for the async task
         let post_process = self.post_process;
+        let is_hands_free = shortcut_str == HANDS_FREE_SHORTCUT;
 
         tauri::async_runtime::spawn(async move {
             let binding_id = binding_id.clone(); // Clone for the inner async task
@@ -322,10 +353,35 @@ impl ShortcutAction for TranscribeAction {
                     duration_secs
                 );
 
+                // Hands-free captures only go through when they open with the wake phrase
+                let wake_phrase = if is_hands_free {
+                    hands_free::wake_phrase(&get_settings(&ah))
+                } else {
+                    None
+                };
+                let heard_wake_phrase = wake_phrase.as_deref().map_or(true, |phrase| {
+                    hands_free::heard_wake_phrase(&tm, &samples, phrase)
+                });
+
                 let transcription_time = Instant::now();
                 let samples_clone = samples.clone(); // Clone for history saving
-                match tm.transcribe(samples) {
-                    Ok(transcription) => {
+                let transcription_result = if heard_wake_phrase {
+                    tm.transcribe_detailed(samples, None)
+                } else {
+                    debug!("Wake phrase not heard, discarding hands-free capture");
+                    Ok(TranscriptionOutput {
+                        text: String::new(),
+                        language: None,
+                    })
+                };
+                match transcription_result {
+                    Ok(output) => {
+                        let detected_language = output.language;
+                        let language_code = detected_language.as_ref().map(|l| l.code.as_str());
+                        let transcription = match &wake_phrase {
+                            Some(phrase) => hands_free::strip_wake_phrase(&output.text, phrase),
+                            None => output.text,
+                        };
                         debug!(
                             "Transcription completed in {:?}: '{}'",
                             transcription_time.elapsed(),
@@ -338,8 +394,12 @@ impl ShortcutAction for TranscribeAction {
                             let mut post_process_prompt: Option<String> = None;
 
                             // First, check if Chinese variant conversion is needed
-                            if let Some(converted_text) =
-                                maybe_convert_chinese_variant(&settings, &transcription).await
+                            if let Some(converted_text) = maybe_convert_chinese_variant(
+                                &settings,
+                                &transcription,
+                                language_code,
+                            )
+                            .await
                             {
                                 final_text = converted_text;
                             }
@@ -350,7 +410,8 @@ impl ShortcutAction for TranscribeAction {
                                 show_processing_overlay(&ah);
                             }
                             let processed = if post_process {
-                                post_process_transcription(&settings, &final_text).await
+                                post_process_transcription(&settings, &final_text, language_code)
+                                    .await
                             } else {
                                 None
                             };
@@ -359,15 +420,9 @@ impl ShortcutAction for TranscribeAction {
                                 final_text = processed_text;
 
                                 // Get the prompt that was used
-                                if let Some(prompt_id) = &settings.post_process_selected_prompt_id {
-                                    if let Some(prompt) = settings
-                                        .post_process_prompts
-                                        .iter()
-                                        .find(|p| &p.id == prompt_id)
-                                    {
-                                        post_process_prompt = Some(prompt.prompt.clone());
-                                    }
-                                }
+                                post_process_prompt = settings
+                                    .post_process_prompt_for(language_code)
+                                    .map(|p| p.prompt.clone());
                             } else if final_text != transcription {
                                 // Chinese conversion was applied but no LLM post-processing
                                 post_processed_text = Some(final_text.clone());
@@ -383,6 +438,7 @@ impl ShortcutAction for TranscribeAction {
                                         transcription_for_history,
                                         post_processed_text,
                                         post_process_prompt,
+                                        detected_language,
                                     )
                                     .await
                                 {
@@ -431,6 +487,8 @@ impl ShortcutAction for TranscribeAction {
             if let Ok(mut states) = ah.state::<ManagedToggleState>().lock() {
                 states.active_toggles.insert(binding_id, false);
             }
+
+            hands_free::rearm_if_enabled(&ah);
         });
 
         debug!(
@@ -453,6 +511,44 @@ impl ShortcutAction for CancelAction {
     }
 }
 
+// Undo Last Dictation Action
+struct UndoLastDictationAction;
+
+/// Re-opens the recording as if the transcribe shortcut had been pressed, so
+/// that shortcut (or auto-stop) ends it in either mode.
+fn reopen_recording(app: &AppHandle) {
+    let Some(transcribe) = ACTION_MAP.get("transcribe") else {
+        return;
+    };
+    if let Ok(mut states) = app.state::<ManagedToggleState>().lock() {
+        states.active_toggles.insert("transcribe".to_string(), true);
+    }
+    transcribe.start(app, "transcribe", "");
+}
+
+impl ShortcutAction for UndoLastDictationAction {
+    fn start(&self, app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Keystrokes are sent from the main thread, like the paste they undo.
+        // The recording is only reopened once the undo is known to have happened.
+        let ah = app.clone();
+        app.run_on_main_thread(move || match utils::undo_last_insertion(&ah) {
+            Ok(true) => {
+                debug!("Last dictation undone");
+                if get_settings(&ah).undo_reopens_recording {
+                    reopen_recording(&ah);
+                }
+            }
+            Ok(false) => debug!("No dictation to undo"),
+            Err(e) => warn!("Failed to undo last dictation: {}", e),
+        })
+        .unwrap_or_else(|e| error!("Failed to run undo on main thread: {:?}", e));
+    }
+
+    fn stop(&self, _app: &AppHandle, _binding_id: &str, _shortcut_str: &str) {
+        // Nothing to do on stop for undo
+    }
+}
+
 // Test Action
 struct TestAction;
 
@@ -493,6 +589,10 @@ pub static ACTION_MAP: Lazy<HashMap<String, Arc<dyn ShortcutAction>>> = Lazy::ne
         "cancel".to_string(),
         Arc::new(CancelAction) as Arc<dyn ShortcutAction>,
     );
+    map.insert(
+        "undo_last_dictation".to_string(),
+        Arc::new(UndoLastDictationAction) as Arc<dyn ShortcutAction>,
+    );
     map.insert(
         "test".to_string(),
         Arc::new(TestAction) as Arc<dyn ShortcutAction>,
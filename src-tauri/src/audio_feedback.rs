@@ -67,6 +67,12 @@ pub fn play_test_sound(app: &AppHandle, sound_type: SoundType) {
     }
 }
 
+/// Plays a recording on the selected output device at full volume, blocking until done.
+pub fn play_recording(app: &AppHandle, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = settings::get_settings(app);
+    play_audio_file(path, settings.selected_output_device, 1.0)
+}
+
 fn play_sound_async(app: &AppHandle, path: PathBuf) {
     let app_handle = app.clone();
     thread::spawn(move || {
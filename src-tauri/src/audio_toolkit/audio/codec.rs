@@ -0,0 +1,276 @@
+//! Encoding and decoding of stored recordings: 16 kHz mono as WAV, FLAC or
+//! Ogg Opus.
+
+use anyhow::{bail, Context, Result};
+use flacenc::component::BitRepr;
+use flacenc::error::Verify;
+use ogg::writing::PacketWriteEndInfo;
+use ogg::{PacketReader, PacketWriter};
+use opus::{Application, Bitrate, Channels};
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use super::utils::{read_wav, write_wav};
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
+/// Opus always counts granule positions at 48 kHz.
+const OPUS_GRANULE_RATE: u32 = 48_000;
+const OPUS_RATE_FACTOR: u64 = (OPUS_GRANULE_RATE / WHISPER_SAMPLE_RATE) as u64;
+/// 20 ms frames
+const OPUS_FRAME_LEN: usize = WHISPER_SAMPLE_RATE as usize / 50;
+/// Plenty for speech, about a tenth of 16-bit PCM.
+const OPUS_BITRATE: i32 = 24_000;
+const OPUS_MAX_PACKET_LEN: usize = 4000;
+const OPUS_SERIAL: u32 = 1;
+const FLAC_BLOCK_SIZE: usize = 4096;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AudioFormat {
+    Wav,
+    Flac,
+    Opus,
+}
+
+impl AudioFormat {
+    pub const ALL: [AudioFormat; 3] = [AudioFormat::Wav, AudioFormat::Flac, AudioFormat::Opus];
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::Wav => "wav",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Opus => "opus",
+        }
+    }
+
+    /// The format of a recording, going by its file name.
+    pub fn from_path(path: impl AsRef<Path>) -> Option<AudioFormat> {
+        let extension = path.as_ref().extension()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|format| extension.eq_ignore_ascii_case(format.extension()))
+    }
+}
+
+/// Encodes 16 kHz mono samples in -1.0..1.0.
+pub fn encode_audio(format: AudioFormat, samples: &[f32]) -> Result<Vec<u8>> {
+    match format {
+        AudioFormat::Wav => {
+            let mut wav = Cursor::new(Vec::new());
+            write_wav(&mut wav, samples)?;
+            Ok(wav.into_inner())
+        }
+        AudioFormat::Flac => encode_flac(samples),
+        AudioFormat::Opus => encode_opus(samples),
+    }
+}
+
+/// Decodes what `encode_audio` wrote back into 16 kHz mono samples.
+pub fn decode_audio(format: AudioFormat, data: impl Read) -> Result<Vec<f32>> {
+    match format {
+        AudioFormat::Wav => read_wav(data),
+        AudioFormat::Flac => decode_flac(data),
+        AudioFormat::Opus => decode_opus(data),
+    }
+}
+
+fn to_i16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+fn encode_flac(samples: &[f32]) -> Result<Vec<u8>> {
+    let pcm: Vec<i32> = samples.iter().map(|&s| to_i16(s) as i32).collect();
+    let config = flacenc::config::Encoder::default()
+        .into_verified()
+        .map_err(|(_, e)| anyhow::anyhow!("Invalid FLAC encoder config: {:?}", e))?;
+    let source =
+        flacenc::source::MemSource::from_samples(&pcm, 1, 16, WHISPER_SAMPLE_RATE as usize);
+    let stream = flacenc::encode_with_fixed_block_size(&config, source, FLAC_BLOCK_SIZE)
+        .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+
+    let mut sink = flacenc::bitsink::ByteSink::new();
+    stream
+        .write(&mut sink)
+        .map_err(|e| anyhow::anyhow!("FLAC encoding failed: {:?}", e))?;
+    Ok(sink.as_slice().to_vec())
+}
+
+fn decode_flac(data: impl Read) -> Result<Vec<f32>> {
+    let mut reader = claxon::FlacReader::new(data).context("Not a FLAC file")?;
+    let info = reader.streaminfo();
+    if info.sample_rate != WHISPER_SAMPLE_RATE || info.channels != 1 {
+        bail!(
+            "Expected 16 kHz mono audio, got {} Hz with {} channel(s)",
+            info.sample_rate,
+            info.channels
+        );
+    }
+
+    // The last block may be padded; the stream info has the real length
+    let len = info.samples.map_or(usize::MAX, |n| n as usize);
+    let scale = (1i64 << (info.bits_per_sample - 1)) as f32;
+    reader
+        .samples()
+        .take(len)
+        .map(|s| Ok(s? as f32 / scale))
+        .collect::<Result<_, claxon::Error>>()
+        .context("Failed to decode FLAC")
+}
+
+/// Encodes into an Ogg Opus stream as described in RFC 7845.
+fn encode_opus(samples: &[f32]) -> Result<Vec<u8>> {
+    let mut encoder = opus::Encoder::new(WHISPER_SAMPLE_RATE, Channels::Mono, Application::Voip)?;
+    encoder.set_bitrate(Bitrate::Bits(OPUS_BITRATE))?;
+    let pre_skip = encoder.get_lookahead()? as u64 * OPUS_RATE_FACTOR;
+
+    let mut ogg = Vec::new();
+    let mut writer = PacketWriter::new(&mut ogg);
+    writer.write_packet(
+        opus_head(pre_skip as u16),
+        OPUS_SERIAL,
+        PacketWriteEndInfo::EndPage,
+        0,
+    )?;
+    writer.write_packet(opus_tags(), OPUS_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+    // Silence is appended to flush the encoder's lookahead and fill the last
+    // frame; the final granule position tells decoders where the audio ends.
+    let end_granule = pre_skip + samples.len() as u64 * OPUS_RATE_FACTOR;
+    let padded_len = samples.len() + (pre_skip / OPUS_RATE_FACTOR) as usize;
+    let frame_count = padded_len.div_ceil(OPUS_FRAME_LEN).max(1);
+    let mut frame = vec![0.0f32; OPUS_FRAME_LEN];
+    for index in 0..frame_count {
+        let start = (index * OPUS_FRAME_LEN).min(samples.len());
+        let chunk = &samples[start..(start + OPUS_FRAME_LEN).min(samples.len())];
+        frame.fill(0.0);
+        frame[..chunk.len()].copy_from_slice(chunk);
+        let packet = encoder.encode_vec_float(&frame, OPUS_MAX_PACKET_LEN)?;
+
+        if index + 1 == frame_count {
+            writer.write_packet(
+                packet,
+                OPUS_SERIAL,
+                PacketWriteEndInfo::EndStream,
+                end_granule,
+            )?;
+        } else {
+            let granule = pre_skip + ((index + 1) * OPUS_FRAME_LEN) as u64 * OPUS_RATE_FACTOR;
+            writer.write_packet(
+                packet,
+                OPUS_SERIAL,
+                PacketWriteEndInfo::NormalPacket,
+                granule,
+            )?;
+        }
+    }
+    drop(writer);
+    Ok(ogg)
+}
+
+fn opus_head(pre_skip: u16) -> Vec<u8> {
+    let mut head = b"OpusHead".to_vec();
+    head.push(1); // version
+    head.push(1); // channels
+    head.extend_from_slice(&pre_skip.to_le_bytes());
+    head.extend_from_slice(&WHISPER_SAMPLE_RATE.to_le_bytes());
+    head.extend_from_slice(&0i16.to_le_bytes()); // output gain
+    head.push(0); // channel mapping family
+    head
+}
+
+fn opus_tags() -> Vec<u8> {
+    let vendor = b"typezero";
+    let mut tags = b"OpusTags".to_vec();
+    tags.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    tags.extend_from_slice(vendor);
+    tags.extend_from_slice(&0u32.to_le_bytes()); // no comments
+    tags
+}
+
+fn decode_opus(mut data: impl Read) -> Result<Vec<f32>> {
+    let mut bytes = Vec::new();
+    data.read_to_end(&mut bytes)?;
+    let mut reader = PacketReader::new(Cursor::new(bytes));
+
+    let head = reader
+        .read_packet()?
+        .filter(|packet| packet.data.starts_with(b"OpusHead") && packet.data.len() >= 19)
+        .context("Not an Ogg Opus file")?;
+    if head.data[9] != 1 {
+        bail!("Expected mono audio, got {} channels", head.data[9]);
+    }
+    let pre_skip = u16::from_le_bytes([head.data[10], head.data[11]]) as u64;
+    // OpusTags
+    reader.read_packet()?.context("Truncated Ogg Opus file")?;
+
+    let mut decoder = opus::Decoder::new(WHISPER_SAMPLE_RATE, Channels::Mono)?;
+    let mut samples = Vec::new();
+    // Room for the longest packet Opus allows, 120 ms
+    let mut frame = vec![0.0f32; WHISPER_SAMPLE_RATE as usize * 120 / 1000];
+    let mut end_granule = None;
+    while let Some(packet) = reader.read_packet()? {
+        let decoded = decoder.decode_float(&packet.data, &mut frame, false)?;
+        samples.extend_from_slice(&frame[..decoded]);
+        if packet.last_in_stream() {
+            end_granule = Some(packet.absgp_page());
+        }
+    }
+
+    let skip = (pre_skip / OPUS_RATE_FACTOR) as usize;
+    if let Some(end) = end_granule {
+        let len = (end.saturating_sub(pre_skip) / OPUS_RATE_FACTOR) as usize;
+        samples.truncate(skip + len);
+    }
+    samples.drain(..skip.min(samples.len()));
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 16000.0).sin() * 0.5)
+            .collect()
+    }
+
+    #[test]
+    fn flac_is_lossless_at_16_bits() {
+        let samples = tone(10_000);
+        let encoded = encode_audio(AudioFormat::Flac, &samples).unwrap();
+        let decoded = decode_audio(AudioFormat::Flac, encoded.as_slice()).unwrap();
+
+        assert_eq!(decoded.len(), samples.len());
+        for (a, b) in samples.iter().zip(&decoded) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn opus_keeps_the_length_and_shrinks_the_file() {
+        for len in [0, 100, OPUS_FRAME_LEN, 16_000 * 3 + 7] {
+            let samples = tone(len);
+            let encoded = encode_audio(AudioFormat::Opus, &samples).unwrap();
+            let decoded = decode_audio(AudioFormat::Opus, encoded.as_slice()).unwrap();
+            assert_eq!(decoded.len(), samples.len());
+        }
+
+        let samples = tone(16_000 * 3);
+        let opus = encode_audio(AudioFormat::Opus, &samples).unwrap();
+        let wav = encode_audio(AudioFormat::Wav, &samples).unwrap();
+        assert!(opus.len() * 5 < wav.len());
+    }
+
+    #[test]
+    fn formats_follow_the_extension() {
+        assert_eq!(
+            AudioFormat::from_path("meeting-1.opus"),
+            Some(AudioFormat::Opus)
+        );
+        assert_eq!(
+            AudioFormat::from_path("typezero-1.WAV"),
+            Some(AudioFormat::Wav)
+        );
+        assert_eq!(AudioFormat::from_path("typezero-1.wav.tmp"), None);
+    }
+}
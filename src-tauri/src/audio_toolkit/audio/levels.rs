@@ -0,0 +1,85 @@
+use crate::audio_toolkit::dsp::FRAME_SAMPLES;
+use crate::audio_toolkit::vad::{frame_db, VoiceActivityDetector};
+
+/// Level summary of a 16 kHz recording, in dBFS.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelStats {
+    pub rms_db: f32,
+    pub peak_db: f32,
+    /// 10th percentile of 30-ms frame levels
+    pub noise_floor_db: f32,
+    /// Share of frames the VAD marked as speech, when a VAD was given
+    pub speech_ratio: Option<f32>,
+}
+
+fn amplitude_db(amplitude: f32) -> f32 {
+    20.0 * amplitude.max(1e-6).log10()
+}
+
+/// Measures `samples`, optionally running them through `vad` frame by frame.
+pub fn measure_levels(samples: &[f32], vad: Option<&mut dyn VoiceActivityDetector>) -> LevelStats {
+    let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+    let frames: Vec<&[f32]> = samples.chunks_exact(FRAME_SAMPLES).collect();
+
+    let mut levels: Vec<f32> = frames.iter().map(|f| frame_db(f)).collect();
+    levels.sort_by(f32::total_cmp);
+    let noise_floor_db = levels
+        .get(levels.len() / 10)
+        .copied()
+        .unwrap_or_else(|| frame_db(samples));
+
+    let speech_ratio = vad.filter(|_| !frames.is_empty()).map(|vad| {
+        vad.reset();
+        let speech = frames
+            .iter()
+            .filter(|f| vad.is_voice(f).unwrap_or(false))
+            .count();
+        speech as f32 / frames.len() as f32
+    });
+
+    LevelStats {
+        rms_db: frame_db(samples).max(amplitude_db(0.0)),
+        peak_db: amplitude_db(peak),
+        noise_floor_db: noise_floor_db.max(amplitude_db(0.0)),
+        speech_ratio,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::vad::EnergyVad;
+
+    fn tone(amplitude: f32, frames: usize) -> Vec<f32> {
+        (0..frames * FRAME_SAMPLES)
+            .map(|i| amplitude * (i as f32 * 0.3).sin())
+            .collect()
+    }
+
+    #[test]
+    fn reports_levels_of_a_tone() {
+        let stats = measure_levels(&tone(0.5, 20), None);
+        // A full-scale sine has an RMS 3 dB below its peak
+        assert!((stats.peak_db - -6.0).abs() < 0.1, "{:?}", stats);
+        assert!((stats.rms_db - -9.0).abs() < 0.2, "{:?}", stats);
+        assert_eq!(stats.speech_ratio, None);
+    }
+
+    #[test]
+    fn silence_bottoms_out() {
+        let stats = measure_levels(&[0.0; 4800], None);
+        assert_eq!(stats.peak_db, -120.0);
+        assert_eq!(stats.rms_db, -120.0);
+    }
+
+    #[test]
+    fn counts_speech_frames() {
+        let mut samples = tone(0.001, 30);
+        samples.extend(tone(0.3, 10));
+        let mut vad = EnergyVad::new(9.0).unwrap();
+
+        let stats = measure_levels(&samples, Some(&mut vad));
+        assert_eq!(stats.speech_ratio, Some(0.25));
+        assert!(stats.noise_floor_db < -55.0);
+    }
+}
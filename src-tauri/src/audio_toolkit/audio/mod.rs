@@ -1,12 +1,16 @@
 // Re-export all audio components
+mod codec;
 mod device;
+mod levels;
 mod recorder;
 mod resampler;
 mod utils;
 mod visualizer;
 
+pub use codec::{decode_audio, encode_audio, AudioFormat};
 pub use device::{list_input_devices, list_output_devices, CpalDeviceInfo};
-pub use recorder::AudioRecorder;
+pub use levels::{measure_levels, LevelStats};
+pub use recorder::{AudioRecorder, Capture, SpeechEvent};
 pub use resampler::FrameResampler;
-pub use utils::save_wav_file;
+pub use utils::{read_wav, read_wav_file, save_wav_file, write_wav_file};
 pub use visualizer::AudioVisualiser;
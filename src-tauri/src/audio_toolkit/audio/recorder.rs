@@ -1,7 +1,7 @@
 use std::{
     io::Error,
     sync::{mpsc, Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use cpal::{
@@ -12,12 +12,46 @@ use cpal::{
 use crate::audio_toolkit::{
     audio::{AudioVisualiser, FrameResampler},
     constants,
+    dsp::ProcessingChain,
     vad::{self, VadFrame},
     VoiceActivityDetector,
 };
 
+/// Audio collected by `AudioRecorder::capture`, with stats on the device stream.
+#[derive(Clone, Debug)]
+pub struct Capture {
+    /// 16 kHz audio after pre-processing
+    pub samples: Vec<f32>,
+    /// Rate the device stream was opened at
+    pub device_sample_rate: u32,
+    /// Device rate measured from how fast audio actually arrived
+    pub measured_sample_rate: f32,
+    /// Peak of the unprocessed device signal
+    pub device_peak: f32,
+    /// Unprocessed device samples at or near full scale
+    pub clipped_samples: usize,
+    pub device_samples: usize,
+}
+
+/// Speech boundaries reported by the consumer thread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpeechEvent {
+    /// Listening mode heard speech and started capturing on its own.
+    Started,
+    /// Speech stopped; the silence timeout is now counting down.
+    Paused,
+    /// Speech came back before the silence timeout ran out.
+    Resumed,
+    /// Trailing silence after speech exceeded the silence timeout.
+    Ended,
+}
+
 enum Cmd {
     Start,
+    Listen(Duration),
+    Unlisten,
+    WatchSilence(Duration),
+    Capture(usize, mpsc::Sender<Capture>),
     Stop(mpsc::Sender<Vec<f32>>),
     Yield(mpsc::Sender<Vec<f32>>),
     Shutdown,
@@ -28,7 +62,9 @@ pub struct AudioRecorder {
     cmd_tx: Option<mpsc::Sender<Cmd>>,
     worker_handle: Option<std::thread::JoinHandle<()>>,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+    processing: Option<Arc<Mutex<ProcessingChain>>>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    speech_cb: Option<Arc<dyn Fn(SpeechEvent) + Send + Sync + 'static>>,
 }
 
 impl AudioRecorder {
@@ -38,7 +74,9 @@ impl AudioRecorder {
             cmd_tx: None,
             worker_handle: None,
             vad: None,
+            processing: None,
             level_cb: None,
+            speech_cb: None,
         })
     }
 
@@ -47,6 +85,22 @@ impl AudioRecorder {
         self
     }
 
+    /// Swaps the VAD. On an open stream it takes effect from the next frame,
+    /// provided the recorder was built with a VAD.
+    pub fn set_vad(&mut self, vad: Box<dyn VoiceActivityDetector>) {
+        match &self.vad {
+            Some(shared) => *shared.lock().unwrap() = vad,
+            None => self.vad = Some(Arc::new(Mutex::new(vad))),
+        }
+    }
+
+    /// Runs every resampled frame through `chain` before the VAD. The chain is
+    /// shared so callers can swap its stages while the stream is open.
+    pub fn with_processing(mut self, chain: Arc<Mutex<ProcessingChain>>) -> Self {
+        self.processing = Some(chain);
+        self
+    }
+
     pub fn with_level_callback<F>(mut self, cb: F) -> Self
     where
         F: Fn(Vec<f32>) + Send + Sync + 'static,
@@ -55,6 +109,15 @@ impl AudioRecorder {
         self
     }
 
+    /// Called from the audio thread, so the callback must not block on this recorder.
+    pub fn with_speech_callback<F>(mut self, cb: F) -> Self
+    where
+        F: Fn(SpeechEvent) + Send + Sync + 'static,
+    {
+        self.speech_cb = Some(Arc::new(cb));
+        self
+    }
+
     pub fn open(&mut self, device: Option<Device>) -> Result<(), Box<dyn std::error::Error>> {
         if self.worker_handle.is_some() {
             return Ok(()); // already open
@@ -73,8 +136,10 @@ impl AudioRecorder {
 
         let thread_device = device.clone();
         let vad = self.vad.clone();
+        let processing = self.processing.clone();
         // Move the optional level callback into the worker thread
         let level_cb = self.level_cb.clone();
+        let speech_cb = self.speech_cb.clone();
 
         let worker = std::thread::spawn(move || {
             let config = AudioRecorder::get_preferred_config(&thread_device)
@@ -118,7 +183,15 @@ impl AudioRecorder {
             stream.play().expect("failed to start stream");
 
             // keep the stream alive while we process samples
-            run_consumer(sample_rate, vad, sample_rx, cmd_rx, level_cb);
+            run_consumer(
+                sample_rate,
+                vad,
+                processing,
+                sample_rx,
+                cmd_rx,
+                level_cb,
+                speech_cb,
+            );
             // stream is dropped here, after run_consumer returns
         });
 
@@ -136,6 +209,50 @@ impl AudioRecorder {
         Ok(())
     }
 
+    /// Waits for speech and starts capturing on its own once the VAD hears it.
+    /// The capture keeps running until `stop`; `SpeechEvent::Ended` is reported
+    /// after `silence_timeout` of trailing silence so the caller can stop it.
+    pub fn listen(&self, silence_timeout: Duration) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Listen(silence_timeout))?;
+        }
+        Ok(())
+    }
+
+    /// Reports `SpeechEvent::Ended` once the current capture has seen
+    /// `silence_timeout` of trailing silence after speech.
+    pub fn watch_silence(
+        &self,
+        silence_timeout: Duration,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::WatchSilence(silence_timeout))?;
+        }
+        Ok(())
+    }
+
+    /// Stops waiting for speech. A capture that already started is left running.
+    pub fn unlisten(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Unlisten)?;
+        }
+        Ok(())
+    }
+
+    /// Collects `samples` of pre-processed 16 kHz audio as the VAD sees it,
+    /// whether or not a capture is running. The receiver gets the audio once
+    /// enough has arrived; it is not added to the recording buffer.
+    pub fn capture(
+        &self,
+        samples: usize,
+    ) -> Result<mpsc::Receiver<Capture>, Box<dyn std::error::Error>> {
+        let (resp_tx, resp_rx) = mpsc::channel();
+        if let Some(tx) = &self.cmd_tx {
+            tx.send(Cmd::Capture(samples, resp_tx))?;
+        }
+        Ok(resp_rx)
+    }
+
     pub fn stop(&self) -> Result<Vec<f32>, Box<dyn std::error::Error>> {
         let (resp_tx, resp_rx) = mpsc::channel();
         if let Some(tx) = &self.cmd_tx {
@@ -248,21 +365,153 @@ impl AudioRecorder {
     }
 }
 
+const FRAME_MS: u64 = 30;
+
+/// Counts consecutive non-speech frames once speech has been heard.
+struct SilenceWatch {
+    limit_frames: usize,
+    heard_speech: bool,
+    silent_frames: usize,
+    fired: bool,
+}
+
+impl SilenceWatch {
+    fn new(timeout: Duration) -> Self {
+        Self {
+            limit_frames: ((timeout.as_millis() as u64 / FRAME_MS) as usize).max(1),
+            heard_speech: false,
+            silent_frames: 0,
+            fired: false,
+        }
+    }
+
+    /// Returns the event for this frame, if it changes the silence state.
+    /// `Ended` is reported once, on the frame that reaches the silence limit.
+    fn observe(&mut self, is_speech: bool) -> Option<SpeechEvent> {
+        if self.fired {
+            return None;
+        }
+        if is_speech {
+            self.heard_speech = true;
+            let resumed = self.silent_frames > 0;
+            self.silent_frames = 0;
+            return resumed.then_some(SpeechEvent::Resumed);
+        }
+        if !self.heard_speech {
+            return None;
+        }
+        self.silent_frames += 1;
+        if self.silent_frames >= self.limit_frames {
+            self.fired = true;
+            Some(SpeechEvent::Ended)
+        } else if self.silent_frames == 1 {
+            Some(SpeechEvent::Paused)
+        } else {
+            None
+        }
+    }
+}
+
+/// Samples at or above this magnitude count as clipped.
+const CLIP_LEVEL: f32 = 0.999;
+
+/// Raw audio requested through `AudioRecorder::capture`.
+struct RawCapture {
+    wanted: usize,
+    reply: mpsc::Sender<Capture>,
+    samples: Vec<f32>,
+    /// Arrival of the first device chunk, and its length
+    first_chunk: Option<(Instant, usize)>,
+    device_samples: usize,
+    device_peak: f32,
+    clipped_samples: usize,
+}
+
+impl RawCapture {
+    fn new(wanted: usize, reply: mpsc::Sender<Capture>) -> Self {
+        Self {
+            wanted,
+            reply,
+            samples: Vec::with_capacity(wanted),
+            first_chunk: None,
+            device_samples: 0,
+            device_peak: 0.0,
+            clipped_samples: 0,
+        }
+    }
+
+    fn observe_device(&mut self, raw: &[f32]) {
+        self.first_chunk.get_or_insert((Instant::now(), raw.len()));
+        self.device_samples += raw.len();
+        for sample in raw {
+            let level = sample.abs();
+            self.device_peak = self.device_peak.max(level);
+            if level >= CLIP_LEVEL {
+                self.clipped_samples += 1;
+            }
+        }
+    }
+
+    fn finish(mut self, device_sample_rate: u32) {
+        self.samples.truncate(self.wanted);
+        // The first chunk was recorded before the clock started
+        let measured_sample_rate = match self.first_chunk {
+            Some((start, first_len)) => {
+                let elapsed = start.elapsed().as_secs_f32();
+                if elapsed > 0.0 {
+                    (self.device_samples - first_len) as f32 / elapsed
+                } else {
+                    0.0
+                }
+            }
+            None => 0.0,
+        };
+
+        let _ = self.reply.send(Capture {
+            samples: self.samples,
+            device_sample_rate,
+            measured_sample_rate,
+            device_peak: self.device_peak,
+            clipped_samples: self.clipped_samples,
+            device_samples: self.device_samples,
+        });
+    }
+}
+
 fn run_consumer(
     in_sample_rate: u32,
     vad: Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
+    processing: Option<Arc<Mutex<ProcessingChain>>>,
     sample_rx: mpsc::Receiver<Vec<f32>>,
     cmd_rx: mpsc::Receiver<Cmd>,
     level_cb: Option<Arc<dyn Fn(Vec<f32>) + Send + Sync + 'static>>,
+    speech_cb: Option<Arc<dyn Fn(SpeechEvent) + Send + Sync + 'static>>,
 ) {
     let mut frame_resampler = FrameResampler::new(
         in_sample_rate as usize,
         constants::WHISPER_SAMPLE_RATE as usize,
-        Duration::from_millis(30),
+        Duration::from_millis(FRAME_MS),
     );
 
     let mut processed_samples = Vec::<f32>::new();
+    let mut frame_buf = Vec::<f32>::new();
+
+    // The chain runs on every frame, recording or not, so its noise and gain
+    // estimates are already settled when a capture starts. Only a new stream
+    // starts it from scratch.
+    if let Some(chain) = &processing {
+        chain.lock().unwrap().reset();
+    }
     let mut recording = false;
+    // Set while listening for speech or when the capture should end on silence
+    let mut silence_watch: Option<SilenceWatch> = None;
+    let mut raw_capture: Option<RawCapture> = None;
+
+    let emit_speech = |event: SpeechEvent| {
+        if let Some(cb) = &speech_cb {
+            cb(event);
+        }
+    };
 
     // ---------- spectrum visualisation setup ---------------------------- //
     const BUCKETS: usize = 16;
@@ -275,24 +524,48 @@ fn run_consumer(
         4000.0, // vocal_max_hz
     );
 
+    fn preprocess<'a>(
+        samples: &'a [f32],
+        processing: &Option<Arc<Mutex<ProcessingChain>>>,
+        buf: &'a mut Vec<f32>,
+    ) -> &'a [f32] {
+        match processing {
+            Some(chain) => {
+                let mut chain = chain.lock().unwrap();
+                if chain.is_empty() {
+                    return samples;
+                }
+                buf.clear();
+                buf.extend_from_slice(samples);
+                chain.process(buf);
+                buf
+            }
+            None => samples,
+        }
+    }
+
     fn handle_frame(
         samples: &[f32],
         recording: bool,
         vad: &Option<Arc<Mutex<Box<dyn vad::VoiceActivityDetector>>>>,
         out_buf: &mut Vec<f32>,
-    ) {
+    ) -> bool {
         if !recording {
-            return;
+            return false;
         }
 
         if let Some(vad_arc) = vad {
             let mut det = vad_arc.lock().unwrap();
             match det.push_frame(samples).unwrap_or(VadFrame::Speech(samples)) {
-                VadFrame::Speech(buf) => out_buf.extend_from_slice(buf),
-                VadFrame::Noise => {}
+                VadFrame::Speech(buf) => {
+                    out_buf.extend_from_slice(buf);
+                    true
+                }
+                VadFrame::Noise => false,
             }
         } else {
             out_buf.extend_from_slice(samples);
+            true
         }
     }
 
@@ -309,9 +582,40 @@ fn run_consumer(
             }
         }
 
+        if let Some(capture) = raw_capture.as_mut() {
+            capture.observe_device(&raw);
+        }
+
         // ---------- existing pipeline ------------------------------------ //
         frame_resampler.push(&raw, &mut |frame: &[f32]| {
-            handle_frame(frame, recording, &vad, &mut processed_samples)
+            let frame = preprocess(frame, &processing, &mut frame_buf);
+
+            if let Some(capture) = raw_capture.as_mut() {
+                capture.samples.extend_from_slice(frame);
+                if capture.samples.len() >= capture.wanted {
+                    if let Some(capture) = raw_capture.take() {
+                        capture.finish(in_sample_rate);
+                    }
+                }
+            }
+
+            let listening = silence_watch.is_some();
+            // While listening, the VAD drops everything until speech begins,
+            // so the buffer only fills once the capture has started.
+            let is_speech =
+                handle_frame(frame, recording || listening, &vad, &mut processed_samples);
+
+            if let Some(watch) = silence_watch.as_mut() {
+                if !recording && is_speech {
+                    recording = true;
+                    emit_speech(SpeechEvent::Started);
+                }
+                if recording {
+                    if let Some(event) = watch.observe(is_speech) {
+                        emit_speech(event);
+                    }
+                }
+            }
         });
 
         // non-blocking check for a command
@@ -320,18 +624,50 @@ fn run_consumer(
                 Cmd::Start => {
                     processed_samples.clear();
                     recording = true;
+                    silence_watch = None;
                     visualizer.reset(); // Reset visualization buffer
                     if let Some(v) = &vad {
                         v.lock().unwrap().reset();
                     }
                 }
+                Cmd::Listen(silence_timeout) => {
+                    if recording {
+                        continue;
+                    }
+                    processed_samples.clear();
+                    silence_watch = Some(SilenceWatch::new(silence_timeout));
+                    if let Some(v) = &vad {
+                        v.lock().unwrap().reset();
+                    }
+                }
+                Cmd::WatchSilence(silence_timeout) => {
+                    if recording {
+                        silence_watch = Some(SilenceWatch::new(silence_timeout));
+                    }
+                }
+                Cmd::Capture(wanted, reply) => {
+                    raw_capture = Some(RawCapture::new(wanted, reply));
+                }
+                Cmd::Unlisten => {
+                    if !recording {
+                        silence_watch = None;
+                        processed_samples.clear();
+                    }
+                }
                 Cmd::Stop(reply_tx) => {
                     recording = false;
+                    silence_watch = None;
 
                     frame_resampler.finish(&mut |frame: &[f32]| {
                         // we still want to process the last few frames
-                        handle_frame(frame, true, &vad, &mut processed_samples)
+                        let frame = preprocess(frame, &processing, &mut frame_buf);
+                        handle_frame(frame, true, &vad, &mut processed_samples);
                     });
+                    // The noise suppressor still holds back the last frame
+                    let tail = processing.as_ref().and_then(|c| c.lock().unwrap().flush());
+                    if let Some(tail) = tail {
+                        handle_frame(&tail, true, &vad, &mut processed_samples);
+                    }
 
                     let _ = reply_tx.send(std::mem::take(&mut processed_samples));
                 }
@@ -344,3 +680,114 @@ fn run_consumer(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(count: u64) -> Duration {
+        Duration::from_millis(count * FRAME_MS)
+    }
+
+    #[test]
+    fn silence_watch_ignores_silence_before_speech() {
+        let mut watch = SilenceWatch::new(frames(2));
+        assert_eq!(watch.observe(false), None);
+        assert_eq!(watch.observe(false), None);
+        assert_eq!(watch.observe(false), None);
+    }
+
+    #[test]
+    fn silence_watch_reports_pause_resume_and_end_once() {
+        let mut watch = SilenceWatch::new(frames(3));
+        assert_eq!(watch.observe(true), None);
+        assert_eq!(watch.observe(false), Some(SpeechEvent::Paused));
+        assert_eq!(watch.observe(true), Some(SpeechEvent::Resumed));
+        assert_eq!(watch.observe(false), Some(SpeechEvent::Paused));
+        assert_eq!(watch.observe(false), None);
+        assert_eq!(watch.observe(false), Some(SpeechEvent::Ended));
+        assert_eq!(watch.observe(true), None);
+        assert_eq!(watch.observe(false), None);
+    }
+
+    #[test]
+    fn silence_watch_waits_at_least_one_frame() {
+        let mut watch = SilenceWatch::new(Duration::ZERO);
+        assert_eq!(watch.observe(true), None);
+        assert_eq!(watch.observe(false), Some(SpeechEvent::Ended));
+    }
+
+    /// Treats any frame with a loud sample as speech.
+    struct LevelVad;
+
+    impl VoiceActivityDetector for LevelVad {
+        fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> anyhow::Result<VadFrame<'a>> {
+            if frame.iter().any(|s| s.abs() > 0.1) {
+                Ok(VadFrame::Speech(frame))
+            } else {
+                Ok(VadFrame::Noise)
+            }
+        }
+    }
+
+    #[test]
+    fn listening_captures_speech_until_trailing_silence() {
+        let (sample_tx, sample_rx) = mpsc::channel();
+        let (cmd_tx, cmd_rx) = mpsc::channel();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let vad: Box<dyn VoiceActivityDetector> = Box::new(LevelVad);
+        let speech_cb: Arc<dyn Fn(SpeechEvent) + Send + Sync> = {
+            let events = events.clone();
+            Arc::new(move |event| events.lock().unwrap().push(event))
+        };
+        let consumer = std::thread::spawn(move || {
+            run_consumer(
+                constants::WHISPER_SAMPLE_RATE,
+                Some(Arc::new(Mutex::new(vad))),
+                None,
+                sample_rx,
+                cmd_rx,
+                None,
+                Some(speech_cb),
+            )
+        });
+
+        let frame_len = (constants::WHISPER_SAMPLE_RATE as u64 * FRAME_MS / 1000) as usize;
+        let silence = vec![0.0; frame_len];
+        let speech = vec![0.5; frame_len];
+
+        // Commands are handled after the next chunk arrives
+        cmd_tx.send(Cmd::Listen(frames(2))).unwrap();
+        for chunk in [
+            &silence, &silence, &speech, &speech, &silence, &speech, &silence, &silence,
+        ] {
+            sample_tx.send(chunk.clone()).unwrap();
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !events.lock().unwrap().contains(&SpeechEvent::Ended) {
+            assert!(Instant::now() < deadline, "capture never ended");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let (reply_tx, reply_rx) = mpsc::channel();
+        cmd_tx.send(Cmd::Stop(reply_tx)).unwrap();
+        sample_tx.send(silence.clone()).unwrap();
+        let samples = reply_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        drop(sample_tx);
+        consumer.join().unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                SpeechEvent::Started,
+                SpeechEvent::Paused,
+                SpeechEvent::Resumed,
+                SpeechEvent::Paused,
+                SpeechEvent::Ended,
+            ]
+        );
+        // Only the three speech frames are kept
+        assert_eq!(samples, [speech.clone(), speech.clone(), speech].concat());
+    }
+}
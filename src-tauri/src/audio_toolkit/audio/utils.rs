@@ -1,10 +1,26 @@
 use anyhow::Result;
 use hound::{WavSpec, WavWriter};
 use log::debug;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, Write};
 use std::path::Path;
 
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
+
 /// Save audio samples as a WAV file
 pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
+    write_wav_file(file_path, samples)
+}
+
+/// Blocking variant of [`save_wav_file`] for callers outside an async context
+pub fn write_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Result<()> {
+    write_wav(BufWriter::new(File::create(file_path.as_ref())?), samples)?;
+    debug!("Saved WAV file: {:?}", file_path.as_ref());
+    Ok(())
+}
+
+/// Like [`write_wav_file`], for any seekable writer.
+pub fn write_wav<W: Write + Seek>(writer: W, samples: &[f32]) -> Result<()> {
     let spec = WavSpec {
         channels: 1,
         sample_rate: 16000,
@@ -12,7 +28,7 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
         sample_format: hound::SampleFormat::Int,
     };
 
-    let mut writer = WavWriter::create(file_path.as_ref(), spec)?;
+    let mut writer = WavWriter::new(writer, spec)?;
 
     // Convert f32 samples to i16 for WAV
     for sample in samples {
@@ -21,6 +37,32 @@ pub async fn save_wav_file<P: AsRef<Path>>(file_path: P, samples: &[f32]) -> Res
     }
 
     writer.finalize()?;
-    debug!("Saved WAV file: {:?}", file_path.as_ref());
     Ok(())
 }
+
+/// Reads a 16 kHz mono WAV file (16-bit or float) into samples in -1.0..1.0.
+pub fn read_wav_file<P: AsRef<Path>>(file_path: P) -> Result<Vec<f32>> {
+    read_wav(BufReader::new(File::open(file_path.as_ref())?))
+}
+
+/// Like [`read_wav_file`], for WAV data from any reader.
+pub fn read_wav<R: Read>(reader: R) -> Result<Vec<f32>> {
+    let mut reader = hound::WavReader::new(reader)?;
+    let spec = reader.spec();
+    if spec.sample_rate != WHISPER_SAMPLE_RATE || spec.channels != 1 {
+        anyhow::bail!(
+            "Expected 16 kHz mono audio, got {} Hz with {} channel(s)",
+            spec.sample_rate,
+            spec.channels
+        );
+    }
+
+    let samples = match spec.sample_format {
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<_, _>>()?,
+        hound::SampleFormat::Int => reader
+            .samples::<i16>()
+            .map(|s| s.map(|s| s as f32 / i16::MAX as f32))
+            .collect::<Result<_, _>>()?,
+    };
+    Ok(samples)
+}
@@ -0,0 +1,56 @@
+use super::AudioProcessor;
+
+/// Level the gain steers towards (about -20 dBFS RMS).
+const TARGET_RMS: f32 = 0.1;
+const MAX_GAIN: f32 = 10.0;
+const MIN_GAIN: f32 = 0.1;
+/// Frames quieter than this are treated as silence and leave the gain alone,
+/// so pauses don't get boosted up to speech level.
+const SILENCE_RMS: f32 = 0.002;
+/// Per-frame smoothing when the gain has to drop (fast) or rise (slow).
+const ATTACK: f32 = 0.5;
+const RELEASE: f32 = 0.05;
+
+/// Frame-based automatic gain control with a hard limiter.
+pub struct AutomaticGainControl {
+    gain: f32,
+}
+
+impl AutomaticGainControl {
+    pub fn new() -> Self {
+        Self { gain: 1.0 }
+    }
+}
+
+impl Default for AutomaticGainControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioProcessor for AutomaticGainControl {
+    fn process(&mut self, frame: &mut [f32]) {
+        if frame.is_empty() {
+            return;
+        }
+
+        let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+        let previous = self.gain;
+        if rms > SILENCE_RMS {
+            let desired = (TARGET_RMS / rms).clamp(MIN_GAIN, MAX_GAIN);
+            let rate = if desired < self.gain { ATTACK } else { RELEASE };
+            self.gain += rate * (desired - self.gain);
+        }
+
+        // Ramp across the frame so gain changes don't click
+        let step = (self.gain - previous) / frame.len() as f32;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            let gain = previous + step * (i + 1) as f32;
+            *sample = (*sample * gain).clamp(-1.0, 1.0);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.gain = 1.0;
+    }
+}
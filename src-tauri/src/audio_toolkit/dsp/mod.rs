@@ -0,0 +1,307 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::audio_toolkit::constants;
+
+/// Samples per 30-ms frame at 16 kHz, the frame size the recorder feeds the VAD.
+pub const FRAME_SAMPLES: usize = (constants::WHISPER_SAMPLE_RATE as usize * 30) / 1000;
+
+pub trait AudioProcessor: Send {
+    /// Processes one 16 kHz frame in place.
+    fn process(&mut self, frame: &mut [f32]);
+
+    /// Clears any state carried between frames.
+    fn reset(&mut self) {}
+
+    /// Returns output still held back once the input has ended, for
+    /// processors whose output lags their input.
+    fn flush(&mut self) -> Option<Vec<f32>> {
+        None
+    }
+}
+
+/// Which stages of the processing chain are enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DspConfig {
+    pub high_pass: bool,
+    pub noise_suppression: bool,
+    pub auto_gain: bool,
+}
+
+/// Ordered list of processors run on every frame before the VAD sees it.
+#[derive(Default)]
+pub struct ProcessingChain {
+    stages: Vec<Box<dyn AudioProcessor>>,
+}
+
+impl ProcessingChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_stage(mut self, stage: Box<dyn AudioProcessor>) -> Self {
+        self.stages.push(stage);
+        self
+    }
+
+    /// Builds the chain for `config`. The high-pass filter runs first so DC
+    /// offset and rumble don't skew the noise estimate, and gain control runs
+    /// last so it isn't pumped up by noise that is about to be removed.
+    pub fn from_config(config: &DspConfig) -> Self {
+        let mut chain = Self::new();
+        if config.high_pass {
+            chain = chain.with_stage(Box::new(HighPassFilter::new(
+                constants::WHISPER_SAMPLE_RATE,
+            )));
+        }
+        if config.noise_suppression {
+            chain = chain.with_stage(Box::new(NoiseSuppressor::new(FRAME_SAMPLES)));
+        }
+        if config.auto_gain {
+            chain = chain.with_stage(Box::new(AutomaticGainControl::new()));
+        }
+        chain
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    pub fn process(&mut self, frame: &mut [f32]) {
+        for stage in &mut self.stages {
+            stage.process(frame);
+        }
+    }
+
+    pub fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset();
+        }
+    }
+
+    /// Drains the frame a lagging stage still holds back once the input has
+    /// ended, run through the stages after it. Only the noise suppressor
+    /// lags, so one drained frame covers the whole chain.
+    pub fn flush(&mut self) -> Option<Vec<f32>> {
+        let mut drained: Option<Vec<f32>> = None;
+        for stage in &mut self.stages {
+            match drained.as_mut() {
+                Some(frame) => stage.process(frame),
+                None => drained = stage.flush(),
+            }
+        }
+        drained
+    }
+
+    /// Runs a whole buffer through the chain in `FRAME_SAMPLES` frames.
+    /// A trailing partial frame is zero-padded and trimmed from the output,
+    /// and the chain's lag is drained so output lines up with the input.
+    pub fn process_samples(&mut self, samples: &[f32]) -> Vec<f32> {
+        let mut out = Vec::with_capacity(samples.len() + FRAME_SAMPLES);
+        let mut frame = [0.0f32; FRAME_SAMPLES];
+        for chunk in samples.chunks(FRAME_SAMPLES) {
+            frame[..chunk.len()].copy_from_slice(chunk);
+            frame[chunk.len()..].fill(0.0);
+            self.process(&mut frame);
+            out.extend_from_slice(&frame);
+        }
+        if let Some(tail) = self.flush() {
+            // Drop the lead-in the lag added before the first real sample
+            out.drain(..tail.len().min(out.len()));
+            out.extend_from_slice(&tail);
+        }
+        out.truncate(samples.len());
+        out
+    }
+}
+
+/// Processes a 16 kHz mono WAV file offline, e.g. to tune the chain on fixtures.
+pub fn process_wav<P: AsRef<Path>, Q: AsRef<Path>>(
+    input: P,
+    output: Q,
+    config: &DspConfig,
+) -> Result<()> {
+    let samples = crate::audio_toolkit::audio::read_wav_file(input)?;
+
+    let processed = ProcessingChain::from_config(config).process_samples(&samples);
+    crate::audio_toolkit::audio::write_wav_file(output, &processed)?;
+    Ok(())
+}
+
+mod agc;
+mod high_pass;
+mod noise;
+
+pub use agc::AutomaticGainControl;
+pub use high_pass::HighPassFilter;
+pub use noise::NoiseSuppressor;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::test_signals::noise;
+    use std::f32::consts::PI;
+
+    const SAMPLE_RATE: f32 = constants::WHISPER_SAMPLE_RATE as f32;
+
+    fn sine(freq: f32, amplitude: f32, seconds: f32) -> Vec<f32> {
+        (0..(seconds * SAMPLE_RATE) as usize)
+            .map(|i| amplitude * (2.0 * PI * freq * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    /// Writes `samples` as a WAV fixture, runs it through the chain and reads the result.
+    fn process_fixture(samples: &[f32], config: DspConfig) -> Vec<f32> {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("input.wav");
+        let output = dir.path().join("output.wav");
+        crate::audio_toolkit::audio::write_wav_file(&input, samples).unwrap();
+
+        process_wav(&input, &output, &config).unwrap();
+
+        let mut reader = hound::WavReader::open(&output).unwrap();
+        reader
+            .samples::<i16>()
+            .map(|s| s.unwrap() as f32 / i16::MAX as f32)
+            .collect()
+    }
+
+    #[test]
+    fn empty_chain_passes_audio_through() {
+        let input = sine(440.0, 0.3, 0.5);
+        let output = process_fixture(&input, DspConfig::default());
+        assert_eq!(output.len(), input.len());
+        assert!(input.iter().zip(&output).all(|(a, b)| (a - b).abs() < 1e-3));
+    }
+
+    #[test]
+    fn high_pass_removes_dc_offset() {
+        let input: Vec<f32> = sine(440.0, 0.2, 1.0).iter().map(|s| s + 0.3).collect();
+        let output = process_fixture(
+            &input,
+            DspConfig {
+                high_pass: true,
+                ..Default::default()
+            },
+        );
+
+        let tail = &output[output.len() / 2..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        assert!(mean.abs() < 0.01, "mean {} still offset", mean);
+        assert!(rms(tail) > 0.12, "tone was attenuated");
+    }
+
+    #[test]
+    fn auto_gain_boosts_quiet_input() {
+        let input = sine(300.0, 0.01, 2.0);
+        let output = process_fixture(
+            &input,
+            DspConfig {
+                auto_gain: true,
+                ..Default::default()
+            },
+        );
+
+        let tail = &output[output.len() / 2..];
+        assert!(rms(tail) > 5.0 * rms(&input), "rms {}", rms(tail));
+        assert!(tail.iter().all(|s| s.abs() <= 1.0));
+    }
+
+    #[test]
+    fn auto_gain_attenuates_loud_input() {
+        let input = sine(300.0, 0.9, 2.0);
+        let output = process_fixture(
+            &input,
+            DspConfig {
+                auto_gain: true,
+                ..Default::default()
+            },
+        );
+
+        let tail = &output[output.len() / 2..];
+        assert!(rms(tail) < 0.5 * rms(&input), "rms {}", rms(tail));
+    }
+
+    #[test]
+    fn noise_suppression_reduces_stationary_noise() {
+        let input = noise(0.1, (2.0 * SAMPLE_RATE) as usize);
+        let output = process_fixture(
+            &input,
+            DspConfig {
+                noise_suppression: true,
+                ..Default::default()
+            },
+        );
+
+        let tail = &output[output.len() / 2..];
+        let before = rms(&input[input.len() / 2..]);
+        assert!(rms(tail) < 0.5 * before, "{} vs {}", rms(tail), before);
+    }
+
+    #[test]
+    fn noise_suppression_keeps_speech_onset() {
+        // One second of background noise, then a tone burst over the same noise
+        let mut input = noise(0.02, (1.5 * SAMPLE_RATE) as usize);
+        let tone = sine(800.0, 0.3, 0.5);
+        for (sample, t) in input[(SAMPLE_RATE as usize)..].iter_mut().zip(&tone) {
+            *sample += t;
+        }
+
+        let output = process_fixture(
+            &input,
+            DspConfig {
+                noise_suppression: true,
+                ..Default::default()
+            },
+        );
+
+        // Skip the first frames of the burst while the gains open up
+        let start = SAMPLE_RATE as usize + 2 * FRAME_SAMPLES;
+        let end = start + (0.25 * SAMPLE_RATE) as usize;
+        assert!(
+            rms(&output[start..end]) > 0.8 * rms(&tone),
+            "burst rms {}",
+            rms(&output[start..end])
+        );
+    }
+
+    #[test]
+    fn noise_suppression_pads_a_partial_last_frame() {
+        let input = noise(0.05, SAMPLE_RATE as usize);
+        let mut suppressor = NoiseSuppressor::new(FRAME_SAMPLES);
+        let full = input.len() / FRAME_SAMPLES - 1;
+        for frame in input[..full * FRAME_SAMPLES].chunks(FRAME_SAMPLES) {
+            suppressor.process(&mut frame.to_vec());
+        }
+
+        let tail = &input[full * FRAME_SAMPLES..][..FRAME_SAMPLES / 2];
+        let mut output = tail.to_vec();
+        suppressor.process(&mut output);
+        assert_eq!(output.len(), tail.len());
+        assert!(rms(&output) < 0.5 * rms(tail), "rms {}", rms(&output));
+    }
+
+    #[test]
+    fn noise_suppression_flushes_a_trailing_partial_frame() {
+        // Background noise, then a tone that only sounds in the last, partial frame
+        let mut input = noise(0.02, SAMPLE_RATE as usize);
+        input.truncate(input.len() / FRAME_SAMPLES * FRAME_SAMPLES);
+        let tone = sine(800.0, 0.3, 0.5);
+        let tone = &tone[..FRAME_SAMPLES / 2];
+        input.extend_from_slice(tone);
+
+        let output = ProcessingChain::from_config(&DspConfig {
+            noise_suppression: true,
+            ..Default::default()
+        })
+        .process_samples(&input);
+
+        assert_eq!(output.len(), input.len());
+        let tail = &output[output.len() - tone.len()..];
+        assert!(rms(tail) > 0.8 * rms(tone), "tail rms {}", rms(tail));
+    }
+}
@@ -0,0 +1,169 @@
+use rustfft::{num_complex::Complex32, Fft, FftPlanner};
+use std::sync::Arc;
+
+use super::AudioProcessor;
+
+/// Frames averaged at start-up to seed the noise estimate.
+const INIT_FRAMES: usize = 10;
+/// How quickly the per-bin noise estimate follows the signal when it drops
+/// below, sits near, or rises well above the current estimate.
+const NOISE_FALL: f32 = 0.1;
+const NOISE_RISE: f32 = 0.02;
+const NOISE_RISE_SPEECH: f32 = 0.001;
+/// Bins louder than this multiple of the noise estimate count as speech.
+const SPEECH_RATIO: f32 = 4.0;
+const OVER_SUBTRACTION: f32 = 2.0;
+/// Lowest gain applied to a bin (-20 dB), keeps residual noise natural.
+const GAIN_FLOOR: f32 = 0.1;
+const GAIN_SMOOTHING: f32 = 0.5;
+
+/// Spectral-gating noise suppressor.
+///
+/// Tracks a per-bin noise floor and attenuates bins close to it. Frames are
+/// analysed with 50% overlap (a window of two frames), so output lags the
+/// input by one frame; `flush` drains that frame once the input ends.
+pub struct NoiseSuppressor {
+    hop: usize,
+    fft: Arc<dyn Fft<f32>>,
+    ifft: Arc<dyn Fft<f32>>,
+    window: Vec<f32>,
+    input: Vec<f32>,
+    overlap: Vec<f32>,
+    spectrum: Vec<Complex32>,
+    noise: Vec<f32>,
+    gains: Vec<f32>,
+    frames_seen: usize,
+    /// Whether a processed frame is still held back in `overlap`
+    pending: bool,
+}
+
+impl NoiseSuppressor {
+    /// `frame_len` is the number of samples passed to each `process` call.
+    pub fn new(frame_len: usize) -> Self {
+        let size = frame_len * 2;
+        let mut planner = FftPlanner::<f32>::new();
+        // Square-root periodic Hann: analysis and synthesis windows together
+        // sum to one at 50% overlap
+        let window = (0..size)
+            .map(|i| {
+                (0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / size as f32).cos())).sqrt()
+            })
+            .collect();
+        let bins = size / 2 + 1;
+
+        Self {
+            hop: frame_len,
+            fft: planner.plan_fft_forward(size),
+            ifft: planner.plan_fft_inverse(size),
+            window,
+            input: vec![0.0; size],
+            overlap: vec![0.0; frame_len],
+            spectrum: vec![Complex32::new(0.0, 0.0); size],
+            noise: vec![0.0; bins],
+            gains: vec![1.0; bins],
+            frames_seen: 0,
+            pending: false,
+        }
+    }
+
+    fn update_noise(&mut self, bin: usize, power: f32) {
+        let noise = &mut self.noise[bin];
+        if self.frames_seen < INIT_FRAMES {
+            *noise += (power - *noise) / (self.frames_seen + 1) as f32;
+            return;
+        }
+        let rate = if power < *noise {
+            NOISE_FALL
+        } else if power < *noise * SPEECH_RATIO {
+            NOISE_RISE
+        } else {
+            NOISE_RISE_SPEECH
+        };
+        *noise += rate * (power - *noise);
+    }
+}
+
+impl AudioProcessor for NoiseSuppressor {
+    fn process(&mut self, frame: &mut [f32]) {
+        if frame.len() != self.hop {
+            // Usually the shorter last frame of a recording; partial chunks
+            // are padded with silence to a full frame
+            for chunk in frame.chunks_mut(self.hop) {
+                let mut padded = vec![0.0; self.hop];
+                padded[..chunk.len()].copy_from_slice(chunk);
+                self.process(&mut padded);
+                let len = chunk.len();
+                chunk.copy_from_slice(&padded[..len]);
+            }
+            return;
+        }
+
+        let size = self.input.len();
+        self.input.copy_within(self.hop.., 0);
+        self.input[size - self.hop..].copy_from_slice(frame);
+
+        for ((bin, &sample), &w) in self.spectrum.iter_mut().zip(&self.input).zip(&self.window) {
+            *bin = Complex32::new(sample * w, 0.0);
+        }
+        self.fft.process(&mut self.spectrum);
+
+        for k in 0..=size / 2 {
+            let power = self.spectrum[k].norm_sqr();
+            self.update_noise(k, power);
+
+            let gain = if power > 0.0 {
+                (1.0 - OVER_SUBTRACTION * self.noise[k] / power).max(GAIN_FLOOR)
+            } else {
+                GAIN_FLOOR
+            };
+            // Gains open at once so speech onsets come through, and close
+            // smoothly to avoid musical noise
+            let gain = if gain > self.gains[k] {
+                gain
+            } else {
+                GAIN_SMOOTHING * self.gains[k] + (1.0 - GAIN_SMOOTHING) * gain
+            };
+            self.gains[k] = gain;
+
+            self.spectrum[k] *= gain;
+            if k != 0 && k != size / 2 {
+                self.spectrum[size - k] *= gain;
+            }
+        }
+        self.frames_seen = self.frames_seen.saturating_add(1);
+
+        self.ifft.process(&mut self.spectrum);
+        let scale = 1.0 / size as f32;
+        for (i, sample) in frame.iter_mut().enumerate() {
+            *sample = self.spectrum[i].re * scale * self.window[i] + self.overlap[i];
+        }
+        for (i, tail) in self.overlap.iter_mut().enumerate() {
+            let j = i + self.hop;
+            *tail = self.spectrum[j].re * scale * self.window[j];
+        }
+        self.pending = true;
+    }
+
+    fn flush(&mut self) -> Option<Vec<f32>> {
+        if !self.pending {
+            return None;
+        }
+        // A silent frame completes the overlap-add of the last real one
+        let mut tail = vec![0.0; self.hop];
+        self.process(&mut tail);
+        // The silent frame wasn't input; whatever comes next starts afresh
+        self.input.fill(0.0);
+        self.overlap.fill(0.0);
+        self.pending = false;
+        Some(tail)
+    }
+
+    fn reset(&mut self) {
+        self.input.fill(0.0);
+        self.overlap.fill(0.0);
+        self.noise.fill(0.0);
+        self.gains.fill(1.0);
+        self.frames_seen = 0;
+        self.pending = false;
+    }
+}
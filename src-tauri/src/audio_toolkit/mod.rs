@@ -1,12 +1,18 @@
 pub mod audio;
 pub mod constants;
+pub mod dsp;
 pub mod text;
 pub mod utils;
 pub mod vad;
 
+#[cfg(test)]
+pub(crate) mod test_signals;
+
 pub use audio::{
-    list_input_devices, list_output_devices, save_wav_file, AudioRecorder, CpalDeviceInfo,
+    decode_audio, encode_audio, list_input_devices, list_output_devices, read_wav, read_wav_file,
+    save_wav_file, write_wav_file, AudioFormat, AudioRecorder, CpalDeviceInfo,
 };
-pub use text::{apply_custom_words, filter_transcription_output};
+pub use dsp::{DspConfig, ProcessingChain};
+pub use text::{apply_custom_words, filter_transcription_output, suggest_custom_words};
 pub use utils::get_cpal_host;
 pub use vad::{SileroVad, VoiceActivityDetector};
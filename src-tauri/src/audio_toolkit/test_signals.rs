@@ -0,0 +1,12 @@
+//! Signals shared by the audio toolkit's tests.
+
+/// Deterministic white noise in [-amplitude, amplitude], `len` samples long.
+pub fn noise(amplitude: f32, len: usize) -> Vec<f32> {
+    let mut state: u32 = 0x1234_5678;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            amplitude * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+        })
+        .collect()
+}
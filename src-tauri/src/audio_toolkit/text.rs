@@ -283,10 +283,124 @@ pub fn filter_transcription_output(text: &str) -> String {
     filtered.trim().to_string()
 }
 
+/// Longest common subsequence of two word lists, as pairs of matching indices
+fn matching_words(a: &[String], b: &[String]) -> Vec<(usize, usize)> {
+    let mut lengths = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Suggests custom words from a user's correction of a transcription
+///
+/// Compares the texts word by word and looks at the words the user typed in
+/// place of transcribed ones. Words that look like vocabulary the model gets
+/// wrong (names, brands, acronyms: anything with capitals or digits, except a
+/// capital at the start of a sentence) are suggested. Inserted words without
+/// a replaced counterpart and plain lowercase fixes ("there" -> "their") are not.
+///
+/// # Arguments
+/// * `original` - The text before the correction
+/// * `corrected` - The text after the correction
+/// * `custom_words` - Existing custom words, which are never suggested again
+///
+/// # Returns
+/// Suggested words in the order they appear in the corrected text
+pub fn suggest_custom_words(
+    original: &str,
+    corrected: &str,
+    custom_words: &[String],
+) -> Vec<String> {
+    const MAX_WORDS: usize = 2000;
+
+    let original_words: Vec<&str> = original.split_whitespace().take(MAX_WORDS).collect();
+    let corrected_words: Vec<&str> = corrected.split_whitespace().take(MAX_WORDS).collect();
+    let normalize =
+        |words: &[&str]| -> Vec<String> { words.iter().map(|w| build_ngram(&[w])).collect() };
+
+    let mut pairs = matching_words(&normalize(&original_words), &normalize(&corrected_words));
+    pairs.push((original_words.len(), corrected_words.len()));
+
+    let mut suggestions: Vec<String> = Vec::new();
+    let (mut prev_i, mut prev_j) = (0, 0);
+    for (i, j) in pairs {
+        // Words in corrected[prev_j..j] replace original[prev_i..i]
+        if i > prev_i {
+            for k in prev_j..j {
+                let word = corrected_words[k].trim_matches(|c: char| !c.is_alphanumeric());
+                let sentence_start = k == 0 || corrected_words[k - 1].ends_with(['.', '!', '?']);
+                let mut chars = word.chars();
+                let capitalized_only = chars.next().is_some_and(char::is_uppercase)
+                    && !chars.any(|c| c.is_uppercase() || c.is_numeric());
+                let looks_like_vocabulary =
+                    word.chars().any(|c| c.is_uppercase() || c.is_numeric())
+                        && !(sentence_start && capitalized_only);
+
+                let already_known = custom_words
+                    .iter()
+                    .chain(suggestions.iter())
+                    .any(|w| w.eq_ignore_ascii_case(word));
+                if word.chars().count() >= 2
+                    && word.chars().any(char::is_alphabetic)
+                    && looks_like_vocabulary
+                    && !already_known
+                {
+                    suggestions.push(word.to_string());
+                }
+            }
+        }
+        prev_i = i + 1;
+        prev_j = j + 1;
+    }
+
+    suggestions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_suggest_custom_words_from_replaced_words() {
+        let original = "we deploy to cooper netties with get hub actions.";
+        let corrected = "we deploy to Kubernetes with GitHub actions.";
+        let suggestions = suggest_custom_words(original, corrected, &[]);
+        assert_eq!(suggestions, vec!["Kubernetes", "GitHub"]);
+    }
+
+    #[test]
+    fn test_suggest_custom_words_skips_known_and_plain_fixes() {
+        let original = "their going to ship the hand e build. so cool";
+        let corrected = "they're going to ship the Handy build. So cool";
+        let existing = vec!["handy".to_string()];
+        assert!(suggest_custom_words(original, corrected, &existing).is_empty());
+
+        // Inserted words without a replaced counterpart aren't corrections
+        let suggestions = suggest_custom_words("call me", "call me ASAP", &[]);
+        assert!(suggestions.is_empty());
+    }
+
     #[test]
     fn test_apply_custom_words_exact_match() {
         let text = "hello world";
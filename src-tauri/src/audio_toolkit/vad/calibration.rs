@@ -0,0 +1,138 @@
+//! Suggests VAD parameters from a few seconds of the room with nobody talking.
+
+use anyhow::Result;
+
+use super::frame_db;
+use crate::audio_toolkit::dsp::FRAME_SAMPLES;
+
+/// Shortest ambient sample worth calibrating on (0.3 s).
+const MIN_FRAMES: usize = 10;
+/// Headroom added above the loudest ambient frames.
+const ENERGY_HEADROOM_DB: f32 = 6.0;
+const PROBABILITY_HEADROOM: f32 = 0.15;
+/// Frames of lead-in kept ahead of the onset, so the first syllable survives (~400 ms).
+const LEAD_IN_FRAMES: usize = 13;
+
+/// What the ambient sample looked like and the parameters suggested for it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VadCalibration {
+    /// Median frame level
+    pub noise_floor_db: f32,
+    /// 95th percentile frame level
+    pub noise_peak_db: f32,
+    /// 95th percentile Silero speech probability, when probabilities were given
+    pub noise_probability: Option<f32>,
+    pub threshold: Option<f32>,
+    pub energy_margin_db: f32,
+    pub onset_frames: usize,
+    pub prefill_frames: usize,
+}
+
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    let idx = ((sorted.len() - 1) as f32 * p).round() as usize;
+    sorted[idx]
+}
+
+/// Longest stretch of consecutive `true` values.
+fn longest_run(flags: impl Iterator<Item = bool>) -> usize {
+    let (mut longest, mut current) = (0, 0);
+    for flag in flags {
+        current = if flag { current + 1 } else { 0 };
+        longest = longest.max(current);
+    }
+    longest
+}
+
+/// Scores one 30-ms frame with a model, returning its speech probability.
+pub type FrameScorer<'a> = &'a mut dyn FnMut(&[f32]) -> Result<f32>;
+
+/// Calibrates on 16 kHz ambient audio. `probability` scores each 30-ms frame
+/// with the Silero model; without it only the energy parameters are derived.
+///
+/// Thresholds are set just above the loudest ambient frames, and the onset is
+/// made longer than any burst (keyboard clicks, door slams) that still crosses
+/// them, so only sustained sound opens the gate.
+pub fn calibrate(samples: &[f32], mut probability: Option<FrameScorer>) -> Result<VadCalibration> {
+    let frames: Vec<&[f32]> = samples.chunks_exact(FRAME_SAMPLES).collect();
+    if frames.len() < MIN_FRAMES {
+        anyhow::bail!("need at least {} ms of audio to calibrate", MIN_FRAMES * 30);
+    }
+
+    let levels: Vec<f32> = frames.iter().map(|f| frame_db(f)).collect();
+    let mut sorted = levels.clone();
+    sorted.sort_by(f32::total_cmp);
+    let noise_floor_db = percentile(&sorted, 0.5);
+    let noise_peak_db = percentile(&sorted, 0.95);
+    let energy_margin_db = (noise_peak_db - noise_floor_db + ENERGY_HEADROOM_DB).clamp(6.0, 30.0);
+    let mut onset_run = longest_run(
+        levels
+            .iter()
+            .map(|db| *db > noise_floor_db + energy_margin_db),
+    );
+
+    let mut noise_probability = None;
+    let mut threshold = None;
+    if let Some(score) = probability.as_mut() {
+        let probs = frames
+            .iter()
+            .map(|f| score(f))
+            .collect::<Result<Vec<f32>>>()?;
+        let mut sorted = probs.clone();
+        sorted.sort_by(f32::total_cmp);
+        let peak = percentile(&sorted, 0.95);
+        let suggested = (peak + PROBABILITY_HEADROOM).clamp(0.2, 0.9);
+
+        onset_run = onset_run.max(longest_run(probs.iter().map(|p| *p > suggested)));
+        noise_probability = Some(peak);
+        threshold = Some(suggested);
+    }
+
+    let onset_frames = (onset_run + 1).clamp(2, 10);
+    Ok(VadCalibration {
+        noise_floor_db,
+        noise_peak_db,
+        noise_probability,
+        threshold,
+        energy_margin_db,
+        onset_frames,
+        prefill_frames: onset_frames + LEAD_IN_FRAMES,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_toolkit::test_signals::noise;
+
+    #[test]
+    fn rejects_short_samples() {
+        assert!(calibrate(&noise(0.01, (MIN_FRAMES - 1) * FRAME_SAMPLES), None).is_err());
+    }
+
+    #[test]
+    fn steady_noise_keeps_defaults_tight() {
+        let calibration = calibrate(&noise(0.01, 100 * FRAME_SAMPLES), None).unwrap();
+        assert!((-50.0..-40.0).contains(&calibration.noise_floor_db));
+        assert!(calibration.energy_margin_db < 10.0);
+        assert_eq!(calibration.onset_frames, 2);
+        assert!(calibration.threshold.is_none());
+    }
+
+    #[test]
+    fn clicks_lengthen_the_onset() {
+        let mut samples = noise(0.005, 200 * FRAME_SAMPLES);
+        // Three-frame bursts, like a keyboard clatter
+        for start in [40, 100, 160] {
+            for s in &mut samples[start * FRAME_SAMPLES..(start + 3) * FRAME_SAMPLES] {
+                *s *= 40.0;
+            }
+        }
+
+        let mut score = |frame: &[f32]| Ok(if frame_db(frame) > -30.0 { 0.6 } else { 0.05 });
+        let calibration = calibrate(&samples, Some(&mut score)).unwrap();
+
+        assert_eq!(calibration.onset_frames, 4);
+        assert_eq!(calibration.prefill_frames, 4 + LEAD_IN_FRAMES);
+        assert!(calibration.threshold.unwrap() < 0.6);
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+
+use super::{VadFrame, VoiceActivityDetector};
+
+/// Frames quieter than this are never speech, whatever the noise floor.
+const MIN_SPEECH_DB: f32 = -55.0;
+/// The floor never tracks below this, so digital silence can't make
+/// the faintest sound look like speech.
+const LOWEST_FLOOR_DB: f32 = -100.0;
+/// Per-frame adaptation of the noise floor when the level drops below it,
+/// sits within the margin above it, or is loud enough to be speech (barely,
+/// so long utterances don't raise the floor).
+const FLOOR_FALL: f32 = 0.2;
+const FLOOR_RISE: f32 = 0.05;
+const FLOOR_RISE_SPEECH: f32 = 0.002;
+
+/// Returns the RMS level of `frame` in dBFS.
+pub fn frame_db(frame: &[f32]) -> f32 {
+    if frame.is_empty() {
+        return f32::NEG_INFINITY;
+    }
+    let mean_square = frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32;
+    10.0 * mean_square.max(1e-12).log10()
+}
+
+/// Lightweight VAD that flags frames rising a margin above an adaptive
+/// noise floor. No model to load, so it suits low-power machines; pair it
+/// with `SmoothedVad` to ride over short clicks and gaps.
+pub struct EnergyVad {
+    margin_db: f32,
+    /// Seeded from the first frame seen
+    noise_floor_db: Option<f32>,
+}
+
+impl EnergyVad {
+    pub fn new(margin_db: f32) -> Result<Self> {
+        if !(0.0..=60.0).contains(&margin_db) {
+            anyhow::bail!("margin must be between 0 and 60 dB");
+        }
+
+        Ok(Self {
+            margin_db,
+            noise_floor_db: None,
+        })
+    }
+}
+
+impl VoiceActivityDetector for EnergyVad {
+    fn push_frame<'a>(&'a mut self, frame: &'a [f32]) -> Result<VadFrame<'a>> {
+        let db = frame_db(frame).max(LOWEST_FLOOR_DB);
+        let floor = self.noise_floor_db.get_or_insert(db);
+        let is_speech = db > MIN_SPEECH_DB && db > *floor + self.margin_db;
+
+        let rate = if db < *floor {
+            FLOOR_FALL
+        } else if is_speech {
+            FLOOR_RISE_SPEECH
+        } else {
+            FLOOR_RISE
+        };
+        *floor += rate * (db - *floor);
+
+        if is_speech {
+            Ok(VadFrame::Speech(frame))
+        } else {
+            Ok(VadFrame::Noise)
+        }
+    }
+
+    // The noise floor describes the room rather than the utterance, so it is
+    // kept across `reset` calls.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32) -> Vec<f32> {
+        (0..480)
+            .map(|i| amplitude * (i as f32 * 0.2).sin())
+            .collect()
+    }
+
+    #[test]
+    fn flags_frames_above_the_noise_floor() {
+        let mut vad = EnergyVad::new(9.0).unwrap();
+        for _ in 0..20 {
+            assert!(!vad.is_voice(&tone(0.005)).unwrap());
+        }
+        assert!(vad.is_voice(&tone(0.2)).unwrap());
+        assert!(!vad.is_voice(&tone(0.006)).unwrap());
+    }
+
+    #[test]
+    fn ignores_digital_silence() {
+        let mut vad = EnergyVad::new(9.0).unwrap();
+        for _ in 0..20 {
+            assert!(!vad.is_voice(&[0.0; 480]).unwrap());
+        }
+        assert!(!vad.is_voice(&tone(0.0005)).unwrap());
+    }
+}
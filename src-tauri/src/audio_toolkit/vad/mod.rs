@@ -25,8 +25,12 @@ pub trait VoiceActivityDetector: Send + Sync {
     fn reset(&mut self) {}
 }
 
+mod calibration;
+mod energy;
 mod silero;
 mod smoothed;
 
+pub use calibration::{calibrate, FrameScorer, VadCalibration};
+pub use energy::{frame_db, EnergyVad};
 pub use silero::SileroVad;
 pub use smoothed::SmoothedVad;
@@ -0,0 +1,199 @@
+//! Automatic end of toggle-mode and hands-free recordings
+//!
+//! A recording can end on its own after a stretch of trailing silence (decided
+//! by the recorder's VAD) or once it reaches a maximum duration. Either way it
+//! goes through the binding's regular `stop` action. While a stop is coming
+//! up, the recording overlay shows a countdown.
+
+use log::debug;
+use once_cell::sync::Lazy;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+
+use crate::actions::ACTION_MAP;
+use crate::audio_toolkit::audio::SpeechEvent;
+use crate::utils::emit_countdown;
+
+/// The overlay starts counting down this many seconds before a stop
+const COUNTDOWN_SECS: u64 = 5;
+const TICK_INTERVAL: Duration = Duration::from_millis(250);
+
+struct Session {
+    id: u64,
+    binding_id: String,
+    shortcut: String,
+    started_at: Instant,
+    max_duration: Option<Duration>,
+    silence_timeout: Option<Duration>,
+    silent_since: Option<Instant>,
+}
+
+impl Session {
+    /// Time left until whichever limit is hit first
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        let until_max = self
+            .max_duration
+            .map(|max| max.saturating_sub(now - self.started_at));
+        let until_silence = self
+            .silence_timeout
+            .zip(self.silent_since)
+            .map(|(timeout, since)| timeout.saturating_sub(now - since));
+
+        match (until_max, until_silence) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    fn max_duration_reached(&self, now: Instant) -> bool {
+        self.max_duration
+            .is_some_and(|max| now - self.started_at >= max)
+    }
+}
+
+static SESSION: Lazy<Mutex<Option<Session>>> = Lazy::new(|| Mutex::new(None));
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Starts tracking a recording that may end on its own.
+///
+/// The silence timeout is only used for the countdown; the recorder must be
+/// watching for silence itself (see `AudioRecordingManager::watch_silence`).
+pub fn begin(
+    app: &AppHandle,
+    binding_id: &str,
+    shortcut: &str,
+    silence_timeout: Option<Duration>,
+    max_duration: Option<Duration>,
+) {
+    if silence_timeout.is_none() && max_duration.is_none() {
+        return;
+    }
+
+    let id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+    *SESSION.lock().unwrap() = Some(Session {
+        id,
+        binding_id: binding_id.to_string(),
+        shortcut: shortcut.to_string(),
+        started_at: Instant::now(),
+        max_duration,
+        silence_timeout,
+        silent_since: None,
+    });
+    debug!(
+        "Auto-stop armed for '{}' (silence: {:?}, max: {:?})",
+        binding_id, silence_timeout, max_duration
+    );
+
+    let app = app.clone();
+    thread::spawn(move || run_ticker(app, id));
+}
+
+/// Stops tracking the current recording, e.g. when it was stopped by hand.
+pub fn end(app: &AppHandle) {
+    if SESSION.lock().unwrap().take().is_some() {
+        emit_countdown(app, None);
+    }
+}
+
+/// Handles silence state reported by the recorder.
+///
+/// Called on the audio thread, so stopping is moved to a separate thread.
+pub fn handle_speech_event(app: &AppHandle, event: SpeechEvent) {
+    match event {
+        SpeechEvent::Paused | SpeechEvent::Resumed => {
+            if let Some(session) = SESSION.lock().unwrap().as_mut() {
+                session.silent_since = match event {
+                    SpeechEvent::Paused => Some(Instant::now()),
+                    _ => None,
+                };
+            }
+        }
+        SpeechEvent::Ended => {
+            debug!("Trailing silence reached, stopping recording");
+            let app = app.clone();
+            thread::spawn(move || stop_current(&app));
+        }
+        SpeechEvent::Started => {}
+    }
+}
+
+fn run_ticker(app: AppHandle, id: u64) {
+    let mut last_shown: Option<u64> = None;
+    loop {
+        thread::sleep(TICK_INTERVAL);
+
+        let now = Instant::now();
+        let (remaining, max_reached) = {
+            let guard = SESSION.lock().unwrap();
+            match guard.as_ref() {
+                Some(session) if session.id == id => {
+                    (session.remaining(now), session.max_duration_reached(now))
+                }
+                _ => return,
+            }
+        };
+
+        if max_reached {
+            debug!("Maximum recording duration reached, stopping recording");
+            stop_current(&app);
+            return;
+        }
+
+        let shown = remaining
+            .map(|r| (r.as_millis() as u64).div_ceil(1000))
+            .filter(|secs| *secs <= COUNTDOWN_SECS);
+        if shown != last_shown {
+            emit_countdown(&app, shown);
+            last_shown = shown;
+        }
+    }
+}
+
+/// Ends the tracked recording through its binding's regular stop action.
+fn stop_current(app: &AppHandle) {
+    let Some(session) = SESSION.lock().unwrap().take() else {
+        return;
+    };
+    emit_countdown(app, None);
+
+    if let Some(action) = ACTION_MAP.get(&session.binding_id) {
+        action.stop(app, &session.binding_id, &session.shortcut);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(max: Option<u64>, silence: Option<u64>) -> Session {
+        Session {
+            id: 0,
+            binding_id: "transcribe".to_string(),
+            shortcut: "test".to_string(),
+            started_at: Instant::now(),
+            max_duration: max.map(Duration::from_secs),
+            silence_timeout: silence.map(Duration::from_secs),
+            silent_since: None,
+        }
+    }
+
+    #[test]
+    fn remaining_uses_max_duration_until_silence_starts() {
+        let s = session(Some(60), Some(3));
+        let now = s.started_at + Duration::from_secs(10);
+        assert_eq!(s.remaining(now), Some(Duration::from_secs(50)));
+    }
+
+    #[test]
+    fn remaining_picks_the_nearest_limit() {
+        let mut s = session(Some(60), Some(3));
+        s.silent_since = Some(s.started_at + Duration::from_secs(10));
+        let now = s.started_at + Duration::from_secs(11);
+        assert_eq!(s.remaining(now), Some(Duration::from_secs(2)));
+        assert!(!s.max_duration_reached(now));
+        assert!(s.max_duration_reached(s.started_at + Duration::from_secs(60)));
+    }
+}
@@ -0,0 +1,341 @@
+//! Backup archives: a gzipped tarball with a manifest, the history database,
+//! the settings and a selection of recordings.
+
+use crate::settings::AppSettings;
+use anyhow::{bail, Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use log::warn;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use tar::{Archive, Builder, Header};
+
+/// Bumped when the archive layout changes incompatibly.
+pub const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const SETTINGS_FILE: &str = "settings.json";
+const DATABASE_FILE: &str = "history.db";
+const RECORDINGS_DIR: &str = "recordings";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub created_at: i64,
+    /// Whether the settings in the archive still hold the API keys
+    pub includes_api_keys: bool,
+    pub recordings: Vec<String>,
+}
+
+/// A backup unpacked into a staging directory.
+pub struct UnpackedBackup {
+    pub manifest: BackupManifest,
+    pub settings: AppSettings,
+    pub database: PathBuf,
+    /// File names of the recordings with the paths they were unpacked to
+    pub recordings: Vec<(String, PathBuf)>,
+}
+
+#[derive(Debug, PartialEq)]
+enum ArchiveItem {
+    Manifest,
+    Settings,
+    Database,
+    Recording(String),
+}
+
+/// What an archive path holds. Anything else, including paths that would
+/// escape the staging directory, is ignored.
+fn classify(path: &Path) -> Option<ArchiveItem> {
+    let components: Vec<&str> = path
+        .components()
+        .map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+
+    match components.as_slice() {
+        [MANIFEST_FILE] => Some(ArchiveItem::Manifest),
+        [SETTINGS_FILE] => Some(ArchiveItem::Settings),
+        [DATABASE_FILE] => Some(ArchiveItem::Database),
+        [RECORDINGS_DIR, name] => Some(ArchiveItem::Recording(name.to_string())),
+        _ => None,
+    }
+}
+
+/// The settings to put into a backup, with the API keys emptied unless
+/// they're explicitly included.
+pub fn settings_for_backup(mut settings: AppSettings, include_api_keys: bool) -> AppSettings {
+    if !include_api_keys {
+        settings
+            .post_process_api_keys
+            .values_mut()
+            .for_each(String::clear);
+    }
+    settings
+}
+
+/// Combines the local settings with those from a backup. With `replace` the
+/// backup's settings win, but API keys it doesn't carry are kept; otherwise
+/// the local settings stay. Either way custom words and prompts from both
+/// are kept.
+pub fn merge_settings(local: AppSettings, backup: AppSettings, replace: bool) -> AppSettings {
+    let (mut merged, other) = if replace {
+        (backup, local)
+    } else {
+        (local, backup)
+    };
+
+    for word in other.custom_words {
+        if !merged
+            .custom_words
+            .iter()
+            .any(|w| w.eq_ignore_ascii_case(&word))
+        {
+            merged.custom_words.push(word);
+        }
+    }
+    for prompt in other.post_process_prompts {
+        if !merged
+            .post_process_prompts
+            .iter()
+            .any(|p| p.id == prompt.id)
+        {
+            merged.post_process_prompts.push(prompt);
+        }
+    }
+    if replace {
+        for (provider, key) in other.post_process_api_keys {
+            let merged_key = merged.post_process_api_keys.entry(provider).or_default();
+            if merged_key.is_empty() {
+                *merged_key = key;
+            }
+        }
+    }
+
+    merged
+}
+
+/// Writes a backup to `path` and returns its size. The archive is written
+/// next to it first, so a failed backup never leaves a truncated file.
+pub fn write_archive(
+    path: &Path,
+    manifest: &BackupManifest,
+    settings: &AppSettings,
+    database: &Path,
+    recordings_dir: &Path,
+) -> Result<u64> {
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+
+    let written = write_archive_to(&partial, manifest, settings, database, recordings_dir);
+    if written.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    written?;
+
+    fs::rename(&partial, path)?;
+    Ok(fs::metadata(path)?.len())
+}
+
+fn write_archive_to(
+    path: &Path,
+    manifest: &BackupManifest,
+    settings: &AppSettings,
+    database: &Path,
+    recordings_dir: &Path,
+) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("Failed to create {:?}", path))?;
+    let mut builder = Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let mtime = manifest.created_at.max(0) as u64;
+    append_bytes(
+        &mut builder,
+        MANIFEST_FILE,
+        &serde_json::to_vec_pretty(manifest)?,
+        mtime,
+    )?;
+    append_bytes(
+        &mut builder,
+        SETTINGS_FILE,
+        &serde_json::to_vec_pretty(settings)?,
+        mtime,
+    )?;
+    builder.append_path_with_name(database, DATABASE_FILE)?;
+    for name in &manifest.recordings {
+        builder
+            .append_path_with_name(
+                recordings_dir.join(name),
+                format!("{}/{}", RECORDINGS_DIR, name),
+            )
+            .with_context(|| format!("Failed to add recording {}", name))?;
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok(())
+}
+
+fn append_bytes<W: Write>(
+    builder: &mut Builder<W>,
+    name: &str,
+    data: &[u8],
+    mtime: u64,
+) -> Result<()> {
+    let mut header = Header::new_gnu();
+    header.set_size(data.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+    builder.append_data(&mut header, name, data)?;
+    Ok(())
+}
+
+/// Unpacks a backup into `staging_dir`, which should be empty.
+pub fn unpack_archive(path: &Path, staging_dir: &Path) -> Result<UnpackedBackup> {
+    let file = File::open(path).with_context(|| format!("Failed to open {:?}", path))?;
+    let mut archive = Archive::new(GzDecoder::new(file));
+    fs::create_dir_all(staging_dir.join(RECORDINGS_DIR))?;
+
+    let mut manifest = None;
+    let mut settings = None;
+    let mut database = None;
+    let mut recordings = Vec::new();
+
+    for entry in archive.entries().context("Not a valid backup archive")? {
+        let mut entry = entry?;
+        let entry_path = entry.path()?.into_owned();
+        if !entry.header().entry_type().is_file() {
+            // Links could point outside the staging directory
+            warn!("Ignoring non-file backup entry {:?}", entry_path);
+            continue;
+        }
+        match classify(&entry_path) {
+            Some(ArchiveItem::Manifest) => {
+                let mut json = String::new();
+                entry.read_to_string(&mut json)?;
+                manifest = Some(serde_json::from_str::<BackupManifest>(&json)?);
+            }
+            Some(ArchiveItem::Settings) => {
+                let mut json = String::new();
+                entry.read_to_string(&mut json)?;
+                settings = Some(
+                    serde_json::from_str::<AppSettings>(&json)
+                        .context("Failed to read the backed up settings")?,
+                );
+            }
+            Some(ArchiveItem::Database) => {
+                let target = staging_dir.join(DATABASE_FILE);
+                entry.unpack(&target)?;
+                database = Some(target);
+            }
+            Some(ArchiveItem::Recording(name)) => {
+                let target = staging_dir.join(RECORDINGS_DIR).join(&name);
+                entry.unpack(&target)?;
+                recordings.push((name, target));
+            }
+            None => warn!("Ignoring unexpected backup entry {:?}", entry_path),
+        }
+    }
+
+    let manifest = manifest.context("The backup has no manifest")?;
+    if manifest.format_version > FORMAT_VERSION {
+        bail!("This backup was made by a newer version of the app");
+    }
+
+    Ok(UnpackedBackup {
+        manifest,
+        settings: settings.context("The backup has no settings")?,
+        database: database.context("The backup has no history database")?,
+        recordings,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::{get_default_settings, LLMPrompt};
+
+    #[test]
+    fn only_known_paths_are_unpacked() {
+        assert_eq!(
+            classify(Path::new("manifest.json")),
+            Some(ArchiveItem::Manifest)
+        );
+        assert_eq!(
+            classify(Path::new("recordings/typezero-1.wav")),
+            Some(ArchiveItem::Recording("typezero-1.wav".to_string()))
+        );
+        assert_eq!(classify(Path::new("recordings/../../evil.wav")), None);
+        assert_eq!(classify(Path::new("/history.db")), None);
+        assert_eq!(classify(Path::new("recordings/nested/a.wav")), None);
+    }
+
+    #[test]
+    fn archive_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let recordings_dir = dir.path().join("recordings");
+        fs::create_dir_all(&recordings_dir).unwrap();
+        fs::write(recordings_dir.join("typezero-1.wav"), b"RIFF").unwrap();
+        let database = dir.path().join("snapshot.db");
+        fs::write(&database, b"SQLite").unwrap();
+
+        let manifest = BackupManifest {
+            format_version: FORMAT_VERSION,
+            app_version: "1.0.0".to_string(),
+            created_at: 100,
+            includes_api_keys: false,
+            recordings: vec!["typezero-1.wav".to_string()],
+        };
+        let mut settings = get_default_settings();
+        settings.custom_words = vec!["Kubernetes".to_string()];
+        let archive = dir.path().join("backup.tar.gz");
+        write_archive(&archive, &manifest, &settings, &database, &recordings_dir).unwrap();
+
+        let staging = dir.path().join("staging");
+        let unpacked = unpack_archive(&archive, &staging).unwrap();
+        assert_eq!(unpacked.manifest.recordings, manifest.recordings);
+        assert_eq!(unpacked.settings.custom_words, ["Kubernetes"]);
+        assert_eq!(fs::read(&unpacked.database).unwrap(), b"SQLite");
+        assert_eq!(unpacked.recordings.len(), 1);
+        assert_eq!(fs::read(&unpacked.recordings[0].1).unwrap(), b"RIFF");
+    }
+
+    #[test]
+    fn merging_keeps_words_prompts_and_local_keys() {
+        let mut local = get_default_settings();
+        local.custom_words = vec!["TypeZero".to_string()];
+        local
+            .post_process_api_keys
+            .insert("openai".to_string(), "local-key".to_string());
+
+        let mut backup = settings_for_backup(local.clone(), false);
+        backup.custom_words = vec!["typezero".to_string(), "Kubernetes".to_string()];
+        backup.post_process_prompts.push(LLMPrompt {
+            id: "backup_prompt".to_string(),
+            name: "Backup".to_string(),
+            prompt: "${output}".to_string(),
+        });
+        assert_eq!(backup.post_process_api_keys["openai"], "");
+
+        let merged = merge_settings(local.clone(), backup.clone(), true);
+        assert_eq!(merged.custom_words, ["typezero", "Kubernetes"]);
+        assert_eq!(merged.post_process_api_keys["openai"], "local-key");
+        assert!(merged
+            .post_process_prompts
+            .iter()
+            .any(|p| p.id == "backup_prompt"));
+
+        let kept = merge_settings(local, backup, false);
+        assert_eq!(kept.custom_words, ["TypeZero", "Kubernetes"]);
+        assert!(kept
+            .post_process_prompts
+            .iter()
+            .any(|p| p.id == "backup_prompt"));
+    }
+}
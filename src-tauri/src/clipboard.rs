@@ -3,16 +3,179 @@ use crate::input::{self, EnigoState};
 use crate::settings::TypingTool;
 use crate::settings::{get_settings, AutoSubmitKey, ClipboardHandling, PasteMethod};
 use enigo::{Direction, Enigo, Key, Keyboard};
-use log::info;
+use log::{info, warn};
+use std::path::PathBuf;
+use std::sync::Mutex;
 use std::time::Duration;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_clipboard_manager::ClipboardExt;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[cfg(target_os = "linux")]
 use crate::utils::{is_kde_wayland, is_wayland};
 #[cfg(target_os = "linux")]
 use std::process::Command;
 
+/// What the most recent `paste` inserted, so it can be undone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LastInsertion {
+    /// Length in grapheme clusters, i.e. the number of backspaces that erase it
+    chars: usize,
+    method: PasteMethod,
+    auto_submitted: bool,
+}
+
+static LAST_INSERTION: Mutex<Option<LastInsertion>> = Mutex::new(None);
+
+/// Keys that remove an insertion again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UndoKeys {
+    /// Typed text is erased character by character
+    Backspaces(usize),
+    /// A paste is a single step in the target app's undo history
+    UndoCombo,
+}
+
+fn undo_keys(insertion: &LastInsertion) -> Result<Option<UndoKeys>, String> {
+    if insertion.auto_submitted {
+        return Err("The last dictation was already submitted and can't be undone".into());
+    }
+    Ok(match insertion.method {
+        PasteMethod::None => None,
+        PasteMethod::Direct => Some(UndoKeys::Backspaces(insertion.chars)),
+        PasteMethod::CtrlV | PasteMethod::CtrlShiftV | PasteMethod::ShiftInsert => {
+            Some(UndoKeys::UndoCombo)
+        }
+    })
+}
+
+/// Clipboard contents saved before a clipboard paste and put back after it,
+/// so dictating doesn't clobber an image, file list or HTML the user copied.
+enum ClipboardSnapshot {
+    Empty,
+    Text(String),
+    Html {
+        html: String,
+        alt_text: Option<String>,
+    },
+    Image(arboard::ImageData<'static>),
+    Files(Vec<PathBuf>),
+    /// Selection read with wl-paste, one entry per MIME type the source
+    /// offered, richest first, so an HTML copy keeps its plain-text flavour.
+    #[cfg(target_os = "linux")]
+    Wayland(Vec<(String, Vec<u8>)>),
+}
+
+impl ClipboardSnapshot {
+    fn capture() -> Self {
+        // wl-paste ships with wl-copy
+        #[cfg(target_os = "linux")]
+        if is_wayland() && is_wl_copy_available() {
+            return match read_clipboard_via_wl_paste() {
+                Ok(contents) if contents.is_empty() => ClipboardSnapshot::Empty,
+                Ok(contents) => ClipboardSnapshot::Wayland(contents),
+                Err(e) => {
+                    warn!("Failed to save clipboard with wl-paste: {}", e);
+                    ClipboardSnapshot::Empty
+                }
+            };
+        }
+
+        // The clipboard plugin keeps its own arboard instance alive for the
+        // app's lifetime, so this one can be dropped without losing ownership
+        // of the selection on X11.
+        let mut clipboard = match arboard::Clipboard::new() {
+            Ok(clipboard) => clipboard,
+            Err(e) => {
+                warn!("Failed to open clipboard: {}", e);
+                return ClipboardSnapshot::Empty;
+            }
+        };
+
+        if let Ok(files) = clipboard.get().file_list() {
+            if !files.is_empty() {
+                return ClipboardSnapshot::Files(files);
+            }
+        }
+        if let Ok(image) = clipboard.get_image() {
+            return ClipboardSnapshot::Image(image);
+        }
+        let text = clipboard.get_text().ok();
+        if let Ok(html) = clipboard.get().html() {
+            return ClipboardSnapshot::Html {
+                html,
+                alt_text: text,
+            };
+        }
+        match text {
+            Some(text) => ClipboardSnapshot::Text(text),
+            None => ClipboardSnapshot::Empty,
+        }
+    }
+
+    fn restore(self) -> Result<(), String> {
+        #[cfg(target_os = "linux")]
+        if is_wayland() && is_wl_copy_available() {
+            return match self {
+                ClipboardSnapshot::Wayland(contents) => write_wayland_clipboard(contents),
+                // Capture reads only with wl-paste here, so nothing was copied
+                _ => clear_clipboard_via_wl_copy(),
+            };
+        }
+
+        let mut clipboard =
+            arboard::Clipboard::new().map_err(|e| format!("Failed to open clipboard: {}", e))?;
+        let result = match self {
+            ClipboardSnapshot::Empty => clipboard.clear(),
+            ClipboardSnapshot::Text(text) => clipboard.set_text(text),
+            ClipboardSnapshot::Html { html, alt_text } => clipboard.set_html(html, alt_text),
+            ClipboardSnapshot::Image(image) => clipboard.set_image(image),
+            ClipboardSnapshot::Files(files) => clipboard.set().file_list(&files),
+            #[cfg(target_os = "linux")]
+            ClipboardSnapshot::Wayland(_) => {
+                return Err("wl-copy is needed to restore the clipboard".to_string())
+            }
+        };
+        result.map_err(|e| format!("Failed to restore clipboard: {}", e))
+    }
+}
+
+/// Writes the text to be pasted, asking clipboard managers not to record it:
+/// the history and cloud exclusion formats on Windows, the password manager
+/// hint on X11 and `org.nspasteboard.ConcealedType` on macOS. On Wayland
+/// wl-copy is used for its better compatibility (especially with umlauts),
+/// which has no such option.
+fn write_transient_text(app_handle: &AppHandle, text: &str) -> Result<(), String> {
+    #[cfg(target_os = "linux")]
+    if is_wayland() && is_wl_copy_available() {
+        info!("Using wl-copy for clipboard write on Wayland");
+        return write_clipboard_via_wl_copy(text);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    if let Ok(mut clipboard) = arboard::Clipboard::new() {
+        #[cfg(target_os = "linux")]
+        use arboard::SetExtLinux;
+        #[cfg(target_os = "macos")]
+        use arboard::SetExtApple;
+        #[cfg(target_os = "windows")]
+        use arboard::SetExtWindows;
+
+        let set = clipboard.set().exclude_from_history();
+        #[cfg(target_os = "windows")]
+        let set = set.exclude_from_cloud();
+        match set.text(text) {
+            Ok(()) => return Ok(()),
+            Err(e) => warn!("Failed to write clipboard excluded from history: {}", e),
+        }
+    }
+
+    app_handle
+        .clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to write to clipboard: {}", e))
+}
+
 /// Pastes text using the clipboard: saves current content, writes text, sends paste keystroke, restores clipboard.
 fn paste_via_clipboard(
     enigo: &mut Enigo,
@@ -21,27 +184,9 @@ fn paste_via_clipboard(
     paste_method: &PasteMethod,
     paste_delay_ms: u64,
 ) -> Result<(), String> {
-    let clipboard = app_handle.clipboard();
-    let clipboard_content = clipboard.read_text().unwrap_or_default();
-
-    // Write text to clipboard first
-    // On Wayland, prefer wl-copy for better compatibility (especially with umlauts)
-    #[cfg(target_os = "linux")]
-    let write_result = if is_wayland() && is_wl_copy_available() {
-        info!("Using wl-copy for clipboard write on Wayland");
-        write_clipboard_via_wl_copy(text)
-    } else {
-        clipboard
-            .write_text(text)
-            .map_err(|e| format!("Failed to write to clipboard: {}", e))
-    };
-
-    #[cfg(not(target_os = "linux"))]
-    let write_result = clipboard
-        .write_text(text)
-        .map_err(|e| format!("Failed to write to clipboard: {}", e));
+    let snapshot = ClipboardSnapshot::capture();
 
-    write_result?;
+    write_transient_text(app_handle, text)?;
 
     std::thread::sleep(Duration::from_millis(paste_delay_ms));
 
@@ -64,18 +209,10 @@ fn paste_via_clipboard(
 
     std::thread::sleep(std::time::Duration::from_millis(50));
 
-    // Restore original clipboard content
-    // On Wayland, prefer wl-copy for better compatibility
-    #[cfg(target_os = "linux")]
-    if is_wayland() && is_wl_copy_available() {
-        let _ = write_clipboard_via_wl_copy(&clipboard_content);
-    } else {
-        let _ = clipboard.write_text(&clipboard_content);
+    if let Err(e) = snapshot.restore() {
+        warn!("{}", e);
     }
 
-    #[cfg(not(target_os = "linux"))]
-    let _ = clipboard.write_text(&clipboard_content);
-
     Ok(())
 }
 
@@ -402,6 +539,141 @@ fn write_clipboard_via_wl_copy(text: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Orders the MIME types of a Wayland selection for saving: images, then
+/// file lists, then HTML, then plain text, then the rest in the order the
+/// source listed them. X11 targets such as `TARGETS` or `UTF8_STRING` that
+/// XWayland adds are skipped.
+#[cfg(any(target_os = "linux", test))]
+fn snapshot_mime_types(types: &[String]) -> Vec<&str> {
+    let rank = |t: &str| match t {
+        "image/png" => 0,
+        _ if t.starts_with("image/") => 1,
+        "text/uri-list" => 2,
+        "text/html" => 3,
+        "text/plain;charset=utf-8" => 4,
+        _ if t.starts_with("text/plain") => 5,
+        _ => 6,
+    };
+
+    let mut mime_types: Vec<&str> = Vec::new();
+    for t in types.iter().map(|t| t.as_str()) {
+        if t.contains('/') && !mime_types.contains(&t) {
+            mime_types.push(t);
+        }
+    }
+    // Stable, so equally ranked types keep the source's order
+    mime_types.sort_by_key(|t| rank(t));
+    mime_types
+}
+
+/// Reads the current Wayland selection in every MIME type it is offered in.
+/// Returns an empty list when nothing is copied.
+#[cfg(target_os = "linux")]
+fn read_clipboard_via_wl_paste() -> Result<Vec<(String, Vec<u8>)>, String> {
+    let output = Command::new("wl-paste")
+        .arg("--list-types")
+        .output()
+        .map_err(|e| format!("Failed to execute wl-paste: {}", e))?;
+    if !output.status.success() {
+        // wl-paste exits with an error when the clipboard is empty
+        return Ok(Vec::new());
+    }
+
+    let types: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut contents = Vec::new();
+    for mime_type in snapshot_mime_types(&types) {
+        let output = Command::new("wl-paste")
+            .args(["--no-newline", "--type", mime_type])
+            .output()
+            .map_err(|e| format!("Failed to execute wl-paste: {}", e))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("wl-paste failed: {}", stderr));
+        }
+        contents.push((mime_type.to_string(), output.stdout));
+    }
+
+    Ok(contents)
+}
+
+/// Offers every saved MIME type on the Wayland clipboard at once. wl-copy
+/// can offer only one type per selection, so this serves the selection from
+/// a background thread instead, and falls back to wl-copy with the richest
+/// type on compositors without the data-control protocol.
+#[cfg(target_os = "linux")]
+fn write_wayland_clipboard(contents: Vec<(String, Vec<u8>)>) -> Result<(), String> {
+    use wl_clipboard_rs::copy::{MimeSource, MimeType, Options, Source};
+
+    let sources = contents
+        .iter()
+        .map(|(mime_type, data)| MimeSource {
+            source: Source::Bytes(data.clone().into_boxed_slice()),
+            mime_type: MimeType::Specific(mime_type.clone()),
+        })
+        .collect();
+    match Options::new().copy_multi(sources) {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            warn!(
+                "Failed to offer every clipboard type ({}), restoring only the richest",
+                e
+            );
+            let (mime_type, data) = &contents[0];
+            write_clipboard_via_wl_copy_typed(mime_type, data)
+        }
+    }
+}
+
+/// Offers `data` as `mime_type` on the Wayland clipboard via wl-copy.
+/// wl-copy reads all of stdin before forking its daemon, so stdin can be piped.
+#[cfg(target_os = "linux")]
+fn write_clipboard_via_wl_copy_typed(mime_type: &str, data: &[u8]) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = Command::new("wl-copy")
+        .args(["--type", mime_type])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn wl-copy: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(data)
+            .map_err(|e| format!("Failed to write to wl-copy stdin: {}", e))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for wl-copy: {}", e))?;
+    if !status.success() {
+        return Err("wl-copy failed".into());
+    }
+
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn clear_clipboard_via_wl_copy() -> Result<(), String> {
+    let status = Command::new("wl-copy")
+        .arg("--clear")
+        .status()
+        .map_err(|e| format!("Failed to execute wl-copy: {}", e))?;
+
+    if !status.success() {
+        return Err("wl-copy --clear failed".into());
+    }
+
+    Ok(())
+}
+
 /// Send a key combination (e.g., Ctrl+V) via wtype on Wayland.
 #[cfg(target_os = "linux")]
 fn send_key_combo_via_wtype(paste_method: &PasteMethod) -> Result<(), String> {
@@ -500,6 +772,155 @@ fn send_key_combo_via_xdotool(paste_method: &PasteMethod) -> Result<(), String>
     Ok(())
 }
 
+/// Attempts to send undo keys using Linux-native tools, in the same order of
+/// preference as `try_send_key_combo_linux`.
+/// Returns `Ok(true)` if a native tool handled it, `Ok(false)` to fall back to enigo.
+#[cfg(target_os = "linux")]
+fn try_send_undo_keys_linux(keys: UndoKeys) -> Result<bool, String> {
+    if is_wayland() {
+        if !is_kde_wayland() && is_wtype_available() {
+            info!("Using wtype for undo");
+            let args: Vec<&str> = match keys {
+                UndoKeys::UndoCombo => vec!["-M", "ctrl", "-k", "z", "-m", "ctrl"],
+                UndoKeys::Backspaces(count) => ["-k", "BackSpace"].repeat(count),
+            };
+            run_key_tool("wtype", &args)?;
+            return Ok(true);
+        }
+        if is_dotool_available() {
+            info!("Using dotool for undo");
+            send_undo_keys_via_dotool(keys)?;
+            return Ok(true);
+        }
+        if is_ydotool_available() {
+            info!("Using ydotool for undo");
+            send_undo_keys_via_ydotool(keys)?;
+            return Ok(true);
+        }
+    } else {
+        if is_xdotool_available() {
+            info!("Using xdotool for undo");
+            let args = match keys {
+                UndoKeys::UndoCombo => vec![
+                    "key".to_string(),
+                    "--clearmodifiers".into(),
+                    "ctrl+z".into(),
+                ],
+                UndoKeys::Backspaces(count) => vec![
+                    "key".to_string(),
+                    "--clearmodifiers".into(),
+                    "--repeat".into(),
+                    count.to_string(),
+                    "BackSpace".into(),
+                ],
+            };
+            run_key_tool("xdotool", &args)?;
+            return Ok(true);
+        }
+        if is_ydotool_available() {
+            info!("Using ydotool for undo");
+            send_undo_keys_via_ydotool(keys)?;
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(target_os = "linux")]
+fn run_key_tool<S: AsRef<std::ffi::OsStr>>(program: &str, args: &[S]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute {}: {}", program, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} failed: {}", program, stderr));
+    }
+
+    Ok(())
+}
+
+/// Send undo keys via dotool, which reads one command per line.
+#[cfg(target_os = "linux")]
+fn send_undo_keys_via_dotool(keys: UndoKeys) -> Result<(), String> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let commands = match keys {
+        UndoKeys::UndoCombo => "key ctrl+z\n".to_string(),
+        UndoKeys::Backspaces(count) => "key backspace\n".repeat(count),
+    };
+
+    let mut child = Command::new("dotool")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn dotool: {}", e))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(commands.as_bytes())
+            .map_err(|e| format!("Failed to write to dotool stdin: {}", e))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for dotool: {}", e))?;
+    if !status.success() {
+        return Err("dotool failed".into());
+    }
+
+    Ok(())
+}
+
+/// Send undo keys via ydotool (requires ydotoold daemon).
+/// Keycodes: ctrl=29, z=44, backspace=14
+#[cfg(target_os = "linux")]
+fn send_undo_keys_via_ydotool(keys: UndoKeys) -> Result<(), String> {
+    let mut args = vec!["key"];
+    match keys {
+        UndoKeys::UndoCombo => args.extend(["29:1", "44:1", "44:0", "29:0"]),
+        UndoKeys::Backspaces(count) => args.extend(["14:1", "14:0"].repeat(count)),
+    }
+    run_key_tool("ydotool", &args)
+}
+
+/// Removes the text inserted by the most recent `paste`, with backspaces for
+/// typed text or the app's undo key for a clipboard paste. Returns `Ok(false)`
+/// when there's nothing to undo. Each insertion can only be undone once.
+pub fn undo_last_insertion(app_handle: &AppHandle) -> Result<bool, String> {
+    let Some(insertion) = LAST_INSERTION.lock().unwrap().take() else {
+        return Ok(false);
+    };
+    let Some(keys) = undo_keys(&insertion)? else {
+        return Ok(false);
+    };
+    info!("Undoing last insertion: {:?}", keys);
+
+    #[cfg(target_os = "linux")]
+    if try_send_undo_keys_linux(keys)? {
+        return Ok(true);
+    }
+
+    let enigo_state = app_handle
+        .try_state::<EnigoState>()
+        .ok_or("Enigo state not initialized")?;
+    let mut enigo = enigo_state
+        .0
+        .lock()
+        .map_err(|e| format!("Failed to lock Enigo: {}", e))?;
+
+    match keys {
+        UndoKeys::Backspaces(count) => input::send_backspaces(&mut enigo, count)?,
+        UndoKeys::UndoCombo => input::send_undo(&mut enigo)?,
+    }
+
+    Ok(true)
+}
+
 /// Types text directly by simulating individual key presses.
 fn paste_direct(
     enigo: &mut Enigo,
@@ -614,7 +1035,14 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
         }
     }
 
-    if should_send_auto_submit(settings.auto_submit, paste_method) {
+    let auto_submitted = should_send_auto_submit(settings.auto_submit, paste_method);
+    *LAST_INSERTION.lock().unwrap() = Some(LastInsertion {
+        chars: text.graphemes(true).count(),
+        method: paste_method,
+        auto_submitted,
+    });
+
+    if auto_submitted {
         std::thread::sleep(Duration::from_millis(50));
         send_return_key(&mut enigo, settings.auto_submit_key)?;
     }
@@ -634,6 +1062,65 @@ pub fn paste(text: String, app_handle: AppHandle) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    fn types(list: &[&str]) -> Vec<String> {
+        list.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn wayland_snapshot_keeps_every_mime_type_richest_first() {
+        let copied_image = types(&["text/html", "image/jpeg", "image/png", "TARGETS"]);
+        assert_eq!(
+            snapshot_mime_types(&copied_image),
+            vec!["image/png", "image/jpeg", "text/html"]
+        );
+
+        let copied_files = types(&[
+            "x-special/gnome-copied-files",
+            "text/uri-list",
+            "UTF8_STRING",
+        ]);
+        assert_eq!(
+            snapshot_mime_types(&copied_files),
+            vec!["text/uri-list", "x-special/gnome-copied-files"]
+        );
+
+        let copied_html = types(&["TEXT", "text/plain", "text/html", "text/html"]);
+        assert_eq!(
+            snapshot_mime_types(&copied_html),
+            vec!["text/html", "text/plain"]
+        );
+
+        let copied_text = types(&["TEXT", "text/plain", "text/plain;charset=utf-8"]);
+        assert_eq!(
+            snapshot_mime_types(&copied_text),
+            vec!["text/plain;charset=utf-8", "text/plain"]
+        );
+
+        let custom = types(&["STRING", "application/x-custom"]);
+        assert_eq!(snapshot_mime_types(&custom), vec!["application/x-custom"]);
+        assert!(snapshot_mime_types(&types(&["TARGETS"])).is_empty());
+    }
+
+    #[test]
+    fn undo_matches_how_text_was_inserted() {
+        let insertion = |method, auto_submitted| LastInsertion {
+            chars: 12,
+            method,
+            auto_submitted,
+        };
+
+        assert_eq!(
+            undo_keys(&insertion(PasteMethod::Direct, false)),
+            Ok(Some(UndoKeys::Backspaces(12)))
+        );
+        assert_eq!(
+            undo_keys(&insertion(PasteMethod::CtrlShiftV, false)),
+            Ok(Some(UndoKeys::UndoCombo))
+        );
+        assert_eq!(undo_keys(&insertion(PasteMethod::None, false)), Ok(None));
+        assert!(undo_keys(&insertion(PasteMethod::CtrlV, true)).is_err());
+    }
+
     #[test]
     fn auto_submit_requires_setting_enabled() {
         assert!(!should_send_auto_submit(false, PasteMethod::CtrlV));
@@ -1,11 +1,16 @@
 use crate::audio_feedback;
-use crate::audio_toolkit::audio::{list_input_devices, list_output_devices};
+use crate::audio_toolkit::audio::{
+    list_input_devices, list_output_devices, measure_levels, write_wav_file,
+};
+use crate::audio_toolkit::vad;
+use crate::audio_toolkit::SileroVad;
 use crate::managers::audio::{AudioRecordingManager, MicrophoneMode};
-use crate::settings::{get_settings, write_settings};
-use log::warn;
+use crate::settings::{get_settings, write_settings, VadBackend, VadTuning};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
 #[derive(Serialize, Type)]
@@ -194,6 +199,279 @@ pub fn get_clamshell_microphone(app: AppHandle) -> Result<String, String> {
         .unwrap_or_else(|| "default".to_string()))
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn set_noise_suppression(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.noise_suppression = enabled;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_audio_processing();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_auto_gain_control(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.auto_gain_control = enabled;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_audio_processing();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_high_pass_filter(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.high_pass_filter = enabled;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_audio_processing();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_vad_backend(app: AppHandle, backend: VadBackend) -> Result<(), String> {
+    let mut settings = get_settings(&app);
+    settings.vad_backend = backend;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_vad()
+        .map_err(|e| format!("Failed to update VAD: {}", e))
+}
+
+/// Longest prefill or hangover `set_vad_tuning` accepts: 3 s of 30-ms frames,
+/// which every frame of the buffer is held in memory for
+const MAX_VAD_BUFFER_FRAMES: usize = 100;
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_vad_tuning(app: AppHandle, tuning: VadTuning) -> Result<(), String> {
+    if !(0.0..=1.0).contains(&tuning.threshold) {
+        return Err("VAD threshold must be between 0.0 and 1.0".to_string());
+    }
+    if !(0.0..=60.0).contains(&tuning.energy_margin_db) {
+        return Err("Energy margin must be between 0 and 60 dB".to_string());
+    }
+    if tuning.onset_frames == 0 {
+        return Err("Onset must be at least one frame".to_string());
+    }
+    if tuning.prefill_frames > MAX_VAD_BUFFER_FRAMES {
+        return Err(format!(
+            "Prefill must be at most {} frames",
+            MAX_VAD_BUFFER_FRAMES
+        ));
+    }
+    if tuning.hangover_frames > MAX_VAD_BUFFER_FRAMES {
+        return Err(format!(
+            "Hangover must be at most {} frames",
+            MAX_VAD_BUFFER_FRAMES
+        ));
+    }
+
+    let mut settings = get_settings(&app);
+    settings.vad_tuning = tuning;
+    write_settings(&app, settings);
+
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.update_vad()
+        .map_err(|e| format!("Failed to update VAD: {}", e))
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct VadCalibrationResult {
+    pub noise_floor_db: f32,
+    pub noise_peak_db: f32,
+    /// Highest typical Silero speech probability in the ambient sample
+    pub noise_probability: Option<f32>,
+    /// Current tuning with the calibrated values filled in; apply with `set_vad_tuning`
+    pub suggested: VadTuning,
+}
+
+const DEFAULT_CALIBRATION_MS: u64 = 3000;
+
+/// Records a short ambient sample (nobody talking) and suggests VAD tuning for it.
+#[tauri::command]
+#[specta::specta]
+pub async fn calibrate_vad(
+    app: AppHandle,
+    duration_ms: Option<u64>,
+) -> Result<VadCalibrationResult, String> {
+    let duration = Duration::from_millis(
+        duration_ms
+            .unwrap_or(DEFAULT_CALIBRATION_MS)
+            .clamp(1000, 10000),
+    );
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let rm = app.state::<Arc<AudioRecordingManager>>();
+        let samples = rm
+            .capture_raw(duration)
+            .map_err(|e| format!("Failed to record ambient audio: {}", e))?
+            .samples;
+
+        let settings = get_settings(&app);
+        let calibration = match settings.vad_backend {
+            VadBackend::Silero => {
+                let vad_path = rm.vad_model_path().map_err(|e| e.to_string())?;
+                let mut silero = SileroVad::new(&vad_path, settings.vad_tuning.threshold)
+                    .map_err(|e| format!("Failed to load VAD model: {}", e))?;
+                let mut score = |frame: &[f32]| silero.probability(frame);
+                vad::calibrate(&samples, Some(&mut score))
+            }
+            VadBackend::Energy => vad::calibrate(&samples, None),
+        }
+        .map_err(|e| format!("Calibration failed: {}", e))?;
+        info!("VAD calibration: {:?}", calibration);
+
+        let current = settings.vad_tuning;
+        Ok(VadCalibrationResult {
+            noise_floor_db: calibration.noise_floor_db,
+            noise_peak_db: calibration.noise_peak_db,
+            noise_probability: calibration.noise_probability,
+            suggested: VadTuning {
+                threshold: calibration.threshold.unwrap_or(current.threshold),
+                energy_margin_db: calibration.energy_margin_db,
+                prefill_frames: calibration.prefill_frames,
+                hangover_frames: current.hangover_frames,
+                onset_frames: calibration.onset_frames,
+            },
+        })
+    })
+    .await
+    .map_err(|e| format!("Calibration task failed: {}", e))?
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct MicTestReport {
+    pub device_name: String,
+    pub duration_secs: f32,
+    pub rms_db: f32,
+    pub peak_db: f32,
+    pub noise_floor_db: f32,
+    /// Share of device samples at full scale
+    pub clipped_ratio: f32,
+    pub device_sample_rate: u32,
+    /// Rate audio actually arrived at; far off the device rate means dropouts
+    pub measured_sample_rate: f32,
+    pub speech_ratio: f32,
+    /// Plain-language problems found, for support tickets
+    pub warnings: Vec<String>,
+}
+
+const DEFAULT_MIC_TEST_MS: u64 = 5000;
+/// Clipping on more than this share of samples is reported
+const CLIPPING_WARN_RATIO: f32 = 0.001;
+
+/// Records from the selected microphone and reports on the signal. Live
+/// levels arrive on the usual `mic-level` event while it records.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_microphone(
+    app: AppHandle,
+    duration_ms: Option<u64>,
+    play_back: bool,
+) -> Result<MicTestReport, String> {
+    let duration = Duration::from_millis(
+        duration_ms
+            .unwrap_or(DEFAULT_MIC_TEST_MS)
+            .clamp(1000, 30000),
+    );
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let rm = app.state::<Arc<AudioRecordingManager>>();
+        let capture = rm
+            .capture_raw(duration)
+            .map_err(|e| format!("Failed to record from microphone: {}", e))?;
+
+        let mut vad = rm
+            .build_vad()
+            .map_err(|e| format!("Failed to create VAD: {}", e))?;
+        let levels = measure_levels(&capture.samples, Some(vad.as_mut()));
+        let clipped_ratio = if capture.device_samples > 0 {
+            capture.clipped_samples as f32 / capture.device_samples as f32
+        } else {
+            0.0
+        };
+        let speech_ratio = levels.speech_ratio.unwrap_or(0.0);
+
+        let mut warnings = Vec::new();
+        if capture.samples.is_empty() {
+            warnings.push("No audio was received from the microphone".to_string());
+        }
+        if clipped_ratio > CLIPPING_WARN_RATIO {
+            warnings.push("Input is clipping; lower the microphone gain".to_string());
+        }
+        if levels.peak_db < -40.0 {
+            warnings.push("Input is very quiet; check the microphone isn't muted".to_string());
+        }
+        if levels.noise_floor_db > -45.0 {
+            warnings.push("Background noise is high; try noise suppression".to_string());
+        }
+        let rate_error = (capture.measured_sample_rate - capture.device_sample_rate as f32).abs()
+            / capture.device_sample_rate as f32;
+        if capture.measured_sample_rate > 0.0 && rate_error > 0.05 {
+            warnings.push(format!(
+                "Device delivered {:.0} Hz instead of {} Hz; audio may be dropping out",
+                capture.measured_sample_rate, capture.device_sample_rate
+            ));
+        }
+        if speech_ratio == 0.0 {
+            warnings.push("No speech was detected".to_string());
+        }
+
+        let report = MicTestReport {
+            device_name: rm
+                .effective_microphone_name()
+                .unwrap_or_else(|| "Default".to_string()),
+            duration_secs: capture.samples.len() as f32
+                / crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE as f32,
+            rms_db: levels.rms_db,
+            peak_db: levels.peak_db,
+            noise_floor_db: levels.noise_floor_db,
+            clipped_ratio,
+            device_sample_rate: capture.device_sample_rate,
+            measured_sample_rate: capture.measured_sample_rate,
+            speech_ratio,
+            warnings,
+        };
+        info!("Microphone test: {:?}", report);
+
+        if play_back && !capture.samples.is_empty() {
+            let path = app
+                .path()
+                .app_cache_dir()
+                .map(|dir| dir.join("mic_test.wav"))
+                .map_err(|e| format!("Failed to resolve cache directory: {}", e))?;
+            let saved = path
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .map_err(anyhow::Error::from)
+                .and_then(|()| write_wav_file(&path, &capture.samples));
+            match saved {
+                Ok(()) => {
+                    if let Err(e) = audio_feedback::play_recording(&app, &path) {
+                        error!("Failed to play back microphone test: {}", e);
+                    }
+                    let _ = std::fs::remove_file(&path);
+                }
+                Err(e) => error!("Failed to save microphone test: {}", e),
+            }
+        }
+
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Microphone test failed: {}", e))?
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn is_recording(app: AppHandle) -> bool {
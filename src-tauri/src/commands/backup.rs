@@ -0,0 +1,213 @@
+use crate::backup::{self, BackupManifest, UnpackedBackup};
+use crate::managers::history::{HistoryImport, HistoryManager, RecordingSelection};
+use crate::settings::{get_settings, write_settings};
+use chrono::Utc;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, State};
+
+#[derive(Deserialize, Debug, Clone, Default, Type)]
+#[serde(default)]
+pub struct BackupOptions {
+    /// Keep the post-processing API keys in the backed up settings
+    pub include_api_keys: bool,
+    pub recordings: RecordingSelection,
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct BackupResult {
+    pub recordings: usize,
+    pub bytes: u64,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, Type)]
+#[serde(default)]
+pub struct RestoreOptions {
+    /// Replace the local settings with the backed up ones. Custom words and
+    /// prompts are merged either way.
+    pub replace_settings: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct RestoreResult {
+    pub history: HistoryImport,
+    pub recordings: usize,
+    /// Settings like shortcuts and devices only apply after a restart
+    pub restart_required: bool,
+}
+
+/// Writes history, meetings, TTS entries, settings and the selected
+/// recordings to a single archive at `path`.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_backup(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    path: String,
+    options: BackupOptions,
+) -> Result<BackupResult, String> {
+    let history_manager = history_manager.inner().clone();
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let settings = backup::settings_for_backup(get_settings(&app), options.include_api_keys);
+    let app_version = app.package_info().version.to_string();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let created_at = Utc::now().timestamp();
+        let snapshot = app_data_dir.join(format!("backup-{}.db", created_at));
+        history_manager
+            .backup_database(&snapshot)
+            .map_err(|e| format!("Failed to snapshot the history database: {}", e))?;
+
+        let recordings: Vec<String> = history_manager
+            .recordings_for_backup(options.recordings)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|name| history_manager.get_audio_file_path(name).is_file())
+            .collect();
+        let manifest = BackupManifest {
+            format_version: backup::FORMAT_VERSION,
+            app_version,
+            created_at,
+            includes_api_keys: options.include_api_keys,
+            recordings,
+        };
+
+        let staging_dir = app_data_dir.join("backup-export");
+        let written = plain_recordings_dir(&history_manager, &manifest.recordings, &staging_dir)
+            .and_then(|recordings_dir| {
+                backup::write_archive(
+                    Path::new(&path),
+                    &manifest,
+                    &settings,
+                    &snapshot,
+                    &recordings_dir,
+                )
+                .map_err(|e| e.to_string())
+            });
+        let _ = fs::remove_dir_all(&staging_dir);
+        if let Err(e) = fs::remove_file(&snapshot) {
+            warn!("Failed to remove database snapshot {:?}: {}", snapshot, e);
+        }
+        let bytes = written.map_err(|e| format!("Failed to write backup: {}", e))?;
+
+        info!(
+            "Backed up history and {} recordings to {} ({} bytes)",
+            manifest.recordings.len(),
+            path,
+            bytes
+        );
+        Ok(BackupResult {
+            recordings: manifest.recordings.len(),
+            bytes,
+        })
+    })
+    .await
+    .map_err(|e| format!("Backup task failed: {}", e))?
+}
+
+/// Merges a backup made by `export_backup` into the current data. Entries
+/// get new ids and ones already present are skipped; existing recordings
+/// are never overwritten.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_backup(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    path: String,
+    options: RestoreOptions,
+) -> Result<RestoreResult, String> {
+    let history_manager = history_manager.inner().clone();
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let staging_dir = app_data_dir.join("backup-import");
+
+    let (unpacked, history, recordings) = tauri::async_runtime::spawn_blocking(move || {
+        // Leftovers of an interrupted import
+        let _ = fs::remove_dir_all(&staging_dir);
+        let restored = restore_archive(&history_manager, Path::new(&path), &staging_dir);
+        if let Err(e) = fs::remove_dir_all(&staging_dir) {
+            warn!("Failed to remove backup staging directory: {}", e);
+        }
+        restored
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {}", e))??;
+
+    let settings = backup::merge_settings(
+        get_settings(&app),
+        unpacked.settings,
+        options.replace_settings,
+    );
+    write_settings(&app, settings);
+
+    info!(
+        "Restored backup made by version {} ({} entries, {} recordings)",
+        unpacked.manifest.app_version, history.entries, recordings
+    );
+    Ok(RestoreResult {
+        history,
+        recordings,
+        restart_required: options.replace_settings,
+    })
+}
+
+/// Unpacks a backup, merges its history and moves in the recordings that
+/// aren't here yet.
+fn restore_archive(
+    history_manager: &HistoryManager,
+    path: &Path,
+    staging_dir: &Path,
+) -> Result<(UnpackedBackup, HistoryImport, usize), String> {
+    let unpacked = backup::unpack_archive(path, staging_dir)
+        .map_err(|e| format!("Failed to read backup: {}", e))?;
+    let included: Vec<String> = unpacked
+        .recordings
+        .iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+    let history = history_manager
+        .import_database(&unpacked.database, &included)
+        .map_err(|e| format!("Failed to import history: {}", e))?;
+
+    let mut recordings = 0;
+    for (name, unpacked_path) in &unpacked.recordings {
+        let target = history_manager.get_audio_file_path(name);
+        if target.exists() {
+            continue;
+        }
+        let moved = fs::rename(unpacked_path, &target)
+            .or_else(|_| fs::copy(unpacked_path, &target).map(|_| ()));
+        match moved
+            .map_err(anyhow::Error::from)
+            .and_then(|_| history_manager.seal_recording(&target))
+        {
+            Ok(()) => recordings += 1,
+            Err(e) => warn!("Failed to restore recording {}: {}", name, e),
+        }
+    }
+
+    Ok((unpacked, history, recordings))
+}
+
+/// Directory to archive the recordings from. Backups are never encrypted,
+/// so encrypted recordings are decrypted into `staging_dir` first.
+fn plain_recordings_dir(
+    history_manager: &HistoryManager,
+    recordings: &[String],
+    staging_dir: &Path,
+) -> Result<PathBuf, String> {
+    if !history_manager.is_encrypted() {
+        return Ok(history_manager.recordings_dir().to_path_buf());
+    }
+
+    fs::create_dir_all(staging_dir).map_err(|e| e.to_string())?;
+    for name in recordings {
+        history_manager
+            .export_recording(name, &staging_dir.join(name))
+            .map_err(|e| format!("Failed to decrypt recording {}: {}", name, e))?;
+    }
+    Ok(staging_dir.to_path_buf())
+}
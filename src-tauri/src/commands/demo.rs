@@ -19,6 +19,7 @@ pub async fn seed_demo_content(app: AppHandle) -> Result<(), String> {
         "We need to focus on local-first AI and ensuring that the latency for transcription is below 200 milliseconds. The goal is to make it feel like you are typing with your voice.".to_string(),
         Some("Focus on local-first AI and ensuring transcription latency is below 200ms. Goal: seamless voice-to-text experience.".to_string()),
         None,
+        None,
     ).map_err(|e: anyhow::Error| e.to_string())?;
 
     history_manager.save_to_database(
@@ -28,6 +29,7 @@ pub async fn seed_demo_content(app: AppHandle) -> Result<(), String> {
         "Milk eggs bread butter and some almond milk for the coffee also don't forget the organic honey from the farmers market.".to_string(),
         None,
         None,
+        None,
     ).map_err(|e: anyhow::Error| e.to_string())?;
 
     // 2. Seed a Sample Meeting
@@ -1,6 +1,16 @@
-use crate::managers::history::{HistoryEntry, HistoryManager, TtsHistoryEntry};
+use crate::actions::{maybe_convert_chinese_variant, post_process_with_prompt};
+use crate::managers::history::{
+    CleanupPreview, EntryKind, HistoryEntry, HistoryManager, TranscriptionRevision,
+    TranscriptionVersion, TtsHistoryEntry,
+};
+use crate::managers::transcription::{TranscribeParams, TranscriptionManager};
+use crate::settings::{get_settings, RecordingFormat, RetentionPolicies};
+use serde::Serialize;
+use specta::Type;
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_clipboard_manager::ClipboardExt;
 
 // ... existing commands ...
 
@@ -8,8 +18,11 @@ use tauri::{AppHandle, State};
 #[specta::specta]
 pub async fn get_tts_history(
     history_manager: State<'_, Arc<HistoryManager>>,
+    tags: Option<Vec<String>>,
 ) -> Result<Vec<TtsHistoryEntry>, String> {
-    history_manager.get_tts_history().map_err(|e| e.to_string())
+    history_manager
+        .get_tts_history(&tags.unwrap_or_default())
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -36,9 +49,10 @@ pub async fn delete_tts_entry(
 pub async fn get_history_entries(
     _app: AppHandle,
     history_manager: State<'_, Arc<HistoryManager>>,
+    tags: Option<Vec<String>>,
 ) -> Result<Vec<HistoryEntry>, String> {
     history_manager
-        .get_history_entries()
+        .get_history_entries(&tags.unwrap_or_default())
         .await
         .map_err(|e| e.to_string())
 }
@@ -91,6 +105,7 @@ pub async fn update_history_limit(
 ) -> Result<(), String> {
     let mut settings = crate::settings::get_settings(&app);
     settings.history_limit = limit;
+    settings.sync_legacy_retention();
     crate::settings::write_settings(&app, settings);
 
     history_manager
@@ -120,6 +135,32 @@ pub async fn update_recording_retention_period(
 
     let mut settings = crate::settings::get_settings(&app);
     settings.recording_retention_period = retention_period;
+    settings.sync_legacy_retention();
+    crate::settings::write_settings(&app, settings);
+
+    history_manager
+        .cleanup_old_entries()
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_retention_policies(app: AppHandle) -> RetentionPolicies {
+    crate::settings::get_retention_policies(&app)
+}
+
+/// Replaces the retention policies of all kinds of history and applies them.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_retention_policies(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    policies: RetentionPolicies,
+) -> Result<(), String> {
+    let mut settings = crate::settings::get_settings(&app);
+    settings.retention_policies = Some(policies);
     crate::settings::write_settings(&app, settings);
 
     history_manager
@@ -128,3 +169,379 @@ pub async fn update_recording_retention_period(
 
     Ok(())
 }
+
+/// Lists what retention cleanup would delete, without deleting anything.
+/// With `policies`, previews those instead of the saved ones.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_retention_cleanup(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    policies: Option<RetentionPolicies>,
+) -> Result<CleanupPreview, String> {
+    history_manager
+        .preview_cleanup(policies)
+        .map_err(|e| e.to_string())
+}
+
+/// Sets the format new recordings are stored in. Existing recordings keep
+/// theirs until `reencode_recordings` runs.
+#[tauri::command]
+#[specta::specta]
+pub fn update_recording_format(app: AppHandle, format: RecordingFormat) -> Result<(), String> {
+    let mut settings = crate::settings::get_settings(&app);
+    settings.recording_format = format;
+    crate::settings::write_settings(&app, settings);
+    Ok(())
+}
+
+/// Starts re-encoding the recordings of history entries and meetings into
+/// the configured format. Returns right away; progress and the outcome
+/// arrive as `recording-reencode-*` events.
+#[tauri::command]
+#[specta::specta]
+pub fn reencode_recordings(history_manager: State<'_, Arc<HistoryManager>>) -> Result<(), String> {
+    history_manager.start_reencode().map_err(|e| e.to_string())
+}
+
+/// Transcribes a history entry's audio again, optionally with another model,
+/// language ("auto" to detect) or post-processing prompt, and stores the result
+/// as a version of the entry. The active model is restored afterwards.
+#[tauri::command]
+#[specta::specta]
+pub async fn retranscribe_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    id: i64,
+    model_id: Option<String>,
+    language: Option<String>,
+    prompt_id: Option<String>,
+) -> Result<TranscriptionVersion, String> {
+    let entry = history_manager
+        .get_entry_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("History entry {} not found", id))?;
+    let audio = history_manager
+        .read_recording_samples(&entry.file_name)
+        .map_err(|e| format!("Failed to read recording: {}", e))?;
+
+    let mut settings = get_settings(&app);
+    let prompt = match &prompt_id {
+        Some(prompt_id) => Some(
+            settings
+                .post_process_prompts
+                .iter()
+                .find(|p| &p.id == prompt_id)
+                .ok_or_else(|| format!("Prompt with id '{}' not found", prompt_id))?
+                .prompt
+                .clone(),
+        ),
+        None => None,
+    };
+
+    let transcription = transcription_manager.inner().clone();
+    let requested_language = language.clone();
+    let selected_model = settings.selected_model.clone();
+    let (model_id, output) = tauri::async_runtime::spawn_blocking(move || {
+        let model_id = model_id
+            .or_else(|| transcription.get_current_model())
+            .unwrap_or(selected_model);
+        if model_id.is_empty() {
+            return Err("No model selected".to_string());
+        }
+
+        // Dictations started meanwhile wait for the previous model to be back
+        let result = transcription.with_model_swap(|| {
+            if transcription.get_current_model().as_deref() != Some(model_id.as_str()) {
+                transcription
+                    .load_model(&model_id)
+                    .map_err(|e| format!("Failed to load model {}: {}", model_id, e))?;
+            }
+
+            let params = requested_language.map(|language| TranscribeParams {
+                language: (language != "auto").then_some(language),
+                translate: None,
+            });
+            transcription
+                .transcribe_during_swap(audio, params)
+                .map_err(|e| format!("Transcription failed: {}", e))
+        });
+
+        result.map(|output| (model_id, output))
+    })
+    .await
+    .map_err(|e| format!("Re-transcription task failed: {}", e))??;
+
+    // Same conversion and post-processing as a dictation, with the requested
+    // language and prompt in place of the configured ones
+    if let Some(language) = &language {
+        settings.selected_language = language.clone();
+    }
+    let detected_language = output.language.as_ref().map(|l| l.code.clone());
+    let converted =
+        maybe_convert_chinese_variant(&settings, &output.text, detected_language.as_deref()).await;
+    let processed = match &prompt {
+        Some(prompt) => {
+            let text = converted.as_deref().unwrap_or(&output.text);
+            post_process_with_prompt(&settings, text, prompt).await
+        }
+        None => None,
+    };
+    let post_process_prompt = prompt.filter(|_| processed.is_some());
+    let post_processed_text = processed.or(converted);
+
+    history_manager
+        .save_version(
+            id,
+            model_id,
+            language,
+            output,
+            post_processed_text,
+            post_process_prompt,
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_entry_versions(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<Vec<TranscriptionVersion>, String> {
+    history_manager.get_versions(id).map_err(|e| e.to_string())
+}
+
+async fn entry_text(
+    history_manager: &HistoryManager,
+    id: i64,
+    version_id: Option<i64>,
+) -> Result<String, String> {
+    history_manager
+        .get_entry_text(id, version_id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "History entry or version not found".to_string())
+}
+
+/// Copies the final text of a history entry, or of one of its versions.
+#[tauri::command]
+#[specta::specta]
+pub async fn copy_history_text(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    version_id: Option<i64>,
+) -> Result<(), String> {
+    let text = entry_text(&history_manager, id, version_id).await?;
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+}
+
+/// Pastes the final text of a history entry, or of one of its versions, into
+/// the app that had focus before the main window. The window is hidden first
+/// so focus returns to that app.
+#[tauri::command]
+#[specta::specta]
+pub async fn paste_history_text(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    version_id: Option<i64>,
+) -> Result<(), String> {
+    let text = entry_text(&history_manager, id, version_id).await?;
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    tokio::time::sleep(Duration::from_millis(300)).await;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let ah = app.clone();
+    app.run_on_main_thread(move || {
+        let _ = tx.send(crate::utils::paste(text, ah));
+    })
+    .map_err(|e| format!("Failed to run paste on main thread: {}", e))?;
+    rx.await.map_err(|_| "Paste was interrupted".to_string())?
+}
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct HistoryEdit {
+    pub revision: TranscriptionRevision,
+    /// Words from the correction worth adding to the custom words
+    pub suggested_words: Vec<String>,
+}
+
+/// Saves the user's correction of a history entry as a revision, keeping the
+/// original, and suggests custom words from what was corrected.
+#[tauri::command]
+#[specta::specta]
+pub async fn edit_history_entry(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+    text: String,
+) -> Result<HistoryEdit, String> {
+    let previous = entry_text(&history_manager, id, None).await?;
+    let revision = history_manager
+        .edit_entry(id, text)
+        .map_err(|e| e.to_string())?;
+
+    let custom_words = get_settings(&app).custom_words;
+    let suggested_words =
+        crate::audio_toolkit::suggest_custom_words(&previous, &revision.text, &custom_words);
+
+    Ok(HistoryEdit {
+        revision,
+        suggested_words,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_entry_revisions(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    id: i64,
+) -> Result<Vec<TranscriptionRevision>, String> {
+    history_manager.get_revisions(id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_entry_title(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    kind: EntryKind,
+    id: i64,
+    title: String,
+) -> Result<(), String> {
+    history_manager
+        .set_title(kind, id, title)
+        .map_err(|e| e.to_string())
+}
+
+/// Replaces an entry's tags and returns them as stored (trimmed, without duplicates).
+#[tauri::command]
+#[specta::specta]
+pub async fn set_entry_tags(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    kind: EntryKind,
+    id: i64,
+    tags: Vec<String>,
+) -> Result<Vec<String>, String> {
+    history_manager
+        .set_tags(kind, id, tags)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_entry_notes(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    kind: EntryKind,
+    id: i64,
+    notes: Option<String>,
+) -> Result<(), String> {
+    history_manager
+        .set_notes(kind, id, notes)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_all_tags(
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<Vec<String>, String> {
+    history_manager.get_all_tags().map_err(|e| e.to_string())
+}
+
+const TITLE_PROMPT: &str = "Write a short title of at most eight words for the following text. \
+Reply with the title only, without quotes or a trailing period.\n\n${output}";
+
+/// Longest content sent to the LLM; the start of a long meeting is enough for a title.
+const TITLE_CONTENT_CHARS: usize = 4000;
+
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Generates a title for an entry from its content with the active
+/// post-processing provider, stores it and returns it.
+#[tauri::command]
+#[specta::specta]
+pub async fn generate_entry_title(
+    app: AppHandle,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    kind: EntryKind,
+    id: i64,
+) -> Result<String, String> {
+    let content = history_manager
+        .get_entry_content(kind, id)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Entry {} not found", id))?;
+    if content.trim().is_empty() {
+        return Err("Entry has no text to generate a title from".to_string());
+    }
+    let content: String = content.chars().take(TITLE_CONTENT_CHARS).collect();
+
+    let settings = get_settings(&app);
+    let response = post_process_with_prompt(&settings, &content, TITLE_PROMPT)
+        .await
+        .ok_or_else(|| {
+            "Could not generate a title; check the post-processing provider".to_string()
+        })?;
+    let title = clean_generated_title(&response)
+        .ok_or_else(|| "The generated title was empty".to_string())?;
+
+    history_manager
+        .set_title(kind, id, title.clone())
+        .map_err(|e| e.to_string())?;
+    Ok(title)
+}
+
+/// First line of an LLM reply, without surrounding quotes or a trailing period.
+fn clean_generated_title(response: &str) -> Option<String> {
+    let line = response.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let line = line
+        .trim_start_matches(|c| matches!(c, '"' | '\'' | '“' | '#' | '*'))
+        .trim_end_matches(|c| matches!(c, '"' | '\'' | '”' | '.' | '*'))
+        .trim();
+    if line.is_empty() {
+        return None;
+    }
+    Some(line.chars().take(MAX_TITLE_CHARS).collect())
+}
+
+/// Why history couldn't be opened this session, e.g. an encrypted database
+/// whose key the keychain didn't provide. `None` when history works.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_unavailable_reason(
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<Option<String>, String> {
+    Ok(history_manager.unavailable_reason().map(str::to_string))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_history_encryption(
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<bool, String> {
+    Ok(history_manager.is_encrypted())
+}
+
+/// Turns encryption at rest of the history database and recordings on or
+/// off. Returns how many recordings couldn't be converted.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_history_encryption(
+    history_manager: State<'_, Arc<HistoryManager>>,
+    enabled: bool,
+) -> Result<usize, String> {
+    // Playback caches decrypted audio of the old state
+    crate::playback::clear_cache();
+    let history_manager = history_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || history_manager.set_encryption(enabled))
+        .await
+        .map_err(|e| format!("Encryption task failed: {}", e))?
+        .map_err(|e| e.to_string())
+}
@@ -38,8 +38,11 @@ pub async fn is_meeting_active(
 #[specta::specta]
 pub async fn get_meetings(
     state: State<'_, Arc<MeetingManager>>,
+    tags: Option<Vec<String>>,
 ) -> Result<Vec<crate::managers::history::Meeting>, String> {
-    state.get_meetings().map_err(|e| e.to_string())
+    state
+        .get_meetings(&tags.unwrap_or_default())
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
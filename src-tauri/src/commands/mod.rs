@@ -1,14 +1,16 @@
 pub mod audio;
+pub mod backup;
 pub mod history;
 pub mod meetings;
 pub mod models;
+pub mod storage;
 pub mod transcription;
 pub mod translation;
 pub mod tts;
 pub mod diagnostics;
 pub mod demo;
 
-use crate::settings::{get_settings, write_settings, AppSettings, LogLevel};
+use crate::settings::{get_settings, write_settings, AppSettings, LogLevel, NetworkSettings};
 use crate::utils::cancel_current_operation;
 use tauri::{AppHandle, Manager};
 use tauri_plugin_opener::OpenerExt;
@@ -33,7 +35,7 @@ pub fn get_app_dir_path(app: AppHandle) -> Result<String, String> {
 #[tauri::command]
 #[specta::specta]
 pub fn get_app_settings(app: AppHandle) -> Result<AppSettings, String> {
-    Ok(get_settings(&app))
+    Ok(get_settings(&app).redacted())
 }
 
 #[tauri::command]
@@ -53,6 +55,41 @@ pub fn get_log_dir_path(app: AppHandle) -> Result<String, String> {
     Ok(log_dir.to_string_lossy().to_string())
 }
 
+/// Updates proxy, CA bundle and mirror settings. They're checked by building a
+/// client before being saved, and the TTS service is restarted so its own
+/// downloads pick them up.
+#[specta::specta]
+#[tauri::command]
+pub fn set_network_settings(app: AppHandle, network: NetworkSettings) -> Result<(), String> {
+    if let Some(proxy) = network.proxy() {
+        if !crate::http::is_http_url(proxy) {
+            return Err("Proxy URL must start with http:// or https://".to_string());
+        }
+    }
+    if let Some(mirror) = network
+        .model_mirrors
+        .iter()
+        .find(|m| !crate::http::is_http_url(m.trim()))
+    {
+        return Err(format!("Mirror URL must start with http:// or https://: {}", mirror));
+    }
+    crate::http::client(&network).map_err(|e| e.to_string())?;
+
+    let mut settings = get_settings(&app);
+    settings.network = network;
+    write_settings(&app, settings);
+
+    let tts_manager = app.state::<std::sync::Arc<crate::managers::tts::TtsManager>>();
+    if tts_manager.is_running() {
+        tts_manager.stop_service();
+        tts_manager
+            .start_service()
+            .map_err(|e| format!("Failed to restart TTS service: {}", e))?;
+    }
+
+    Ok(())
+}
+
 #[specta::specta]
 #[tauri::command]
 pub fn set_log_level(app: AppHandle, level: LogLevel) -> Result<(), String> {
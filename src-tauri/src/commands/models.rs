@@ -1,8 +1,13 @@
-use crate::managers::model::{ModelInfo, ModelManager};
+use crate::managers::benchmark::{self, BenchmarkProgress, ModelBenchmark, ModelRecommendation};
+use crate::managers::diagnostics::DiagnosticManager;
+use crate::managers::model::{
+    DownloadQueueState, IntegrityStatus, ModelImport, ModelInfo, ModelManager, ModelVerification,
+};
 use crate::managers::transcription::TranscriptionManager;
 use crate::settings::{get_settings, write_settings};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tauri::{AppHandle, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 #[tauri::command]
 #[specta::specta]
@@ -90,6 +95,17 @@ pub async fn set_active_model(
                 .load_model(&model_id)
                 .map_err(|e| e.to_string())?;
             settings.selected_model = model_id.clone();
+
+            if let Some(result) = model_manager.get_benchmark(&model_id) {
+                if benchmark::is_too_slow(result.real_time_factor) {
+                    log::warn!(
+                        "Model {} is slow on this machine (RTF {:.2})",
+                        model_id,
+                        result.real_time_factor
+                    );
+                    let _ = app_handle.emit("model-too-slow", result);
+                }
+            }
         }
     }
 
@@ -150,6 +166,213 @@ pub async fn has_any_models_or_downloads(
     Ok(models.iter().any(|m| m.is_downloaded))
 }
 
+/// Transcribes the bundled reference clip with each given model (default: all
+/// downloaded speech-to-text models) and stores the real-time factors. The
+/// previously loaded model is loaded again afterwards, and dictations wait
+/// until then.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_model_benchmark(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+    model_ids: Option<Vec<String>>,
+) -> Result<Vec<ModelBenchmark>, String> {
+    let model_ids: Vec<String> = model_manager
+        .get_available_models()
+        .into_iter()
+        .filter(|m| m.is_downloaded && benchmark::is_benchmarkable(m))
+        .filter(|m| model_ids.as_ref().is_none_or(|ids| ids.contains(&m.id)))
+        .map(|m| m.id)
+        .collect();
+    if model_ids.is_empty() {
+        return Err("No downloaded models to benchmark".to_string());
+    }
+
+    let clip_path = app_handle
+        .path()
+        .resolve(benchmark::REFERENCE_CLIP, tauri::path::BaseDirectory::Resource)
+        .map_err(|e| format!("Failed to locate benchmark clip: {}", e))?;
+    let audio = crate::audio_toolkit::read_wav_file(&clip_path)
+        .map_err(|e| format!("Failed to read benchmark clip: {}", e))?;
+
+    let manager = model_manager.inner().clone();
+    let transcription = transcription_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let total = model_ids.len();
+        let mut results = Vec::new();
+
+        // Dictations started meanwhile wait for the benchmark to finish
+        transcription.with_model_swap(|| {
+            for (index, model_id) in model_ids.into_iter().enumerate() {
+                let _ = app_handle.emit(
+                    "model-benchmark-progress",
+                    BenchmarkProgress {
+                        model_id: model_id.clone(),
+                        index,
+                        total,
+                    },
+                );
+                match transcription.benchmark_model(&model_id, &audio) {
+                    Ok(result) => results.push(result),
+                    Err(e) => log::warn!("Benchmark of {} failed: {}", model_id, e),
+                }
+            }
+        });
+
+        manager
+            .record_benchmarks(results.clone())
+            .map_err(|e| format!("Failed to save benchmark results: {}", e))?;
+        Ok(results)
+    })
+    .await
+    .map_err(|e| format!("Benchmark failed: {}", e))?
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_model_benchmarks(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelBenchmark>, String> {
+    Ok(model_manager.get_benchmarks())
+}
+
+/// Benchmark- and RAM-based recommendations for every speech-to-text model,
+/// including ones that aren't downloaded yet (their speed is estimated).
+#[tauri::command]
+#[specta::specta]
+pub async fn get_model_recommendations(
+    model_manager: State<'_, Arc<ModelManager>>,
+    diagnostic_manager: State<'_, Arc<DiagnosticManager>>,
+) -> Result<Vec<ModelRecommendation>, String> {
+    let memory_total_gb = diagnostic_manager.get_system_info().memory_total_gb;
+    Ok(model_manager.recommendations(Some(memory_total_gb), false))
+}
+
+/// Checks installed models against their digests. Corrupt models that have
+/// a download URL are deleted and downloaded again in the background.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_installed_models(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    transcription_manager: State<'_, Arc<TranscriptionManager>>,
+) -> Result<Vec<ModelVerification>, String> {
+    let manager = model_manager.inner().clone();
+    let mut results = tauri::async_runtime::spawn_blocking(move || manager.verify_installed_models())
+        .await
+        .map_err(|e| format!("Verification failed: {}", e))?;
+
+    for verification in results
+        .iter_mut()
+        .filter(|v| v.status == IntegrityStatus::Corrupt)
+    {
+        let has_url = model_manager
+            .get_model_info(&verification.model_id)
+            .is_some_and(|m| m.url.is_some());
+        if !has_url {
+            continue;
+        }
+
+        // Don't keep running on a model whose files are about to be replaced
+        if get_settings(&app_handle).selected_model == verification.model_id {
+            if let Err(e) = transcription_manager.unload_model() {
+                log::warn!("Failed to unload corrupt model: {}", e);
+            }
+        }
+
+        let manager = model_manager.inner().clone();
+        let model_id = verification.model_id.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = manager.redownload_model(&model_id).await {
+                log::error!("Failed to re-download model {}: {}", model_id, e);
+            }
+        });
+        verification.redownloading = true;
+    }
+
+    Ok(results)
+}
+
+/// Imports a model from a local `.bin`/`.tar.gz` file or directory, for machines
+/// that can't reach the download servers. `model_id` forces which catalog model
+/// the file is installed as; without it the file is matched by name or imported
+/// as a custom model.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_model(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+    model_id: Option<String>,
+) -> Result<ModelImport, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        manager.import_model(&PathBuf::from(path), model_id.as_deref())
+    })
+    .await
+    .map_err(|e| format!("Import failed: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
+/// Imports every model in an offline bundle directory.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_model_bundle(
+    model_manager: State<'_, Arc<ModelManager>>,
+    path: String,
+) -> Result<Vec<ModelImport>, String> {
+    let manager = model_manager.inner().clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        manager.import_model_bundle(&PathBuf::from(path))
+    })
+    .await
+    .map_err(|e| format!("Import failed: {}", e))?
+    .map_err(|e| e.to_string())
+}
+
+/// Reloads the model catalog from the configured source.
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_model_catalog(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<Vec<ModelInfo>, String> {
+    model_manager
+        .refresh_catalog()
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(model_manager.get_available_models())
+}
+
+/// Sets a local file or https URL to load the model catalog from, `published`
+/// for the published manifest, or clears it to go back to the catalog shipped
+/// with the app. The setting is only kept if the new catalog loads and
+/// validates.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_model_catalog_source(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    source: Option<String>,
+) -> Result<Vec<ModelInfo>, String> {
+    let source = source
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+
+    let mut settings = get_settings(&app_handle);
+    let previous = settings.model_catalog_source.clone();
+    settings.model_catalog_source = source;
+    write_settings(&app_handle, settings);
+
+    if let Err(e) = model_manager.refresh_catalog().await {
+        let mut settings = get_settings(&app_handle);
+        settings.model_catalog_source = previous;
+        write_settings(&app_handle, settings);
+        return Err(format!("Failed to load model catalog: {}", e));
+    }
+
+    Ok(model_manager.get_available_models())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn cancel_download(
@@ -160,3 +383,59 @@ pub async fn cancel_download(
         .cancel_download(&model_id)
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn pause_download(
+    model_manager: State<'_, Arc<ModelManager>>,
+    model_id: String,
+) -> Result<(), String> {
+    model_manager
+        .pause_download(&model_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_download_queue(
+    model_manager: State<'_, Arc<ModelManager>>,
+) -> Result<DownloadQueueState, String> {
+    Ok(model_manager.get_download_queue())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_max_concurrent_downloads(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    max: usize,
+) -> Result<(), String> {
+    if max == 0 {
+        return Err("At least one download must be allowed at a time".to_string());
+    }
+
+    let mut settings = get_settings(&app_handle);
+    settings.max_concurrent_downloads = max;
+    write_settings(&app_handle, settings);
+
+    // Raising the limit may let waiting downloads start
+    model_manager.download_queue_changed();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_download_bandwidth_limit(
+    app_handle: AppHandle,
+    model_manager: State<'_, Arc<ModelManager>>,
+    kib_per_sec: Option<u64>,
+) -> Result<(), String> {
+    let kib_per_sec = kib_per_sec.filter(|&k| k > 0);
+
+    let mut settings = get_settings(&app_handle);
+    settings.download_bandwidth_limit_kib = kib_per_sec;
+    write_settings(&app_handle, settings);
+
+    model_manager.set_bandwidth_limit(kib_per_sec);
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use crate::managers::history::{HistoryManager, RecordingStorage};
+use crate::managers::model::{ModelManager, ModelStorage};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use tauri::State;
+
+#[derive(Serialize, Debug, Clone, Type)]
+pub struct StorageReport {
+    pub models: ModelStorage,
+    pub recordings: RecordingStorage,
+    pub total_bytes: u64,
+}
+
+/// What `cleanup_storage` may delete. Everything defaults to off so callers
+/// opt in to each kind of removal.
+#[derive(Deserialize, Debug, Clone, Default, Type)]
+#[serde(default)]
+pub struct StorageCleanupOptions {
+    /// Audio files that no history, meeting or TTS entry refers to
+    pub orphaned_recordings: bool,
+    /// Transcription history entries whose audio file is missing
+    pub missing_audio_entries: bool,
+    /// `.extracting` directories from interrupted extractions
+    pub extraction_leftovers: bool,
+    /// `.partial` files of downloads that aren't queued or paused; these can
+    /// no longer be resumed once removed
+    pub partial_downloads: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Default, Type)]
+pub struct StorageCleanupResult {
+    pub files_removed: usize,
+    pub bytes_freed: u64,
+    pub entries_removed: usize,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_storage_report(
+    model_manager: State<'_, Arc<ModelManager>>,
+    history_manager: State<'_, Arc<HistoryManager>>,
+) -> Result<StorageReport, String> {
+    let model_manager = model_manager.inner().clone();
+    let history_manager = history_manager.inner().clone();
+
+    // Walking the models and recordings directories can take a while
+    tauri::async_runtime::spawn_blocking(move || {
+        let models = model_manager.storage_usage();
+        let recordings = history_manager.storage_usage().map_err(|e| e.to_string())?;
+        let total_bytes = models.installed_bytes
+            + models.partial_download_bytes
+            + models.extraction_leftover_bytes
+            + recordings.recordings_bytes
+            + recordings.meetings_bytes
+            + recordings.tts_bytes
+            + recordings.orphaned_bytes
+            + recordings.database_bytes;
+
+        Ok(StorageReport {
+            models,
+            recordings,
+            total_bytes,
+        })
+    })
+    .await
+    .map_err(|e| format!("Storage report task failed: {}", e))?
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn cleanup_storage(
+    model_manager: State<'_, Arc<ModelManager>>,
+    history_manager: State<'_, Arc<HistoryManager>>,
+    options: StorageCleanupOptions,
+) -> Result<StorageCleanupResult, String> {
+    let model_manager = model_manager.inner().clone();
+    let history_manager = history_manager.inner().clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut result = StorageCleanupResult::default();
+
+        if options.orphaned_recordings || options.missing_audio_entries {
+            let (files, bytes, entries) = history_manager
+                .cleanup_storage(options.orphaned_recordings, options.missing_audio_entries)
+                .map_err(|e| e.to_string())?;
+            result.files_removed += files;
+            result.bytes_freed += bytes;
+            result.entries_removed += entries;
+        }
+
+        if options.extraction_leftovers || options.partial_downloads {
+            let (files, bytes) = model_manager
+                .remove_stale_downloads(options.extraction_leftovers, options.partial_downloads)
+                .map_err(|e| e.to_string())?;
+            result.files_removed += files;
+            result.bytes_freed += bytes;
+        }
+
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("Storage cleanup task failed: {}", e))?
+}
@@ -1,5 +1,5 @@
 use crate::managers::transcription::TranscriptionManager;
-use crate::settings::{get_settings, write_settings, ModelUnloadTimeout};
+use crate::settings::{get_settings, write_settings, ModelUnloadTimeout, WhisperDecoding};
 use serde::Serialize;
 use specta::Type;
 use tauri::{AppHandle, State};
@@ -18,6 +18,32 @@ pub fn set_model_unload_timeout(app: AppHandle, timeout: ModelUnloadTimeout) {
     write_settings(&app, settings);
 }
 
+/// Updates Whisper decoding options; they apply from the next transcription.
+#[tauri::command]
+#[specta::specta]
+pub fn set_whisper_decoding(app: AppHandle, decoding: WhisperDecoding) -> Result<(), String> {
+    if !(1..=16).contains(&decoding.beam_size) {
+        return Err("Beam size must be between 1 and 16".to_string());
+    }
+    if !(0.0..=1.0).contains(&decoding.temperature) {
+        return Err("Temperature must be between 0.0 and 1.0".to_string());
+    }
+    if !(0.0..=1.0).contains(&decoding.temperature_increment) {
+        return Err("Temperature increment must be between 0.0 and 1.0".to_string());
+    }
+    if !(0.0..=1.0).contains(&decoding.no_speech_threshold) {
+        return Err("No-speech threshold must be between 0.0 and 1.0".to_string());
+    }
+    if decoding.threads == Some(0) {
+        return Err("Thread count must be at least 1".to_string());
+    }
+
+    let mut settings = get_settings(&app);
+    settings.whisper_decoding = decoding;
+    write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn get_model_load_status(
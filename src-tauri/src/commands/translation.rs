@@ -61,7 +61,8 @@ pub async fn translate_text(
         provider,
         api_key,
         &model,
-        prompt
+        prompt,
+        &settings.network,
     ).await;
     let duration = start.elapsed();
 
@@ -59,13 +59,20 @@ pub async fn speak(
 
     log::info!("Received {} bytes of audio data from TTS service", audio_data.len());
     
-    // Save to history
+    // Save to history, unless it's disabled and nothing would refer to the file
+    if let Some(reason) = history_manager.unavailable_reason() {
+        log::warn!("Not saving TTS audio to history: {}", reason);
+        return Ok(audio_data.to_vec());
+    }
     let timestamp = chrono::Utc::now().timestamp();
     let file_name = format!("tts-{}.wav", timestamp);
     let file_path = history_manager.get_audio_file_path(&file_name);
     
     match std::fs::write(&file_path, &audio_data) {
         Ok(_) => {
+            if let Err(e) = history_manager.seal_recording(&file_path) {
+                log::error!("Failed to encrypt TTS audio file: {}", e);
+            }
             if let Err(e) = history_manager.save_tts_entry(
                 request.text.clone(),
                 settings.tts_voice.clone(),
@@ -73,6 +80,9 @@ pub async fn speak(
             ) {
                 log::error!("Failed to save TTS history entry: {}", e);
             }
+            if let Err(e) = history_manager.cleanup_old_entries() {
+                log::error!("Failed to clean up old history entries: {}", e);
+            }
         }
         Err(e) => {
             log::error!("Failed to save TTS audio file: {}", e);
@@ -0,0 +1,275 @@
+//! Encryption at rest for the history database and recordings.
+//!
+//! The database is encrypted by SQLCipher with a raw key. Recordings are
+//! encrypted file by file with XChaCha20-Poly1305 in the STREAM construction,
+//! so long meetings never need to be held in memory: a file is a magic
+//! header, a random nonce prefix and authenticated chunks.
+
+use anyhow::{bail, Context, Result};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
+use chacha20poly1305::aead::OsRng;
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305};
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Marks an encrypted recording; WAV files start with "RIFF".
+const MAGIC: &[u8; 8] = b"TZENC\x00\x00\x01";
+/// XChaCha20's 24-byte nonce minus STREAM's 4-byte counter and last-chunk flag.
+const NONCE_PREFIX_LEN: usize = 19;
+const CHUNK_LEN: usize = 64 * 1024;
+const TAG_LEN: usize = 16;
+
+/// 256-bit key shared by the database and the recordings.
+#[derive(Clone)]
+pub struct EncryptionKey([u8; 32]);
+
+impl EncryptionKey {
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        Self(key)
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self> {
+        if hex.len() != 64 || !hex.is_ascii() {
+            bail!("Encryption key must be 64 hex digits");
+        }
+        let mut key = [0u8; 32];
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .context("Encryption key must be 64 hex digits")?;
+        }
+        Ok(Self(key))
+    }
+
+    /// Value for SQLCipher's `PRAGMA key`: a raw key, so no key derivation
+    /// runs each time a connection is opened.
+    pub fn sqlcipher_key(&self) -> String {
+        format!("x'{}'", self.to_hex())
+    }
+
+    fn cipher(&self) -> XChaCha20Poly1305 {
+        XChaCha20Poly1305::new((&self.0).into())
+    }
+}
+
+impl std::fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("EncryptionKey(..)")
+    }
+}
+
+/// Whether the file at `path` is an encrypted recording.
+pub fn is_encrypted(path: &Path) -> io::Result<bool> {
+    let mut header = [0u8; MAGIC.len()];
+    let mut file = File::open(path)?;
+    Ok(read_full(&mut file, &mut header)? == MAGIC.len() && &header == MAGIC)
+}
+
+/// Reads until `buf` is full or the input ends, returning the bytes read.
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+pub fn encrypt(key: &EncryptionKey, mut reader: impl Read, mut writer: impl Write) -> Result<()> {
+    let mut nonce = [0u8; NONCE_PREFIX_LEN];
+    OsRng.fill_bytes(&mut nonce);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&nonce)?;
+
+    let mut encryptor = EncryptorBE32::from_aead(key.cipher(), (&nonce).into());
+    let mut chunk = vec![0u8; CHUNK_LEN];
+    loop {
+        let n = read_full(&mut reader, &mut chunk)?;
+        if n < CHUNK_LEN {
+            let sealed = encryptor
+                .encrypt_last(&chunk[..n])
+                .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+            writer.write_all(&sealed)?;
+            break;
+        }
+        let sealed = encryptor
+            .encrypt_next(chunk.as_slice())
+            .map_err(|_| anyhow::anyhow!("Encryption failed"))?;
+        writer.write_all(&sealed)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Decrypts what `encrypt` wrote. Fails if the data was modified, truncated
+/// or encrypted with another key.
+pub fn decrypt(key: &EncryptionKey, mut reader: impl Read, mut writer: impl Write) -> Result<()> {
+    let mut header = [0u8; MAGIC.len() + NONCE_PREFIX_LEN];
+    if read_full(&mut reader, &mut header)? < header.len() || &header[..MAGIC.len()] != MAGIC {
+        bail!("Not an encrypted recording");
+    }
+    let nonce: [u8; NONCE_PREFIX_LEN] = header[MAGIC.len()..].try_into()?;
+
+    let mut decryptor = DecryptorBE32::from_aead(key.cipher(), (&nonce).into());
+    let mut chunk = vec![0u8; CHUNK_LEN + TAG_LEN];
+    loop {
+        let n = read_full(&mut reader, &mut chunk)?;
+        if n < chunk.len() {
+            let plain = decryptor
+                .decrypt_last(&chunk[..n])
+                .map_err(|_| anyhow::anyhow!("Recording is corrupt or the key is wrong"))?;
+            writer.write_all(&plain)?;
+            break;
+        }
+        let plain = decryptor
+            .decrypt_next(chunk.as_slice())
+            .map_err(|_| anyhow::anyhow!("Recording is corrupt or the key is wrong"))?;
+        writer.write_all(&plain)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Encrypts a file in place, unless it already is. The encrypted copy is
+/// written next to it and renamed over it, so the file is never half done.
+pub fn encrypt_file(key: &EncryptionKey, path: &Path) -> Result<()> {
+    if is_encrypted(path)? {
+        return Ok(());
+    }
+    replace_file(path, |reader, writer| encrypt(key, reader, writer))
+}
+
+/// Decrypts a file in place, unless it's plain already.
+pub fn decrypt_file(key: &EncryptionKey, path: &Path) -> Result<()> {
+    if !is_encrypted(path)? {
+        return Ok(());
+    }
+    replace_file(path, |reader, writer| decrypt(key, reader, writer))
+}
+
+/// Copies `source` to `target`, decrypting it if it's encrypted.
+pub fn decrypt_to(key: Option<&EncryptionKey>, source: &Path, target: &Path) -> Result<()> {
+    match key {
+        Some(key) if is_encrypted(source)? => {
+            let reader = BufReader::new(File::open(source)?);
+            let writer = BufWriter::new(File::create(target)?);
+            decrypt(key, reader, writer)
+        }
+        _ => {
+            fs::copy(source, target)?;
+            Ok(())
+        }
+    }
+}
+
+/// Contents of `path`, decrypted if it's encrypted.
+pub fn read_decrypted(key: Option<&EncryptionKey>, path: &Path) -> Result<Vec<u8>> {
+    match key {
+        Some(key) if is_encrypted(path)? => {
+            let mut plain = Vec::new();
+            decrypt(key, BufReader::new(File::open(path)?), &mut plain)?;
+            Ok(plain)
+        }
+        _ => Ok(fs::read(path)?),
+    }
+}
+
+fn replace_file(
+    path: &Path,
+    transform: impl FnOnce(BufReader<File>, BufWriter<File>) -> Result<()>,
+) -> Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+
+    let reader = BufReader::new(File::open(path)?);
+    let transformed = File::create(&temp)
+        .map_err(anyhow::Error::from)
+        .and_then(|file| transform(reader, BufWriter::new(file)));
+    if let Err(e) = transformed {
+        let _ = fs::remove_file(&temp);
+        return Err(e.context(format!("Failed to rewrite {:?}", path)));
+    }
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(len: usize) {
+        let key = EncryptionKey::generate();
+        let plain: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+
+        let mut sealed = Vec::new();
+        encrypt(&key, plain.as_slice(), &mut sealed).unwrap();
+        assert!(sealed.starts_with(MAGIC));
+
+        let mut opened = Vec::new();
+        decrypt(&key, sealed.as_slice(), &mut opened).unwrap();
+        assert_eq!(opened, plain);
+    }
+
+    #[test]
+    fn round_trips_across_chunk_boundaries() {
+        for len in [0, 10, CHUNK_LEN, CHUNK_LEN + 1, 3 * CHUNK_LEN - 7] {
+            round_trip(len);
+        }
+    }
+
+    #[test]
+    fn rejects_tampering_truncation_and_wrong_keys() {
+        let key = EncryptionKey::generate();
+        let plain = vec![7u8; CHUNK_LEN * 2];
+        let mut sealed = Vec::new();
+        encrypt(&key, plain.as_slice(), &mut sealed).unwrap();
+
+        let mut tampered = sealed.clone();
+        tampered[100] ^= 1;
+        assert!(decrypt(&key, tampered.as_slice(), io::sink()).is_err());
+
+        let truncated = &sealed[..MAGIC.len() + NONCE_PREFIX_LEN + CHUNK_LEN + TAG_LEN];
+        assert!(decrypt(&key, truncated, io::sink()).is_err());
+
+        let other = EncryptionKey::generate();
+        assert!(decrypt(&other, sealed.as_slice(), io::sink()).is_err());
+    }
+
+    #[test]
+    fn encrypts_files_in_place_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("typezero-1.wav");
+        fs::write(&path, b"RIFF....WAVE").unwrap();
+        let key = EncryptionKey::generate();
+
+        encrypt_file(&key, &path).unwrap();
+        assert!(is_encrypted(&path).unwrap());
+        let sealed = fs::read(&path).unwrap();
+        encrypt_file(&key, &path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), sealed);
+        assert_eq!(read_decrypted(Some(&key), &path).unwrap(), b"RIFF....WAVE");
+
+        decrypt_file(&key, &path).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"RIFF....WAVE");
+    }
+
+    #[test]
+    fn keys_round_trip_through_hex() {
+        let key = EncryptionKey::generate();
+        let parsed = EncryptionKey::from_hex(&key.to_hex()).unwrap();
+        assert_eq!(parsed.0, key.0);
+        assert!(EncryptionKey::from_hex("xyz").is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Hands-free (wake-on-voice) recording
+//!
+//! When enabled, the microphone stream stays open and the recorder's VAD
+//! listens for speech. Speech onset starts a capture on the `transcribe`
+//! binding, and trailing silence stops it (via `auto_stop`) through the
+//! regular `TranscribeAction` pipeline. An optional wake phrase is checked
+//! on the start of the capture with the smallest downloaded model before the
+//! full transcription runs.
+
+use log::{debug, error, info};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::auto_stop;
+use crate::managers::audio::AudioRecordingManager;
+use crate::managers::transcription::TranscriptionManager;
+use crate::settings::{get_settings, AppSettings};
+use crate::shortcut;
+use crate::tray::{change_tray_icon, TrayIconState};
+use crate::utils::show_recording_overlay;
+use crate::ManagedToggleState;
+
+/// Binding that hands-free captures are routed through
+pub const HANDS_FREE_BINDING: &str = "transcribe";
+/// Shortcut string passed to the action so it knows the capture was hands-free
+pub const HANDS_FREE_SHORTCUT: &str = "hands_free";
+
+/// Seconds of audio from the start of a capture checked for the wake phrase
+const WAKE_PHRASE_WINDOW_SECS: usize = 3;
+const SAMPLE_RATE: usize = 16000;
+
+/// Arms hands-free listening if it is enabled in settings.
+pub fn init(app: &AppHandle) {
+    if get_settings(app).hands_free_enabled {
+        arm(app);
+    }
+}
+
+/// Starts listening for speech on the open microphone stream.
+pub fn arm(app: &AppHandle) {
+    let settings = get_settings(app);
+    let timeout = Duration::from_millis(settings.hands_free_silence_timeout_ms);
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    match rm.start_listening(timeout) {
+        Ok(()) => info!("Hands-free listening armed"),
+        Err(e) => error!("Failed to arm hands-free listening: {}", e),
+    }
+}
+
+/// Stops listening for speech. A capture already in progress keeps running.
+pub fn disarm(app: &AppHandle) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    rm.stop_listening();
+    info!("Hands-free listening disarmed");
+}
+
+/// Re-arms listening after a capture finished or was cancelled.
+pub fn rearm_if_enabled(app: &AppHandle) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if rm.is_listening() && !rm.is_recording() {
+        arm(app);
+    }
+}
+
+/// Handles the recorder starting a capture on its own after hearing speech.
+///
+/// Called on the audio thread, so all work is moved to a separate thread.
+pub fn handle_speech_started(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || on_speech_started(&app));
+}
+
+fn on_speech_started(app: &AppHandle) {
+    let rm = app.state::<Arc<AudioRecordingManager>>();
+    if !rm.adopt_listened_recording(HANDS_FREE_BINDING) {
+        // Listening was disarmed meanwhile; don't leave the capture running
+        rm.discard_listened_recording();
+        return;
+    }
+    debug!("Hands-free: speech detected, capture started");
+
+    // Mark the binding active so pressing the hotkey stops this capture
+    if let Ok(mut states) = app.state::<ManagedToggleState>().lock() {
+        states
+            .active_toggles
+            .insert(HANDS_FREE_BINDING.to_string(), true);
+    }
+
+    let tm = app.state::<Arc<TranscriptionManager>>();
+    tm.initiate_model_load();
+
+    change_tray_icon(app, TrayIconState::Recording);
+    show_recording_overlay(app);
+    shortcut::register_cancel_shortcut(app);
+
+    // The recorder already watches for trailing silence; track it for the countdown
+    let settings = get_settings(app);
+    auto_stop::begin(
+        app,
+        HANDS_FREE_BINDING,
+        HANDS_FREE_SHORTCUT,
+        Some(Duration::from_millis(
+            settings.hands_free_silence_timeout_ms,
+        )),
+        settings.max_recording_secs.map(Duration::from_secs),
+    );
+}
+
+/// Returns the configured wake phrase, if any.
+pub fn wake_phrase(settings: &AppSettings) -> Option<String> {
+    settings
+        .hands_free_wake_phrase
+        .as_ref()
+        .map(|phrase| normalize(phrase))
+        .filter(|phrase| !phrase.is_empty())
+}
+
+/// Transcribes the start of the capture with a small model and checks it
+/// opens with the wake phrase.
+pub fn heard_wake_phrase(tm: &TranscriptionManager, samples: &[f32], phrase: &str) -> bool {
+    let window = samples.len().min(WAKE_PHRASE_WINDOW_SECS * SAMPLE_RATE);
+    match tm.transcribe_quick(samples[..window].to_vec()) {
+        Ok(text) => {
+            let heard = normalize(&text).starts_with(phrase);
+            debug!("Wake phrase check on '{}': {}", text, heard);
+            heard
+        }
+        Err(e) => {
+            error!("Wake phrase check failed: {}", e);
+            false
+        }
+    }
+}
+
+/// Removes a leading wake phrase from a transcription.
+pub fn strip_wake_phrase(text: &str, phrase: &str) -> String {
+    let phrase_words = phrase.split_whitespace().count();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.len() < phrase_words || normalize(&words[..phrase_words].join(" ")) != phrase {
+        return text.to_string();
+    }
+
+    words[phrase_words..]
+        .join(" ")
+        .trim_start_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+        .to_string()
+}
+
+/// Lowercases and strips punctuation so phrases compare loosely.
+fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .map(|w| {
+            w.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_ignores_case_and_punctuation() {
+        assert_eq!(normalize("Hey, Computer!"), "hey computer");
+        assert_eq!(normalize("  ...  "), "");
+    }
+
+    #[test]
+    fn strip_wake_phrase_removes_leading_phrase() {
+        assert_eq!(
+            strip_wake_phrase("Hey computer, send the report.", "hey computer"),
+            "send the report."
+        );
+    }
+
+    #[test]
+    fn strip_wake_phrase_keeps_text_without_phrase() {
+        assert_eq!(
+            strip_wake_phrase("Send the report.", "hey computer"),
+            "Send the report."
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::settings::NetworkSettings;
+use anyhow::{Context, Result};
+use reqwest::{Certificate, ClientBuilder, NoProxy, Proxy};
+use std::fs;
+
+/// Hosts that never go through the proxy: the local TTS service and local LLM
+/// servers such as Ollama.
+const NO_PROXY_HOSTS: &str = "localhost,127.0.0.1,::1";
+
+/// Client builder with the user's proxy and CA bundle applied. Every outgoing
+/// HTTP client should start from here so corporate network settings apply
+/// everywhere.
+pub fn client_builder(network: &NetworkSettings) -> Result<ClientBuilder> {
+    let mut builder = reqwest::Client::builder();
+
+    if let Some(proxy_url) = network.proxy() {
+        let proxy = Proxy::all(proxy_url)
+            .with_context(|| format!("Invalid proxy URL {}", proxy_url))?
+            .no_proxy(NoProxy::from_string(NO_PROXY_HOSTS));
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = network.ca_bundle() {
+        let pem = fs::read(path).with_context(|| format!("Failed to read CA bundle {}", path))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid CA bundle {}", path))?;
+        if certs.is_empty() {
+            anyhow::bail!("CA bundle {} contains no certificates", path);
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    Ok(builder)
+}
+
+pub fn client(network: &NetworkSettings) -> Result<reqwest::Client> {
+    Ok(client_builder(network)?.build()?)
+}
+
+pub fn is_http_url(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// URLs to try for a download, in order: each mirror first, then the original.
+/// Mirrors are base URLs that serve files flat by name, so the original URL's
+/// last path segment is appended to each.
+pub fn mirror_urls(url: &str, mirrors: &[String]) -> Vec<String> {
+    let filename = url
+        .split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty());
+
+    let mut urls: Vec<String> = match filename {
+        Some(filename) => mirrors
+            .iter()
+            .map(|m| m.trim())
+            .filter(|m| !m.is_empty())
+            .map(|m| format!("{}/{}", m.trim_end_matches('/'), filename))
+            .collect(),
+        None => Vec::new(),
+    };
+    urls.retain(|u| u != url);
+    urls.push(url.to_string());
+    urls
+}
+
+/// Environment for child processes that download on their own (the TTS
+/// service installs its Python packages and voices), so they honour the same
+/// proxy and CA bundle.
+pub fn child_env(network: &NetworkSettings) -> Vec<(&'static str, String)> {
+    let mut env = Vec::new();
+    if let Some(proxy_url) = network.proxy() {
+        for key in ["HTTP_PROXY", "HTTPS_PROXY", "http_proxy", "https_proxy"] {
+            env.push((key, proxy_url.to_string()));
+        }
+        env.push(("NO_PROXY", NO_PROXY_HOSTS.to_string()));
+        env.push(("no_proxy", NO_PROXY_HOSTS.to_string()));
+    }
+    if let Some(path) = network.ca_bundle() {
+        for key in ["SSL_CERT_FILE", "REQUESTS_CA_BUNDLE", "PIP_CERT"] {
+            env.push((key, path.to_string()));
+        }
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mirrors(urls: &[&str]) -> Vec<String> {
+        urls.iter().map(|u| u.to_string()).collect()
+    }
+
+    #[test]
+    fn mirrors_are_tried_before_the_original() {
+        let urls = mirror_urls(
+            "https://blob.handy.computer/ggml-small.bin",
+            &mirrors(&["https://mirror.corp/models/", "http://10.0.0.5/m"]),
+        );
+        assert_eq!(
+            urls,
+            vec![
+                "https://mirror.corp/models/ggml-small.bin",
+                "http://10.0.0.5/m/ggml-small.bin",
+                "https://blob.handy.computer/ggml-small.bin",
+            ]
+        );
+    }
+
+    #[test]
+    fn mirror_urls_drop_query_and_blank_entries() {
+        let urls = mirror_urls(
+            "https://example.com/voices/en_US-joy-medium.onnx?download=true",
+            &mirrors(&["  ", "https://mirror.corp"]),
+        );
+        assert_eq!(urls[0], "https://mirror.corp/en_US-joy-medium.onnx");
+        assert_eq!(urls.len(), 2);
+    }
+
+    #[test]
+    fn without_mirrors_only_the_original_is_used() {
+        let url = "https://blob.handy.computer/moonshine-base.tar.gz";
+        assert_eq!(mirror_urls(url, &[]), vec![url]);
+    }
+
+    #[test]
+    fn rejects_missing_ca_bundle() {
+        let network = NetworkSettings {
+            ca_bundle_path: Some("/nonexistent/ca.pem".to_string()),
+            ..Default::default()
+        };
+        assert!(client_builder(&network).is_err());
+    }
+}
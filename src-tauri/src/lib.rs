@@ -3,13 +3,20 @@ mod actions;
 mod apple_intelligence;
 mod audio_feedback;
 pub mod audio_toolkit;
+mod auto_stop;
+mod backup;
 mod clipboard;
 mod commands;
+mod encryption;
+mod hands_free;
 mod helpers;
+mod http;
 mod input;
 mod llm_client;
 mod managers;
 mod overlay;
+mod playback;
+mod secrets;
 mod settings;
 mod shortcut;
 mod signal_handle;
@@ -118,7 +125,11 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     // after onboarding completes. This avoids triggering permission dialogs
     // on macOS before the user is ready.
 
-    // Initialize the managers
+    // Initialize the managers. Diagnostics come first so the model manager
+    // can size its first pick to the machine's memory.
+    let diagnostic_manager = Arc::new(DiagnosticManager::new(app_handle));
+    app_handle.manage(diagnostic_manager.clone());
+
     let recording_manager = Arc::new(
         AudioRecordingManager::new(app_handle).expect("Failed to initialize recording manager"),
     );
@@ -137,6 +148,40 @@ fn initialize_core_logic(app_handle: &AppHandle) {
     app_handle.manage(transcription_manager.clone());
     app_handle.manage(history_manager.clone());
 
+    // Apply retention to whatever aged out while the app wasn't running
+    if history_manager.unavailable_reason().is_none() {
+        let history_manager = history_manager.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            if let Err(e) = history_manager.cleanup_old_entries() {
+                log::warn!("Failed to clean up old history entries: {}", e);
+            }
+        });
+    }
+
+    // Pick up catalog changes in the background when it comes from a URL
+    let catalog_source = settings::get_settings(app_handle).model_catalog_source;
+    if matches!(
+        managers::model_catalog::CatalogSource::from_setting(catalog_source.as_deref()),
+        managers::model_catalog::CatalogSource::Remote(_)
+    ) {
+        let model_manager = model_manager.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = model_manager.refresh_catalog().await {
+                log::warn!("Failed to refresh model catalog: {}", e);
+            }
+        });
+    }
+
+    // Resume downloads that were still queued when the app last exited
+    for model_id in model_manager.pending_downloads() {
+        let model_manager = model_manager.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = model_manager.download_model(&model_id).await {
+                log::warn!("Failed to resume download of {}: {}", model_id, e);
+            }
+        });
+    }
+
     let meeting_manager = Arc::new(
         MeetingManager::new(
             app_handle,
@@ -161,9 +206,9 @@ fn initialize_core_logic(app_handle: &AppHandle) {
         .expect("Failed to initialize translation manager"),
     );
     app_handle.manage(translation_manager.clone());
-    
-    let diagnostic_manager = Arc::new(DiagnosticManager::new(app_handle));
-    app_handle.manage(diagnostic_manager.clone());
+
+    // Start waiting for speech if hands-free mode is enabled
+    hands_free::init(app_handle);
 
     // Note: Shortcuts are NOT initialized here.
     // The frontend is responsible for calling the `initialize_shortcuts` command
@@ -302,6 +347,8 @@ pub fn run() {
         shortcut::change_autostart_setting,
         shortcut::change_translate_to_english_setting,
         shortcut::change_selected_language_setting,
+        shortcut::change_auto_detect_languages_setting,
+        shortcut::change_auto_chinese_variant_setting,
         shortcut::change_overlay_position_setting,
         shortcut::change_debug_mode_setting,
         shortcut::change_word_correction_threshold_setting,
@@ -322,11 +369,13 @@ pub fn run() {
         shortcut::update_post_process_prompt,
         shortcut::delete_post_process_prompt,
         shortcut::set_post_process_selected_prompt,
+        shortcut::set_post_process_language_prompt,
         shortcut::update_custom_words,
         shortcut::suspend_binding,
         shortcut::resume_binding,
         shortcut::change_mute_while_recording_setting,
         shortcut::change_append_trailing_space_setting,
+        shortcut::change_undo_reopens_recording_setting,
         shortcut::change_app_language_setting,
         shortcut::change_update_checks_setting,
         shortcut::change_telemetry_setting,
@@ -338,6 +387,11 @@ pub fn run() {
         shortcut::change_tts_enabled_setting,
         shortcut::change_tts_voice_setting,
         shortcut::change_tts_speed_setting,
+        shortcut::change_hands_free_setting,
+        shortcut::change_hands_free_silence_timeout_setting,
+        shortcut::change_hands_free_wake_phrase_setting,
+        shortcut::change_auto_stop_silence_setting,
+        shortcut::change_max_recording_duration_setting,
         shortcut::typezero_keys::start_typezero_keys_recording,
         shortcut::typezero_keys::stop_typezero_keys_recording,
         trigger_update_check,
@@ -347,6 +401,7 @@ pub fn run() {
         commands::get_default_settings,
         commands::get_log_dir_path,
         commands::set_log_level,
+        commands::set_network_settings,
         commands::open_recordings_folder,
         commands::open_log_dir,
         commands::open_app_data_dir,
@@ -358,6 +413,20 @@ pub fn run() {
         commands::models::download_model,
         commands::models::delete_model,
         commands::models::cancel_download,
+        commands::models::pause_download,
+        commands::models::get_download_queue,
+        commands::models::set_max_concurrent_downloads,
+        commands::models::set_download_bandwidth_limit,
+        commands::models::verify_installed_models,
+        commands::models::run_model_benchmark,
+        commands::models::get_model_benchmarks,
+        commands::models::get_model_recommendations,
+        commands::storage::get_storage_report,
+        commands::storage::cleanup_storage,
+        commands::models::import_model,
+        commands::models::import_model_bundle,
+        commands::models::refresh_model_catalog,
+        commands::models::set_model_catalog_source,
         commands::models::set_active_model,
         commands::models::get_current_model,
         commands::models::get_current_tts_model,
@@ -378,15 +447,44 @@ pub fn run() {
         commands::audio::set_clamshell_microphone,
         commands::audio::get_clamshell_microphone,
         commands::audio::is_recording,
+        commands::audio::set_noise_suppression,
+        commands::audio::set_auto_gain_control,
+        commands::audio::set_high_pass_filter,
+        commands::audio::set_vad_backend,
+        commands::audio::set_vad_tuning,
+        commands::audio::calibrate_vad,
+        commands::audio::test_microphone,
         commands::transcription::set_model_unload_timeout,
+        commands::transcription::set_whisper_decoding,
         commands::transcription::get_model_load_status,
         commands::transcription::unload_model_manually,
         commands::history::get_history_entries,
         commands::history::toggle_history_entry_saved,
         commands::history::get_audio_file_path,
         commands::history::delete_history_entry,
+        commands::history::retranscribe_history_entry,
+        commands::history::get_history_entry_versions,
+        commands::history::copy_history_text,
+        commands::history::paste_history_text,
+        commands::history::edit_history_entry,
+        commands::history::get_history_entry_revisions,
+        commands::history::set_entry_title,
+        commands::history::set_entry_tags,
+        commands::history::set_entry_notes,
+        commands::history::get_all_tags,
+        commands::history::generate_entry_title,
+        commands::backup::export_backup,
+        commands::backup::import_backup,
+        commands::history::get_history_unavailable_reason,
+        commands::history::get_history_encryption,
+        commands::history::set_history_encryption,
         commands::history::update_history_limit,
         commands::history::update_recording_retention_period,
+        commands::history::get_retention_policies,
+        commands::history::update_retention_policies,
+        commands::history::preview_retention_cleanup,
+        commands::history::update_recording_format,
+        commands::history::reencode_recordings,
         commands::history::get_tts_history,
         commands::history::toggle_tts_favorite,
         commands::history::delete_tts_entry,
@@ -466,6 +564,12 @@ pub fn run() {
             Some(vec![]),
         ))
         .manage(Mutex::new(ShortcutToggleStates::default()))
+        .register_asynchronous_uri_scheme_protocol(playback::SCHEME, |ctx, request, responder| {
+            let app_handle = ctx.app_handle().clone();
+            tauri::async_runtime::spawn_blocking(move || {
+                responder.respond(playback::handle_request(&app_handle, &request));
+            });
+        })
         .setup(move |app| {
             let settings = get_settings(&app.handle());
             let tauri_log_level: tauri_plugin_log::LogLevel = settings.log_level.into();
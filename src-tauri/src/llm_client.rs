@@ -1,4 +1,4 @@
-use crate::settings::PostProcessProvider;
+use crate::settings::{NetworkSettings, PostProcessProvider};
 use log::debug;
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, REFERER, USER_AGENT};
 use serde::{Deserialize, Serialize};
@@ -67,10 +67,16 @@ fn build_headers(provider: &PostProcessProvider, api_key: &str) -> Result<Header
     Ok(headers)
 }
 
-/// Create an HTTP client with provider-specific headers
-fn create_client(provider: &PostProcessProvider, api_key: &str) -> Result<reqwest::Client, String> {
+/// Create an HTTP client with provider-specific headers and the user's
+/// proxy and CA settings
+fn create_client(
+    provider: &PostProcessProvider,
+    api_key: &str,
+    network: &NetworkSettings,
+) -> Result<reqwest::Client, String> {
     let headers = build_headers(provider, api_key)?;
-    reqwest::Client::builder()
+    crate::http::client_builder(network)
+        .map_err(|e| e.to_string())?
         .default_headers(headers)
         .build()
         .map_err(|e| format!("Failed to build HTTP client: {}", e))
@@ -84,13 +90,14 @@ pub async fn send_chat_completion(
     api_key: String,
     model: &str,
     prompt: String,
+    network: &NetworkSettings,
 ) -> Result<Option<String>, String> {
     let base_url = provider.base_url.trim_end_matches('/');
     let url = format!("{}/chat/completions", base_url);
 
     debug!("Sending chat completion request to: {}", url);
 
-    let client = create_client(provider, &api_key)?;
+    let client = create_client(provider, &api_key, network)?;
 
     let request_body = ChatCompletionRequest {
         model: model.to_string(),
@@ -135,13 +142,14 @@ pub async fn send_chat_completion(
 pub async fn fetch_models(
     provider: &PostProcessProvider,
     api_key: String,
+    network: &NetworkSettings,
 ) -> Result<Vec<String>, String> {
     let base_url = provider.base_url.trim_end_matches('/');
     let url = format!("{}/models", base_url);
 
     debug!("Fetching models from: {}", url);
 
-    let client = create_client(provider, &api_key)?;
+    let client = create_client(provider, &api_key, network)?;
 
     let response = client
         .get(&url)
@@ -1,10 +1,18 @@
-use crate::audio_toolkit::{list_input_devices, vad::SmoothedVad, AudioRecorder, SileroVad};
+use crate::audio_toolkit::audio::{Capture, SpeechEvent};
+use crate::audio_toolkit::{
+    get_cpal_host, list_input_devices,
+    vad::{EnergyVad, SmoothedVad},
+    AudioRecorder, DspConfig, ProcessingChain, SileroVad, VoiceActivityDetector,
+};
 use crate::helpers::clamshell;
-use crate::settings::{get_settings, AppSettings};
+use crate::settings::{get_settings, AppSettings, VadBackend};
 use crate::utils;
+use crate::{auto_stop, hands_free};
+use cpal::traits::{DeviceTrait, HostTrait};
 use log::{debug, error, info};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tauri::Manager;
 
 fn set_mute(mute: bool) {
@@ -114,24 +122,63 @@ pub enum MicrophoneMode {
 
 /* ──────────────────────────────────────────────────────────────── */
 
-fn create_audio_recorder(
+/// Pre-processing stages enabled in settings
+fn dsp_config(settings: &AppSettings) -> DspConfig {
+    DspConfig {
+        high_pass: settings.high_pass_filter,
+        noise_suppression: settings.noise_suppression,
+        auto_gain: settings.auto_gain_control,
+    }
+}
+
+/// Builds the VAD selected in settings, smoothed with the configured frame counts
+fn create_vad(
     vad_path: &str,
+    settings: &AppSettings,
+) -> Result<Box<dyn VoiceActivityDetector>, anyhow::Error> {
+    let tuning = settings.vad_tuning;
+    let inner: Box<dyn VoiceActivityDetector> = match settings.vad_backend {
+        VadBackend::Silero => Box::new(
+            SileroVad::new(vad_path, tuning.threshold)
+                .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?,
+        ),
+        VadBackend::Energy => Box::new(
+            EnergyVad::new(tuning.energy_margin_db)
+                .map_err(|e| anyhow::anyhow!("Failed to create EnergyVad: {}", e))?,
+        ),
+    };
+
+    Ok(Box::new(SmoothedVad::new(
+        inner,
+        tuning.prefill_frames,
+        tuning.hangover_frames,
+        tuning.onset_frames,
+    )))
+}
+
+fn create_audio_recorder(
+    vad: Box<dyn VoiceActivityDetector>,
+    processing: Arc<Mutex<ProcessingChain>>,
     app_handle: &tauri::AppHandle,
 ) -> Result<AudioRecorder, anyhow::Error> {
-    let silero = SileroVad::new(vad_path, 0.3)
-        .map_err(|e| anyhow::anyhow!("Failed to create SileroVad: {}", e))?;
-    let smoothed_vad = SmoothedVad::new(Box::new(silero), 15, 15, 2);
-
     // Recorder with VAD plus a spectrum-level callback that forwards updates to
     // the frontend.
     let recorder = AudioRecorder::new()
         .map_err(|e| anyhow::anyhow!("Failed to create AudioRecorder: {}", e))?
-        .with_vad(Box::new(smoothed_vad))
+        .with_vad(vad)
+        .with_processing(processing)
         .with_level_callback({
             let app_handle = app_handle.clone();
             move |levels| {
                 utils::emit_levels(&app_handle, &levels);
             }
+        })
+        .with_speech_callback({
+            let app_handle = app_handle.clone();
+            move |event| match event {
+                SpeechEvent::Started => hands_free::handle_speech_started(&app_handle),
+                _ => auto_stop::handle_speech_event(&app_handle, event),
+            }
         });
 
     Ok(recorder)
@@ -146,8 +193,12 @@ pub struct AudioRecordingManager {
     app_handle: tauri::AppHandle,
 
     recorder: Arc<Mutex<Option<AudioRecorder>>>,
+    /// Pre-processing run before the VAD, shared with the recorder thread
+    processing: Arc<Mutex<ProcessingChain>>,
     is_open: Arc<Mutex<bool>>,
     is_recording: Arc<Mutex<bool>>,
+    /// Silence timeout while hands-free listening is armed
+    listen_timeout: Arc<Mutex<Option<Duration>>>,
     did_mute: Arc<Mutex<bool>>,
 }
 
@@ -168,8 +219,12 @@ impl AudioRecordingManager {
             app_handle: app.clone(),
 
             recorder: Arc::new(Mutex::new(None)),
+            processing: Arc::new(Mutex::new(ProcessingChain::from_config(&dsp_config(
+                &settings,
+            )))),
             is_open: Arc::new(Mutex::new(false)),
             is_recording: Arc::new(Mutex::new(false)),
+            listen_timeout: Arc::new(Mutex::new(None)),
             did_mute: Arc::new(Mutex::new(false)),
         };
 
@@ -210,6 +265,25 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Name of the input device the stream opens on: the clamshell or selected
+    /// microphone, or the system default when neither is connected.
+    pub fn effective_microphone_name(&self) -> Option<String> {
+        let settings = get_settings(&self.app_handle);
+        self.get_effective_microphone_device(&settings)
+            .or_else(|| get_cpal_host().default_input_device())
+            .and_then(|device| device.name().ok())
+    }
+
+    pub fn vad_model_path(&self) -> Result<PathBuf, anyhow::Error> {
+        self.app_handle
+            .path()
+            .resolve(
+                "resources/models/silero_vad_v4.onnx",
+                tauri::path::BaseDirectory::Resource,
+            )
+            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))
+    }
+
     /* ---------- microphone life-cycle -------------------------------------- */
 
     /// Applies mute if mute_while_recording is enabled and stream is open
@@ -247,25 +321,19 @@ impl AudioRecordingManager {
         let mut did_mute_guard = self.did_mute.lock().unwrap();
         *did_mute_guard = false;
 
-        let vad_path = self
-            .app_handle
-            .path()
-            .resolve(
-                "resources/models/silero_vad_v4.onnx",
-                tauri::path::BaseDirectory::Resource,
-            )
-            .map_err(|e| anyhow::anyhow!("Failed to resolve VAD path: {}", e))?;
+        let settings = get_settings(&self.app_handle);
         let mut recorder_opt = self.recorder.lock().unwrap();
 
         if recorder_opt.is_none() {
+            let vad_path = self.vad_model_path()?;
             *recorder_opt = Some(create_audio_recorder(
-                vad_path.to_str().unwrap(),
+                create_vad(vad_path.to_str().unwrap(), &settings)?,
+                self.processing.clone(),
                 &self.app_handle,
             )?);
         }
 
         // Get the selected device from settings, considering clamshell mode
-        let settings = get_settings(&self.app_handle);
         let selected_device = self.get_effective_microphone_device(&settings);
 
         if let Some(rec) = recorder_opt.as_mut() {
@@ -306,6 +374,60 @@ impl AudioRecordingManager {
         debug!("Microphone stream stopped");
     }
 
+    /// Rebuilds the pre-processing chain from settings. Takes effect on the
+    /// next frame, including on an open stream.
+    pub fn update_audio_processing(&self) {
+        let config = dsp_config(&get_settings(&self.app_handle));
+        *self.processing.lock().unwrap() = ProcessingChain::from_config(&config);
+        debug!("Audio processing updated: {:?}", config);
+    }
+
+    /// Rebuilds the VAD from settings. Takes effect on the next frame; if no
+    /// recorder exists yet it picks the settings up when it is created.
+    pub fn update_vad(&self) -> Result<(), anyhow::Error> {
+        let mut recorder = self.recorder.lock().unwrap();
+        if let Some(rec) = recorder.as_mut() {
+            let settings = get_settings(&self.app_handle);
+            let vad_path = self.vad_model_path()?;
+            rec.set_vad(create_vad(vad_path.to_str().unwrap(), &settings)?);
+            debug!(
+                "VAD updated: {:?} {:?}",
+                settings.vad_backend, settings.vad_tuning
+            );
+        }
+        Ok(())
+    }
+
+    /// Builds a fresh VAD from settings, for analysing audio outside the recorder.
+    pub fn build_vad(&self) -> Result<Box<dyn VoiceActivityDetector>, anyhow::Error> {
+        let vad_path = self.vad_model_path()?;
+        create_vad(vad_path.to_str().unwrap(), &get_settings(&self.app_handle))
+    }
+
+    /// Records `duration` of audio as the VAD would see it, without running
+    /// the VAD. Works alongside a running capture without disturbing it.
+    pub fn capture_raw(&self, duration: Duration) -> Result<Capture, anyhow::Error> {
+        self.start_microphone_stream()?;
+
+        let wanted = (duration.as_secs_f32() * WHISPER_SAMPLE_RATE as f32) as usize;
+        let rx = match self.recorder.lock().unwrap().as_ref() {
+            Some(rec) => rec
+                .capture(wanted)
+                .map_err(|e| anyhow::anyhow!("Failed to capture audio: {}", e))?,
+            None => return Err(anyhow::anyhow!("Recorder not available")),
+        };
+
+        // Don't hold the recorder lock while the audio arrives
+        let result = rx
+            .recv_timeout(duration + Duration::from_secs(2))
+            .map_err(|_| anyhow::anyhow!("Timed out waiting for microphone audio"));
+
+        if !self.is_recording() {
+            self.release_microphone_stream();
+        }
+        result
+    }
+
     /* ---------- mode switching --------------------------------------------- */
 
     pub fn update_mode(&self, new_mode: MicrophoneMode) -> Result<(), anyhow::Error> {
@@ -314,7 +436,9 @@ impl AudioRecordingManager {
 
         match (cur_mode, &new_mode) {
             (MicrophoneMode::AlwaysOn, MicrophoneMode::OnDemand) => {
-                if matches!(*self.state.lock().unwrap(), RecordingState::Idle) {
+                if matches!(*self.state.lock().unwrap(), RecordingState::Idle)
+                    && !self.is_listening()
+                {
                     drop(mode_guard);
                     self.stop_microphone_stream();
                 }
@@ -361,11 +485,115 @@ impl AudioRecordingManager {
         }
     }
 
+    /// Marks a capture that the recorder started on its own (hands-free) as
+    /// the active recording for `binding_id`.
+    pub fn adopt_listened_recording(&self, binding_id: &str) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !matches!(*state, RecordingState::Idle) || !self.is_listening() {
+            return false;
+        }
+
+        *self.is_recording.lock().unwrap() = true;
+        *state = RecordingState::Recording {
+            binding_id: binding_id.to_string(),
+        };
+        debug!("Hands-free recording started for binding {binding_id}");
+        true
+    }
+
+    /// Drops a capture the recorder started on its own that wasn't adopted,
+    /// then listens again if hands-free is still armed.
+    pub fn discard_listened_recording(&self) {
+        let state = self.state.lock().unwrap();
+        if !matches!(*state, RecordingState::Idle) {
+            // A recording started meanwhile and owns the recorder
+            return;
+        }
+
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            let _ = rec.stop(); // Discard the result
+            let listen_timeout = *self.listen_timeout.lock().unwrap();
+            if let Some(timeout) = listen_timeout {
+                let _ = rec.listen(timeout);
+            }
+        }
+        drop(state);
+
+        self.release_microphone_stream();
+        debug!("Discarded a hands-free capture nothing adopted");
+    }
+
+    /// Asks the recorder to report when the current capture falls silent for `timeout`.
+    pub fn watch_silence(&self, timeout: Duration) {
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            if let Err(e) = rec.watch_silence(timeout) {
+                error!("Failed to watch for silence: {e}");
+            }
+        }
+    }
+
+    /* ---------- hands-free listening --------------------------------------- */
+
+    /// Opens the microphone and waits for speech to start a capture.
+    pub fn start_listening(&self, silence_timeout: Duration) -> Result<(), anyhow::Error> {
+        if !matches!(*self.state.lock().unwrap(), RecordingState::Idle) {
+            return Ok(());
+        }
+
+        self.start_microphone_stream()?;
+
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            rec.listen(silence_timeout)
+                .map_err(|e| anyhow::anyhow!("Failed to start listening: {}", e))?;
+        }
+
+        *self.listen_timeout.lock().unwrap() = Some(silence_timeout);
+        debug!(
+            "Listening for speech (silence timeout {:?})",
+            silence_timeout
+        );
+        Ok(())
+    }
+
+    pub fn stop_listening(&self) {
+        if self.listen_timeout.lock().unwrap().take().is_none() {
+            return;
+        }
+
+        if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+            let _ = rec.unlisten();
+        }
+
+        if matches!(*self.state.lock().unwrap(), RecordingState::Idle) {
+            self.release_microphone_stream();
+        }
+        debug!("Stopped listening for speech");
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.listen_timeout.lock().unwrap().is_some()
+    }
+
+    /// In on-demand mode turn the mic off again, unless hands-free still needs it
+    fn release_microphone_stream(&self) {
+        if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) && !self.is_listening() {
+            self.stop_microphone_stream();
+        }
+    }
+
     pub fn update_selected_device(&self) -> Result<(), anyhow::Error> {
         // If currently open, restart the microphone stream to use the new device
         if *self.is_open.lock().unwrap() {
             self.stop_microphone_stream();
             self.start_microphone_stream()?;
+
+            // The new stream starts out idle, so re-arm hands-free listening
+            let listen_timeout = *self.listen_timeout.lock().unwrap();
+            if let Some(timeout) = listen_timeout {
+                if let Some(rec) = self.recorder.lock().unwrap().as_ref() {
+                    let _ = rec.listen(timeout);
+                }
+            }
         }
         Ok(())
     }
@@ -395,10 +623,7 @@ impl AudioRecordingManager {
 
                 *self.is_recording.lock().unwrap() = false;
 
-                // In on-demand mode turn the mic off again
-                if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
-                    self.stop_microphone_stream();
-                }
+                self.release_microphone_stream();
 
                 // Pad if very short
                 let s_len = samples.len();
@@ -450,10 +675,7 @@ impl AudioRecordingManager {
 
             *self.is_recording.lock().unwrap() = false;
 
-            // In on-demand mode turn the mic off again
-            if matches!(*self.mode.lock().unwrap(), MicrophoneMode::OnDemand) {
-                self.stop_microphone_stream();
-            }
+            self.release_microphone_stream();
         }
     }
 }
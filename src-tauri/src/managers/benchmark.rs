@@ -0,0 +1,253 @@
+use crate::managers::model::{EngineType, ModelInfo};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Bundled clip every model transcribes during a benchmark (16 kHz mono).
+pub const REFERENCE_CLIP: &str = "resources/benchmark_reference.wav";
+
+/// Models slower than this real-time factor (processing time divided by audio
+/// length) make dictation feel sluggish, so they aren't recommended and the
+/// user is warned when selecting one.
+pub const MAX_COMFORTABLE_RTF: f64 = 0.5;
+
+/// Rough peak memory of a loaded model relative to its size on disk.
+const MEMORY_PER_MODEL_BYTE: f64 = 2.0;
+
+/// Share of total RAM a model may use before it's considered too large.
+const MAX_MEMORY_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelBenchmark {
+    pub model_id: String,
+    /// Processing time divided by audio duration; below 1.0 is faster than real time
+    pub real_time_factor: f64,
+    pub load_ms: u64,
+    pub transcribe_ms: u64,
+    pub timestamp: i64,
+}
+
+/// Payload of the `model-benchmark-progress` event, sent before each model runs.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct BenchmarkProgress {
+    pub model_id: String,
+    pub index: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ModelRecommendation {
+    pub model_id: String,
+    /// Measured, or estimated from a benchmarked model when `measured` is false
+    pub real_time_factor: Option<f64>,
+    pub measured: bool,
+    pub too_slow: bool,
+    pub fits_in_memory: bool,
+    /// The single best model for this machine
+    pub recommended: bool,
+}
+
+/// Whether a model can be benchmarked, i.e. is a speech-to-text model.
+pub fn is_benchmarkable(model: &ModelInfo) -> bool {
+    matches!(
+        model.engine_type,
+        EngineType::Whisper | EngineType::Parakeet | EngineType::Moonshine | EngineType::SenseVoice
+    )
+}
+
+pub fn is_too_slow(real_time_factor: f64) -> bool {
+    real_time_factor > MAX_COMFORTABLE_RTF
+}
+
+pub fn load_results(path: &Path) -> HashMap<String, ModelBenchmark> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<Vec<ModelBenchmark>>(&json).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|b| (b.model_id.clone(), b))
+        .collect()
+}
+
+pub fn save_results(path: &Path, results: &HashMap<String, ModelBenchmark>) -> Result<()> {
+    let mut list: Vec<&ModelBenchmark> = results.values().collect();
+    list.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+    fs::write(path, serde_json::to_string_pretty(&list)?)?;
+    Ok(())
+}
+
+/// Real-time factor for a model: measured if it was benchmarked, otherwise
+/// scaled from the benchmarked model with the closest catalog speed score.
+fn estimate_rtf(
+    model: &ModelInfo,
+    models: &HashMap<String, ModelInfo>,
+    results: &HashMap<String, ModelBenchmark>,
+) -> Option<(f64, bool)> {
+    if let Some(result) = results.get(&model.id) {
+        return Some((result.real_time_factor, true));
+    }
+    if model.speed_score <= 0.0 {
+        return None;
+    }
+
+    results
+        .values()
+        .filter_map(|r| models.get(&r.model_id).map(|m| (m, r)))
+        .filter(|(m, _)| m.speed_score > 0.0)
+        .min_by(|(a, _), (b, _)| {
+            let da = (a.speed_score - model.speed_score).abs();
+            let db = (b.speed_score - model.speed_score).abs();
+            da.total_cmp(&db)
+        })
+        .map(|(m, r)| {
+            let scale = m.speed_score as f64 / model.speed_score as f64;
+            (r.real_time_factor * scale, false)
+        })
+}
+
+/// Ranks speech-to-text models for this machine. The recommended model is the
+/// most accurate one that is fast enough and fits in memory, preferring
+/// measured results over estimates. Without any benchmarks the catalog's
+/// `is_recommended` flag is kept.
+pub fn recommend(
+    models: &HashMap<String, ModelInfo>,
+    results: &HashMap<String, ModelBenchmark>,
+    memory_total_gb: Option<f64>,
+) -> Vec<ModelRecommendation> {
+    let mut recommendations: Vec<ModelRecommendation> = models
+        .values()
+        .filter(|m| is_benchmarkable(m))
+        .map(|model| {
+            let estimate = estimate_rtf(model, models, results);
+            let fits_in_memory = memory_total_gb.is_none_or(|total| {
+                let needed_gb = model.size_mb as f64 / 1024.0 * MEMORY_PER_MODEL_BYTE;
+                needed_gb <= total * MAX_MEMORY_SHARE
+            });
+            ModelRecommendation {
+                model_id: model.id.clone(),
+                real_time_factor: estimate.map(|(rtf, _)| rtf),
+                measured: estimate.is_some_and(|(_, measured)| measured),
+                too_slow: estimate.is_some_and(|(rtf, _)| is_too_slow(rtf)),
+                fits_in_memory,
+                recommended: false,
+            }
+        })
+        .collect();
+    recommendations.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+
+    let best = if results.is_empty() {
+        recommendations
+            .iter()
+            .position(|r| r.fits_in_memory && models[&r.model_id].is_recommended)
+    } else {
+        recommendations
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.real_time_factor.is_some() && !r.too_slow && r.fits_in_memory)
+            .max_by(|(_, a), (_, b)| {
+                let accuracy = |r: &ModelRecommendation| models[&r.model_id].accuracy_score;
+                a.measured
+                    .cmp(&b.measured)
+                    .then(accuracy(a).total_cmp(&accuracy(b)))
+                    .then(
+                        // Among equals, the faster one
+                        b.real_time_factor
+                            .unwrap_or(f64::MAX)
+                            .total_cmp(&a.real_time_factor.unwrap_or(f64::MAX)),
+                    )
+            })
+            .map(|(i, _)| i)
+    };
+    if let Some(i) = best {
+        recommendations[i].recommended = true;
+    }
+
+    recommendations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::managers::model_catalog;
+
+    fn catalog() -> HashMap<String, ModelInfo> {
+        model_catalog::bundled()
+            .into_iter()
+            .map(|m| (m.id.clone(), m))
+            .collect()
+    }
+
+    fn benchmark(model_id: &str, real_time_factor: f64) -> (String, ModelBenchmark) {
+        (
+            model_id.to_string(),
+            ModelBenchmark {
+                model_id: model_id.to_string(),
+                real_time_factor,
+                load_ms: 0,
+                transcribe_ms: 0,
+                timestamp: 0,
+            },
+        )
+    }
+
+    fn recommended(recommendations: &[ModelRecommendation]) -> Option<&str> {
+        recommendations
+            .iter()
+            .find(|r| r.recommended)
+            .map(|r| r.model_id.as_str())
+    }
+
+    #[test]
+    fn picks_most_accurate_model_that_is_fast_enough() {
+        let models = catalog();
+        let results: HashMap<_, _> = [benchmark("small", 0.2), benchmark("large", 0.9)]
+            .into_iter()
+            .collect();
+
+        let recommendations = recommend(&models, &results, Some(16.0));
+        assert_eq!(recommended(&recommendations), Some("small"));
+
+        let large = recommendations
+            .iter()
+            .find(|r| r.model_id == "large")
+            .unwrap();
+        assert!(large.measured);
+        assert!(large.too_slow);
+    }
+
+    #[test]
+    fn estimates_unmeasured_models_from_benchmarks() {
+        let models = catalog();
+        let results: HashMap<_, _> = [benchmark("small", 0.2)].into_iter().collect();
+
+        let recommendations = recommend(&models, &results, None);
+        let medium = recommendations
+            .iter()
+            .find(|r| r.model_id == "medium")
+            .unwrap();
+        assert!(!medium.measured);
+        assert!(medium.real_time_factor.is_some());
+    }
+
+    #[test]
+    fn falls_back_to_catalog_flag_without_benchmarks() {
+        let models = catalog();
+        let recommendations = recommend(&models, &HashMap::new(), None);
+        let expected = models.values().find(|m| m.is_recommended).unwrap();
+        assert_eq!(recommended(&recommendations), Some(expected.id.as_str()));
+    }
+
+    #[test]
+    fn large_models_do_not_fit_in_small_memory() {
+        let models = catalog();
+        let recommendations = recommend(&models, &HashMap::new(), Some(1.0));
+        let large = recommendations
+            .iter()
+            .find(|r| r.model_id == "large")
+            .unwrap();
+        assert!(!large.fits_in_memory);
+    }
+}
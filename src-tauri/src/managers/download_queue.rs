@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuedDownloadStatus {
+    /// Waiting for a free download slot
+    Queued,
+    Downloading,
+    /// Stopped by the user; the partial file is kept for resuming
+    Paused,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Type)]
+pub struct QueuedDownload {
+    pub model_id: String,
+    pub status: QueuedDownloadStatus,
+}
+
+/// Ordered list of downloads the user asked for. Downloads start in order as
+/// slots free up; paused entries keep their place but are skipped.
+#[derive(Debug, Default)]
+pub struct DownloadQueue {
+    entries: Vec<QueuedDownload>,
+}
+
+impl DownloadQueue {
+    /// Restores a persisted queue. Nothing is downloading yet after a restart,
+    /// so active entries go back to waiting.
+    pub fn restore(entries: Vec<QueuedDownload>) -> Self {
+        let mut queue = Self::default();
+        for mut entry in entries {
+            if queue.position(&entry.model_id).is_some() {
+                continue;
+            }
+            if entry.status == QueuedDownloadStatus::Downloading {
+                entry.status = QueuedDownloadStatus::Queued;
+            }
+            queue.entries.push(entry);
+        }
+        queue
+    }
+
+    pub fn entries(&self) -> &[QueuedDownload] {
+        &self.entries
+    }
+
+    fn position(&self, model_id: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e.model_id == model_id)
+    }
+
+    pub fn status(&self, model_id: &str) -> Option<QueuedDownloadStatus> {
+        self.position(model_id).map(|i| self.entries[i].status)
+    }
+
+    fn set_status(&mut self, model_id: &str, status: QueuedDownloadStatus) -> bool {
+        match self.position(model_id) {
+            Some(i) => {
+                self.entries[i].status = status;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds a download to the back of the queue. A paused entry is resumed in
+    /// place; an entry that is already waiting or running is left alone.
+    pub fn enqueue(&mut self, model_id: &str) {
+        match self.status(model_id) {
+            Some(QueuedDownloadStatus::Paused) => {
+                self.set_status(model_id, QueuedDownloadStatus::Queued);
+            }
+            Some(_) => {}
+            None => self.entries.push(QueuedDownload {
+                model_id: model_id.to_string(),
+                status: QueuedDownloadStatus::Queued,
+            }),
+        }
+    }
+
+    pub fn pause(&mut self, model_id: &str) -> bool {
+        self.set_status(model_id, QueuedDownloadStatus::Paused)
+    }
+
+    pub fn remove(&mut self, model_id: &str) -> bool {
+        match self.position(model_id) {
+            Some(i) => {
+                self.entries.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.status == QueuedDownloadStatus::Downloading)
+            .count()
+    }
+
+    /// Whether `model_id` is the next waiting download and a slot is free.
+    pub fn can_start(&self, model_id: &str, max_concurrent: usize) -> bool {
+        self.active_count() < max_concurrent.max(1)
+            && self
+                .entries
+                .iter()
+                .find(|e| e.status == QueuedDownloadStatus::Queued)
+                .is_some_and(|e| e.model_id == model_id)
+    }
+
+    pub fn start(&mut self, model_id: &str) -> bool {
+        self.set_status(model_id, QueuedDownloadStatus::Downloading)
+    }
+}
+
+/// Caps the combined throughput of all downloads. Each chunk reserves its
+/// share of the budget and the caller sleeps for the returned delay.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    state: Mutex<LimiterState>,
+}
+
+#[derive(Debug)]
+struct LimiterState {
+    bytes_per_sec: Option<u64>,
+    /// When the bytes reserved so far will have been "sent" at the capped rate
+    next_free: Instant,
+}
+
+impl BandwidthLimiter {
+    pub fn new(kib_per_sec: Option<u64>) -> Self {
+        let limiter = Self {
+            state: Mutex::new(LimiterState {
+                bytes_per_sec: None,
+                next_free: Instant::now(),
+            }),
+        };
+        limiter.set_limit(kib_per_sec);
+        limiter
+    }
+
+    pub fn set_limit(&self, kib_per_sec: Option<u64>) {
+        let mut state = self.state.lock().unwrap();
+        state.bytes_per_sec = kib_per_sec.filter(|&k| k > 0).map(|k| k * 1024);
+        state.next_free = Instant::now();
+    }
+
+    /// Reserves `bytes` and returns how long to wait before continuing.
+    pub fn reserve(&self, bytes: u64) -> Duration {
+        self.reserve_at(bytes, Instant::now())
+    }
+
+    fn reserve_at(&self, bytes: u64, now: Instant) -> Duration {
+        let mut state = self.state.lock().unwrap();
+        let Some(rate) = state.bytes_per_sec else {
+            return Duration::ZERO;
+        };
+
+        // Idle time doesn't bank up into a burst
+        let start = state.next_free.max(now);
+        state.next_free = start + Duration::from_secs_f64(bytes as f64 / rate as f64);
+        state.next_free.saturating_duration_since(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(queue: &DownloadQueue) -> Vec<(&str, QueuedDownloadStatus)> {
+        queue
+            .entries()
+            .iter()
+            .map(|e| (e.model_id.as_str(), e.status))
+            .collect()
+    }
+
+    #[test]
+    fn downloads_start_in_order_up_to_the_limit() {
+        let mut queue = DownloadQueue::default();
+        for id in ["a", "b", "c"] {
+            queue.enqueue(id);
+        }
+
+        assert!(queue.can_start("a", 2));
+        assert!(!queue.can_start("b", 2));
+        queue.start("a");
+        assert!(queue.can_start("b", 2));
+        queue.start("b");
+        assert!(!queue.can_start("c", 2));
+
+        queue.remove("a");
+        assert!(queue.can_start("c", 2));
+    }
+
+    #[test]
+    fn paused_downloads_are_skipped_and_resume_in_place() {
+        let mut queue = DownloadQueue::default();
+        queue.enqueue("a");
+        queue.enqueue("b");
+        queue.pause("a");
+
+        assert!(!queue.can_start("a", 1));
+        assert!(queue.can_start("b", 1));
+
+        queue.enqueue("a");
+        assert_eq!(
+            ids(&queue),
+            vec![
+                ("a", QueuedDownloadStatus::Queued),
+                ("b", QueuedDownloadStatus::Queued)
+            ]
+        );
+        assert!(queue.can_start("a", 1));
+    }
+
+    #[test]
+    fn restore_requeues_interrupted_downloads() {
+        let queue = DownloadQueue::restore(vec![
+            QueuedDownload {
+                model_id: "a".to_string(),
+                status: QueuedDownloadStatus::Downloading,
+            },
+            QueuedDownload {
+                model_id: "b".to_string(),
+                status: QueuedDownloadStatus::Paused,
+            },
+            QueuedDownload {
+                model_id: "a".to_string(),
+                status: QueuedDownloadStatus::Queued,
+            },
+        ]);
+        assert_eq!(
+            ids(&queue),
+            vec![
+                ("a", QueuedDownloadStatus::Queued),
+                ("b", QueuedDownloadStatus::Paused)
+            ]
+        );
+    }
+
+    #[test]
+    fn limiter_spaces_out_chunks() {
+        let limiter = BandwidthLimiter::new(Some(100));
+        let now = Instant::now();
+
+        // 100 KiB/s: each 50 KiB chunk costs half a second
+        let first = limiter.reserve_at(50 * 1024, now);
+        let second = limiter.reserve_at(50 * 1024, now);
+        assert!((first.as_secs_f64() - 0.5).abs() < 1e-3);
+        assert!((second.as_secs_f64() - 1.0).abs() < 1e-3);
+
+        // Time that passes while idle isn't saved up
+        let later = now + Duration::from_secs(10);
+        let third = limiter.reserve_at(50 * 1024, later);
+        assert!((third.as_secs_f64() - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn unlimited_limiter_never_waits() {
+        let limiter = BandwidthLimiter::new(None);
+        assert_eq!(limiter.reserve(10 * 1024 * 1024), Duration::ZERO);
+        limiter.set_limit(Some(0));
+        assert_eq!(limiter.reserve(10 * 1024 * 1024), Duration::ZERO);
+    }
+}
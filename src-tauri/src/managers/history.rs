@@ -1,15 +1,23 @@
 use anyhow::Result;
 use chrono::{DateTime, Local, Utc};
-use log::{debug, error, info};
-use rusqlite::{params, Connection, OptionalExtension};
+use log::{debug, error, info, warn};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
 use rusqlite_migration::{Migrations, M};
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
 use tauri::{AppHandle, Emitter, Manager};
 
-use crate::audio_toolkit::save_wav_file;
+use crate::audio_toolkit::{decode_audio, encode_audio, AudioFormat};
+use crate::encryption::{self, EncryptionKey};
+use crate::managers::transcription::{DetectedLanguage, TranscriptionOutput};
+use crate::secrets;
+use crate::settings::{RecordingFormat, RetentionPolicies, RetentionPolicy};
 
 /// Database migrations for transcription history.
 /// Each migration is applied in order. The library tracks which migrations
@@ -65,8 +73,153 @@ static MIGRATIONS: &[M] = &[
             is_favorite BOOLEAN NOT NULL DEFAULT 0
         );",
     ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN detected_language TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN language_probability REAL;"),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_versions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            history_id INTEGER NOT NULL,
+            timestamp INTEGER NOT NULL,
+            model_id TEXT NOT NULL,
+            language TEXT,
+            transcription_text TEXT NOT NULL,
+            post_processed_text TEXT,
+            post_process_prompt TEXT,
+            detected_language TEXT,
+            FOREIGN KEY(history_id) REFERENCES transcription_history(id) ON DELETE CASCADE
+        );",
+    ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN edited_text TEXT;"),
+    M::up(
+        "CREATE TABLE IF NOT EXISTS transcription_revisions (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            history_id INTEGER NOT NULL,
+            timestamp INTEGER NOT NULL,
+            text TEXT NOT NULL,
+            FOREIGN KEY(history_id) REFERENCES transcription_history(id) ON DELETE CASCADE
+        );",
+    ),
+    M::up("ALTER TABLE transcription_history ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN notes TEXT;"),
+    M::up("ALTER TABLE meetings ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';"),
+    M::up("ALTER TABLE meetings ADD COLUMN notes TEXT;"),
+    M::up("ALTER TABLE tts_history ADD COLUMN title TEXT;"),
+    M::up("ALTER TABLE tts_history ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';"),
+    M::up("ALTER TABLE tts_history ADD COLUMN notes TEXT;"),
+    M::up("ALTER TABLE transcription_history ADD COLUMN audio_deleted BOOLEAN NOT NULL DEFAULT 0;"),
+    M::up("ALTER TABLE tts_history ADD COLUMN audio_deleted BOOLEAN NOT NULL DEFAULT 0;"),
 ];
 
+/// Columns `history_entry_from_row` expects in every history entry query.
+const HISTORY_COLUMNS: &str = "id, file_name, timestamp, saved, title, transcription_text, \
+    post_processed_text, post_process_prompt, detected_language, language_probability, edited_text, \
+    tags, notes, audio_deleted";
+
+/// Columns `meeting_from_row` expects in every meeting query.
+const MEETING_COLUMNS: &str = "id, title, start_timestamp, end_timestamp, duration_seconds, \
+    summary, is_pro, file_name, is_favorite, tags, notes";
+
+/// Columns `tts_entry_from_row` expects in every TTS history query.
+const TTS_COLUMNS: &str =
+    "id, text, voice_id, file_name, timestamp, is_favorite, title, tags, notes, audio_deleted";
+
+/// The kinds of history that carry a title, tags and notes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Transcription,
+    Meeting,
+    Tts,
+}
+
+impl EntryKind {
+    fn table(self) -> &'static str {
+        match self {
+            EntryKind::Transcription => "transcription_history",
+            EntryKind::Meeting => "meetings",
+            EntryKind::Tts => "tts_history",
+        }
+    }
+
+    /// Event that tells the UI listing this kind to reload.
+    fn updated_event(self) -> &'static str {
+        match self {
+            EntryKind::Transcription => "history-updated",
+            EntryKind::Meeting => "meetings-updated",
+            EntryKind::Tts => "tts-history-updated",
+        }
+    }
+
+    fn timestamp_column(self) -> &'static str {
+        match self {
+            EntryKind::Meeting => "start_timestamp",
+            EntryKind::Transcription | EntryKind::Tts => "timestamp",
+        }
+    }
+
+    /// Column set on entries the user wants to keep.
+    fn saved_column(self) -> &'static str {
+        match self {
+            EntryKind::Transcription => "saved",
+            EntryKind::Meeting | EntryKind::Tts => "is_favorite",
+        }
+    }
+
+    /// The entry's audio file, NULL once retention deleted it. Meetings
+    /// drop their file name instead, as one without audio already could.
+    fn audio_column(self) -> &'static str {
+        match self {
+            EntryKind::Meeting => "file_name",
+            EntryKind::Transcription | EntryKind::Tts => {
+                "CASE WHEN audio_deleted THEN NULL ELSE file_name END"
+            }
+        }
+    }
+
+    /// What to show for an entry, e.g. in a cleanup preview.
+    fn label_column(self) -> &'static str {
+        match self {
+            EntryKind::Transcription | EntryKind::Meeting => "title",
+            EntryKind::Tts => "COALESCE(title, text)",
+        }
+    }
+}
+
+/// Tags are stored as a JSON array; a malformed value reads as no tags.
+fn tags_from_json(json: String) -> Vec<String> {
+    serde_json::from_str(&json).unwrap_or_default()
+}
+
+/// Trims tags and drops empty ones and case-insensitive duplicates, keeping
+/// the first spelling.
+pub fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.trim();
+        if !tag.is_empty() && !normalized.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            normalized.push(tag.to_string());
+        }
+    }
+    normalized
+}
+
+/// `WHERE` clause matching rows that carry every one of `count` tags, bound
+/// as `?1..=?count`. Tags compare case-insensitively.
+fn tag_filter(count: usize) -> String {
+    if count == 0 {
+        return String::new();
+    }
+    let conditions: Vec<String> = (1..=count)
+        .map(|i| {
+            format!(
+                "EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?{} COLLATE NOCASE)",
+                i
+            )
+        })
+        .collect();
+    format!("WHERE {}", conditions.join(" AND "))
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
 pub struct TtsHistoryEntry {
     pub id: i64,
@@ -75,6 +228,29 @@ pub struct TtsHistoryEntry {
     pub file_name: String,
     pub timestamp: i64,
     pub is_favorite: bool,
+    /// User-given title; the UI falls back to the text
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    /// The audio was deleted by retention cleanup; the text is kept
+    #[serde(default)]
+    pub audio_deleted: bool,
+}
+
+fn tts_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<TtsHistoryEntry> {
+    Ok(TtsHistoryEntry {
+        id: row.get("id")?,
+        text: row.get("text")?,
+        voice_id: row.get("voice_id")?,
+        file_name: row.get("file_name")?,
+        timestamp: row.get("timestamp")?,
+        is_favorite: row.get("is_favorite")?,
+        title: row.get("title")?,
+        tags: tags_from_json(row.get("tags")?),
+        notes: row.get("notes")?,
+        audio_deleted: row.get("audio_deleted")?,
+    })
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -87,6 +263,85 @@ pub struct HistoryEntry {
     pub transcription_text: String,
     pub post_processed_text: Option<String>,
     pub post_process_prompt: Option<String>,
+    /// Language the engine detected, when transcribed with auto-detection
+    pub detected_language: Option<String>,
+    pub language_probability: Option<f32>,
+    /// The user's latest correction; earlier ones are kept as revisions
+    pub edited_text: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+    /// The audio was deleted by retention cleanup; the text is kept
+    #[serde(default)]
+    pub audio_deleted: bool,
+}
+
+impl HistoryEntry {
+    /// The user's correction, else the post-processed text, else the transcription.
+    pub fn final_text(&self) -> &str {
+        self.edited_text
+            .as_deref()
+            .or(self.post_processed_text.as_deref())
+            .unwrap_or(&self.transcription_text)
+    }
+}
+
+fn history_entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<HistoryEntry> {
+    Ok(HistoryEntry {
+        id: row.get("id")?,
+        file_name: row.get("file_name")?,
+        timestamp: row.get("timestamp")?,
+        saved: row.get("saved")?,
+        title: row.get("title")?,
+        transcription_text: row.get("transcription_text")?,
+        post_processed_text: row.get("post_processed_text")?,
+        post_process_prompt: row.get("post_process_prompt")?,
+        detected_language: row.get("detected_language")?,
+        language_probability: row.get("language_probability")?,
+        edited_text: row.get("edited_text")?,
+        tags: tags_from_json(row.get("tags")?),
+        notes: row.get("notes")?,
+        audio_deleted: row.get("audio_deleted")?,
+    })
+}
+
+/// Alternative transcription of a history entry's audio, e.g. with another
+/// model, language or prompt. The entry itself keeps the original.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct TranscriptionVersion {
+    pub id: i64,
+    pub history_id: i64,
+    pub timestamp: i64,
+    pub model_id: String,
+    /// Language requested for this version; `None` means auto-detect
+    pub language: Option<String>,
+    pub transcription_text: String,
+    pub post_processed_text: Option<String>,
+    pub post_process_prompt: Option<String>,
+    pub detected_language: Option<String>,
+}
+
+fn transcription_version_from_row(row: &rusqlite::Row) -> rusqlite::Result<TranscriptionVersion> {
+    Ok(TranscriptionVersion {
+        id: row.get("id")?,
+        history_id: row.get("history_id")?,
+        timestamp: row.get("timestamp")?,
+        model_id: row.get("model_id")?,
+        language: row.get("language")?,
+        transcription_text: row.get("transcription_text")?,
+        post_processed_text: row.get("post_processed_text")?,
+        post_process_prompt: row.get("post_process_prompt")?,
+        detected_language: row.get("detected_language")?,
+    })
+}
+
+/// A correction the user made to a history entry's text.
+#[derive(Clone, Debug, Serialize, Deserialize, Type)]
+pub struct TranscriptionRevision {
+    pub id: i64,
+    pub history_id: i64,
+    pub timestamp: i64,
+    pub text: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -101,6 +356,25 @@ pub struct Meeting {
     pub file_name: Option<String>,
     #[serde(default)]
     pub is_favorite: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub notes: Option<String>,
+}
+
+fn meeting_from_row(row: &rusqlite::Row) -> rusqlite::Result<Meeting> {
+    Ok(Meeting {
+        id: row.get("id")?,
+        title: row.get("title")?,
+        start_timestamp: row.get("start_timestamp")?,
+        end_timestamp: row.get("end_timestamp")?,
+        duration_seconds: row.get("duration_seconds")?,
+        summary: row.get("summary")?,
+        is_pro: row.get("is_pro")?,
+        file_name: row.get("file_name")?,
+        is_favorite: row.get("is_favorite").unwrap_or(false),
+        tags: tags_from_json(row.get("tags")?),
+        notes: row.get("notes")?,
+    })
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Type)]
@@ -120,10 +394,126 @@ pub struct MeetingDetails {
     pub audio_path: Option<String>,
 }
 
+/// Files younger than this are never treated as orphans: a recording or TTS
+/// clip is written to disk just before its database row is inserted.
+const ORPHAN_GRACE_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Disk usage of the recordings directory and history database.
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct RecordingStorage {
+    /// Audio referenced by transcription history entries
+    pub recordings_bytes: u64,
+    pub meetings_bytes: u64,
+    pub tts_bytes: u64,
+    pub database_bytes: u64,
+    /// Audio files no history, meeting or TTS row refers to
+    pub orphaned_files: Vec<String>,
+    pub orphaned_bytes: u64,
+    /// Entries whose audio file is gone. Saved entries are left out, as are
+    /// ones whose audio was deleted on purpose or not included in a backup.
+    pub missing_audio_entries: Vec<MissingAudioEntry>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Type)]
+pub struct MissingAudioEntry {
+    pub kind: EntryKind,
+    pub id: i64,
+}
+
+/// Which recordings a backup includes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingSelection {
+    None,
+    /// Saved history entries and favorite meetings and TTS clips
+    Saved,
+    #[default]
+    All,
+}
+
+/// Rows copied by `HistoryManager::import_database`.
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct HistoryImport {
+    pub entries: usize,
+    pub meetings: usize,
+    pub tts_entries: usize,
+    /// Rows already present, matched by file name and timestamp
+    pub skipped: usize,
+}
+
+/// Payload of `recording-reencode-progress`, emitted after each recording.
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct RecordingReencodeProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Outcome of a re-encode, the payload of `recording-reencode-completed`.
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct RecordingReencode {
+    pub converted: usize,
+    pub failed: usize,
+    /// Negative when the new format takes more space
+    pub bytes_saved: i64,
+}
+
+/// What retention cleanup does to an entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupAction {
+    DeleteEntry,
+    /// Delete the audio, keeping the text
+    DeleteAudio,
+}
+
+/// An entry retention cleanup deletes or strips of its audio.
+#[derive(Clone, Debug, Serialize, Type)]
+pub struct CleanupCandidate {
+    pub kind: EntryKind,
+    pub id: i64,
+    pub timestamp: i64,
+    pub title: Option<String>,
+    /// The entry's audio file, unless it has none left
+    pub file_name: Option<String>,
+    pub action: CleanupAction,
+    pub audio_bytes: u64,
+}
+
+/// What retention cleanup would do right now.
+#[derive(Clone, Debug, Default, Serialize, Type)]
+pub struct CleanupPreview {
+    pub candidates: Vec<CleanupCandidate>,
+    /// Disk space the deleted audio takes up
+    pub audio_bytes: u64,
+}
+
+/// Keychain entry holding the key history is encrypted with.
+const ENCRYPTION_KEY_SECRET: &str = "history-encryption-key";
+
+fn open_database(path: &Path, key: Option<&EncryptionKey>) -> Result<Connection> {
+    let conn = Connection::open(path)?;
+    if let Some(key) = key {
+        conn.pragma_update(None, "key", key.sqlcipher_key())?;
+    }
+    Ok(conn)
+}
+
+/// Whether the database can be read, i.e. it's plain or the key is right.
+fn is_readable(conn: &Connection) -> bool {
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |_| Ok(()))
+        .is_ok()
+}
+
 pub struct HistoryManager {
     app_handle: AppHandle,
     recordings_dir: PathBuf,
     db_path: PathBuf,
+    /// Set while history is encrypted at rest
+    encryption_key: RwLock<Option<EncryptionKey>>,
+    /// Set while recordings are being re-encoded
+    reencoding: AtomicBool,
+    /// Why history is disabled this session, if it couldn't be opened
+    unavailable: Option<String>,
 }
 
 impl HistoryManager {
@@ -139,22 +529,60 @@ impl HistoryManager {
             debug!("Created recordings directory: {:?}", recordings_dir);
         }
 
-        let manager = Self {
+        // Only matters if the database turns out to be encrypted
+        let mut keychain_error = None;
+        let encryption_key = match secrets::get(ENCRYPTION_KEY_SECRET) {
+            Ok(Some(hex)) => match EncryptionKey::from_hex(&hex) {
+                Ok(key) => Some(key),
+                Err(e) => {
+                    warn!("The stored history encryption key is invalid: {}", e);
+                    keychain_error = Some(format!("the stored key is invalid: {}", e));
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Failed to read the history encryption key: {}", e);
+                keychain_error = Some(e.to_string());
+                None
+            }
+        };
+
+        let mut manager = Self {
             app_handle: app_handle.clone(),
             recordings_dir,
             db_path,
+            encryption_key: RwLock::new(encryption_key),
+            reencoding: AtomicBool::new(false),
+            unavailable: None,
         };
 
-        // Initialize database and run migrations synchronously
-        manager.init_database()?;
+        // Initialize database and run migrations synchronously. A database
+        // that can't be opened disables history instead of the whole app.
+        if let Err(e) = manager.init_database(keychain_error.as_deref()) {
+            error!("History is disabled: {:#}", e);
+            manager.unavailable = Some(format!("{:#}", e));
+        }
 
         Ok(manager)
     }
 
-    fn init_database(&self) -> Result<()> {
+    /// Why history couldn't be opened this session, if it couldn't.
+    pub fn unavailable_reason(&self) -> Option<&str> {
+        self.unavailable.as_deref()
+    }
+
+    fn ensure_available(&self) -> Result<()> {
+        match &self.unavailable {
+            Some(reason) => anyhow::bail!("History is unavailable: {}", reason),
+            None => Ok(()),
+        }
+    }
+
+    fn init_database(&self, keychain_error: Option<&str>) -> Result<()> {
         info!("Initializing database at {:?}", self.db_path);
 
-        let mut conn = Connection::open(&self.db_path)?;
+        let mut conn = self.open_initial_connection(keychain_error)?;
 
         // Handle migration from tauri-plugin-sql to rusqlite_migration
         // tauri-plugin-sql used _sqlx_migrations table, rusqlite_migration uses user_version pragma
@@ -190,6 +618,37 @@ impl HistoryManager {
         Ok(())
     }
 
+    /// Opens the database with the stored key, if any. A key left behind by
+    /// an interrupted switch to encryption, while the database is still
+    /// plain, is discarded; no recording is encrypted before the database.
+    fn open_initial_connection(&self, keychain_error: Option<&str>) -> Result<Connection> {
+        let key = self.encryption_key.read().unwrap().clone();
+        let conn = open_database(&self.db_path, key.as_ref())?;
+        if is_readable(&conn) {
+            return Ok(conn);
+        }
+        if let Some(e) = keychain_error {
+            anyhow::bail!(
+                "The history database is encrypted, but its key couldn't be read from the system keychain: {}",
+                e
+            );
+        }
+        if key.is_none() {
+            anyhow::bail!(
+                "The history database is encrypted, but its key isn't in the system keychain"
+            );
+        }
+
+        let plain = open_database(&self.db_path, None)?;
+        if !is_readable(&plain) {
+            anyhow::bail!("The history database can't be decrypted with the key in the keychain");
+        }
+        warn!("Discarding the key of an interrupted switch to history encryption");
+        *self.encryption_key.write().unwrap() = None;
+        secrets::delete(ENCRYPTION_KEY_SECRET)?;
+        Ok(plain)
+    }
+
     /// Migrate from tauri-plugin-sql's migration tracking to rusqlite_migration's.
     /// tauri-plugin-sql used a _sqlx_migrations table, while rusqlite_migration uses
     /// SQLite's user_version pragma. This function checks if the old system was in use
@@ -248,24 +707,29 @@ impl HistoryManager {
     }
 
     fn get_connection(&self) -> Result<Connection> {
-        Ok(Connection::open(&self.db_path)?)
+        self.ensure_available()?;
+        open_database(&self.db_path, self.encryption_key.read().unwrap().as_ref())
     }
 
-    /// Save a transcription to history (both database and WAV file)
+    /// Save a transcription to history (both database and audio file)
     pub async fn save_transcription(
         &self,
         audio_samples: Vec<f32>,
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        language: Option<DetectedLanguage>,
     ) -> Result<()> {
+        // Don't leave a recording nothing refers to
+        self.ensure_available()?;
         let timestamp = Utc::now().timestamp();
-        let file_name = format!("typezero-{}.wav", timestamp);
+        let format = self.recording_format();
+        let file_name = format!("typezero-{}.{}", timestamp, format.extension());
         let title = self.format_timestamp_title(timestamp);
 
-        // Save WAV file
+        // Save audio file
         let file_path = self.recordings_dir.join(&file_name);
-        save_wav_file(file_path, &audio_samples).await?;
+        self.write_recording(&file_path, format, &audio_samples)?;
 
         // Save to database
         self.save_to_database(
@@ -275,6 +739,7 @@ impl HistoryManager {
             transcription_text,
             post_processed_text,
             post_process_prompt,
+            language,
         )?;
 
         // Clean up old entries
@@ -288,6 +753,7 @@ impl HistoryManager {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn save_to_database(
         &self,
         file_name: String,
@@ -296,155 +762,226 @@ impl HistoryManager {
         transcription_text: String,
         post_processed_text: Option<String>,
         post_process_prompt: Option<String>,
+        language: Option<DetectedLanguage>,
     ) -> Result<()> {
+        let (detected_language, language_probability) = match language {
+            Some(language) => (Some(language.code), language.probability),
+            None => (None, None),
+        };
+
         let conn = self.get_connection()?;
         conn.execute(
-            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
-            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt],
+            "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, detected_language, language_probability) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![file_name, timestamp, false, title, transcription_text, post_processed_text, post_process_prompt, detected_language, language_probability],
         )?;
 
         debug!("Saved transcription to database");
         Ok(())
     }
 
+    /// Applies the retention policies of dictations, meetings and TTS clips.
     pub fn cleanup_old_entries(&self) -> Result<()> {
-        let retention_period = crate::settings::get_recording_retention_period(&self.app_handle);
+        let policies = crate::settings::get_retention_policies(&self.app_handle);
+        let conn = self.get_connection()?;
+        let candidates = Self::plan_cleanup_with_conn(&conn, &policies, Utc::now().timestamp())?;
+        if candidates.is_empty() {
+            return Ok(());
+        }
 
-        match retention_period {
-            crate::settings::RecordingRetentionPeriod::Never => {
-                // Don't delete anything
-                return Ok(());
-            }
-            crate::settings::RecordingRetentionPeriod::PreserveLimit => {
-                // Use the old count-based logic with history_limit
-                let limit = crate::settings::get_history_limit(&self.app_handle);
-                return self.cleanup_by_count(limit);
-            }
-            _ => {
-                // Use time-based logic
-                return self.cleanup_by_time(retention_period);
+        let tx = conn.unchecked_transaction()?;
+        for candidate in &candidates {
+            match candidate.action {
+                CleanupAction::DeleteEntry => {
+                    Self::delete_rows_with_conn(&tx, candidate.kind, candidate.id)?
+                }
+                CleanupAction::DeleteAudio => {
+                    Self::mark_audio_deleted_with_conn(&tx, candidate.kind, candidate.id)?
+                }
             }
         }
-    }
+        tx.commit()?;
 
-    fn delete_entries_and_files(&self, entries: &[(i64, String)]) -> Result<usize> {
-        if entries.is_empty() {
-            return Ok(0);
+        // Only once no entry refers to the audio anymore; a failed commit
+        // leaves both in place
+        for file_name in candidates.iter().filter_map(|c| c.file_name.as_deref()) {
+            self.remove_audio_file(file_name);
         }
+        debug!("Retention cleanup handled {} entries", candidates.len());
 
-        let conn = self.get_connection()?;
-        let mut deleted_count = 0;
-
-        for (id, file_name) in entries {
-            // Delete database entry
-            conn.execute(
-                "DELETE FROM transcription_history WHERE id = ?1",
-                params![id],
-            )?;
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+        Ok(())
+    }
 
-            // Delete WAV file
-            let file_path = self.recordings_dir.join(file_name);
-            if file_path.exists() {
-                if let Err(e) = fs::remove_file(&file_path) {
-                    error!("Failed to delete WAV file {}: {}", file_name, e);
-                } else {
-                    debug!("Deleted old WAV file: {}", file_name);
-                    deleted_count += 1;
-                }
+    /// What `cleanup_old_entries` would do right now, without doing it. With
+    /// `policies`, previews those instead of the ones in the settings.
+    pub fn preview_cleanup(&self, policies: Option<RetentionPolicies>) -> Result<CleanupPreview> {
+        let policies =
+            policies.unwrap_or_else(|| crate::settings::get_retention_policies(&self.app_handle));
+        let conn = self.get_connection()?;
+        let mut candidates =
+            Self::plan_cleanup_with_conn(&conn, &policies, Utc::now().timestamp())?;
+
+        let mut audio_bytes = 0;
+        for candidate in &mut candidates {
+            if let Some(file_name) = &candidate.file_name {
+                candidate.audio_bytes = fs::metadata(self.get_audio_file_path(file_name))
+                    .map(|m| m.len())
+                    .unwrap_or(0);
+                audio_bytes += candidate.audio_bytes;
             }
         }
+        Ok(CleanupPreview {
+            candidates,
+            audio_bytes,
+        })
+    }
 
-        Ok(deleted_count)
+    fn remove_audio_file(&self, file_name: &str) {
+        let file_path = self.get_audio_file_path(file_name);
+        if !file_path.exists() {
+            return;
+        }
+        match fs::remove_file(&file_path) {
+            Ok(()) => debug!("Deleted old audio file: {}", file_name),
+            Err(e) => error!("Failed to delete audio file {}: {}", file_name, e),
+        }
     }
 
-    fn cleanup_by_count(&self, limit: usize) -> Result<()> {
-        let conn = self.get_connection()?;
+    /// Entries the retention policies delete or strip of audio at `now`.
+    fn plan_cleanup_with_conn(
+        conn: &Connection,
+        policies: &RetentionPolicies,
+        now: i64,
+    ) -> Result<Vec<CleanupCandidate>> {
+        let mut candidates = Vec::new();
+        for (kind, policy) in [
+            (EntryKind::Transcription, &policies.dictations),
+            (EntryKind::Meeting, &policies.meetings),
+            (EntryKind::Tts, &policies.tts),
+        ] {
+            candidates.extend(Self::plan_kind_cleanup_with_conn(conn, kind, policy, now)?);
+        }
+        Ok(candidates)
+    }
 
-        // Get all entries that are not saved, ordered by timestamp desc
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name FROM transcription_history WHERE saved = 0 ORDER BY timestamp DESC"
-        )?;
+    fn plan_kind_cleanup_with_conn(
+        conn: &Connection,
+        kind: EntryKind,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<Vec<CleanupCandidate>> {
+        let days_ago = |days: u32| now - i64::from(days) * 24 * 60 * 60;
+        let entry_cutoff = policy.max_age_days.map(days_ago);
+        let audio_cutoff = policy.audio_max_age_days.map(days_ago);
+
+        let mut conditions = Vec::new();
+        if policy.keep_saved {
+            conditions.push(format!("{} = 0", kind.saved_column()));
+        }
+        if kind == EntryKind::Meeting {
+            // Still being recorded
+            conditions.push("end_timestamp IS NOT NULL".to_string());
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
 
+        // Newest first, so the count limit keeps the head of the list
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, {timestamp}, {label}, {audio} FROM {table} {where_clause}
+             ORDER BY {timestamp} DESC, id DESC",
+            timestamp = kind.timestamp_column(),
+            label = kind.label_column(),
+            audio = kind.audio_column(),
+            table = kind.table(),
+        ))?;
         let rows = stmt.query_map([], |row| {
-            Ok((row.get::<_, i64>("id")?, row.get::<_, String>("file_name")?))
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
         })?;
 
-        let mut entries: Vec<(i64, String)> = Vec::new();
-        for row in rows {
-            entries.push(row?);
+        let mut candidates = Vec::new();
+        for (index, row) in rows.enumerate() {
+            let (id, timestamp, title, file_name) = row?;
+            let over_limit = policy.max_entries.is_some_and(|max| index >= max);
+            let expired = entry_cutoff.is_some_and(|cutoff| timestamp < cutoff);
+            let audio_expired = audio_cutoff.is_some_and(|cutoff| timestamp < cutoff);
+
+            let action = if over_limit || expired {
+                CleanupAction::DeleteEntry
+            } else if audio_expired && file_name.is_some() {
+                CleanupAction::DeleteAudio
+            } else {
+                continue;
+            };
+            candidates.push(CleanupCandidate {
+                kind,
+                id,
+                timestamp,
+                title,
+                file_name,
+                action,
+                audio_bytes: 0,
+            });
         }
+        Ok(candidates)
+    }
 
-        if entries.len() > limit {
-            let entries_to_delete = &entries[limit..];
-            let deleted_count = self.delete_entries_and_files(entries_to_delete)?;
-
-            if deleted_count > 0 {
-                debug!("Cleaned up {} old history entries by count", deleted_count);
+    fn delete_rows_with_conn(conn: &Connection, kind: EntryKind, id: i64) -> Result<()> {
+        match kind {
+            EntryKind::Transcription => {
+                Self::delete_history_rows(conn, id)?;
+            }
+            EntryKind::Meeting => {
+                conn.execute(
+                    "DELETE FROM meeting_segments WHERE meeting_id = ?1",
+                    params![id],
+                )?;
+                conn.execute("DELETE FROM meetings WHERE id = ?1", params![id])?;
+            }
+            EntryKind::Tts => {
+                conn.execute("DELETE FROM tts_history WHERE id = ?1", params![id])?;
             }
         }
-
         Ok(())
     }
 
-    fn cleanup_by_time(
-        &self,
-        retention_period: crate::settings::RecordingRetentionPeriod,
-    ) -> Result<()> {
-        let conn = self.get_connection()?;
-
-        // Calculate cutoff timestamp (current time minus retention period)
-        let now = Utc::now().timestamp();
-        let cutoff_timestamp = match retention_period {
-            crate::settings::RecordingRetentionPeriod::Days3 => now - (3 * 24 * 60 * 60), // 3 days in seconds
-            crate::settings::RecordingRetentionPeriod::Weeks2 => now - (2 * 7 * 24 * 60 * 60), // 2 weeks in seconds
-            crate::settings::RecordingRetentionPeriod::Months3 => now - (3 * 30 * 24 * 60 * 60), // 3 months in seconds (approximate)
-            _ => unreachable!("Should not reach here"),
-        };
-
-        // Get all unsaved entries older than the cutoff timestamp
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name FROM transcription_history WHERE saved = 0 AND timestamp < ?1",
-        )?;
-
-        let rows = stmt.query_map(params![cutoff_timestamp], |row| {
-            Ok((row.get::<_, i64>("id")?, row.get::<_, String>("file_name")?))
-        })?;
-
-        let mut entries_to_delete: Vec<(i64, String)> = Vec::new();
-        for row in rows {
-            entries_to_delete.push(row?);
-        }
-
-        let deleted_count = self.delete_entries_and_files(&entries_to_delete)?;
-
-        if deleted_count > 0 {
-            debug!(
-                "Cleaned up {} old history entries based on retention period",
-                deleted_count
-            );
+    fn mark_audio_deleted_with_conn(conn: &Connection, kind: EntryKind, id: i64) -> Result<()> {
+        match kind {
+            EntryKind::Meeting => {
+                Self::update_column_with_conn(conn, kind, id, "file_name", Option::<String>::None)
+            }
+            EntryKind::Transcription | EntryKind::Tts => {
+                Self::update_column_with_conn(conn, kind, id, "audio_deleted", true)
+            }
         }
-
-        Ok(())
     }
 
-    pub async fn get_history_entries(&self) -> Result<Vec<HistoryEntry>> {
+    /// History entries, newest first. With `tags`, only entries carrying all of them.
+    pub async fn get_history_entries(&self, tags: &[String]) -> Result<Vec<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt FROM transcription_history ORDER BY timestamp DESC"
-        )?;
+        Self::get_history_entries_with_conn(&conn, tags)
+    }
 
-        let rows = stmt.query_map([], |row| {
-            Ok(HistoryEntry {
-                id: row.get("id")?,
-                file_name: row.get("file_name")?,
-                timestamp: row.get("timestamp")?,
-                saved: row.get("saved")?,
-                title: row.get("title")?,
-                transcription_text: row.get("transcription_text")?,
-                post_processed_text: row.get("post_processed_text")?,
-                post_process_prompt: row.get("post_process_prompt")?,
-            })
-        })?;
+    fn get_history_entries_with_conn(
+        conn: &Connection,
+        tags: &[String],
+    ) -> Result<Vec<HistoryEntry>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history {} ORDER BY timestamp DESC",
+            HISTORY_COLUMNS,
+            tag_filter(tags.len())
+        ))?;
+
+        let rows = stmt.query_map(params_from_iter(tags), history_entry_from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -460,27 +997,12 @@ impl HistoryManager {
     }
 
     fn get_latest_entry_with_conn(conn: &Connection) -> Result<Option<HistoryEntry>> {
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history
-             ORDER BY timestamp DESC
-             LIMIT 1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history ORDER BY timestamp DESC LIMIT 1",
+            HISTORY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([], history_entry_from_row).optional()?;
 
         Ok(entry)
     }
@@ -516,27 +1038,18 @@ impl HistoryManager {
         self.recordings_dir.join(file_name)
     }
 
+    pub fn recordings_dir(&self) -> &Path {
+        &self.recordings_dir
+    }
+
     pub async fn get_entry_by_id(&self, id: i64) -> Result<Option<HistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt
-             FROM transcription_history WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM transcription_history WHERE id = ?1",
+            HISTORY_COLUMNS
+        ))?;
 
-        let entry = stmt
-            .query_row([id], |row| {
-                Ok(HistoryEntry {
-                    id: row.get("id")?,
-                    file_name: row.get("file_name")?,
-                    timestamp: row.get("timestamp")?,
-                    saved: row.get("saved")?,
-                    title: row.get("title")?,
-                    transcription_text: row.get("transcription_text")?,
-                    post_processed_text: row.get("post_processed_text")?,
-                    post_process_prompt: row.get("post_process_prompt")?,
-                })
-            })
-            .optional()?;
+        let entry = stmt.query_row([id], history_entry_from_row).optional()?;
 
         Ok(entry)
     }
@@ -557,10 +1070,7 @@ impl HistoryManager {
         }
 
         // Delete from database
-        conn.execute(
-            "DELETE FROM transcription_history WHERE id = ?1",
-            params![id],
-        )?;
+        Self::delete_history_rows(&conn, id)?;
 
         debug!("Deleted history entry with id: {}", id);
 
@@ -572,50 +1082,375 @@ impl HistoryManager {
         Ok(())
     }
 
-    fn format_timestamp_title(&self, timestamp: i64) -> String {
-        if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
-            // Convert UTC to local timezone
-            let local_datetime = utc_datetime.with_timezone(&Local);
-            local_datetime.format("%B %e, %Y - %l:%M%p").to_string()
-        } else {
-            format!("Recording {}", timestamp)
-        }
-    }
-
-    // --- Meetings Support ---
-
-    pub fn create_meeting(&self, title: Option<String>) -> Result<(i64, PathBuf)> {
-        let timestamp = Utc::now().timestamp();
-        let title = title.unwrap_or_else(|| self.format_timestamp_title(timestamp));
-        let file_name = format!("meeting-{}.wav", timestamp);
-        
-        let conn = self.get_connection()?;
+    /// Deletes a history entry and its alternative versions. SQLite doesn't
+    /// enforce foreign keys by default, so the versions are deleted explicitly.
+    fn delete_history_rows(conn: &Connection, id: i64) -> Result<usize> {
         conn.execute(
-            "INSERT INTO meetings (title, start_timestamp, is_pro, file_name) VALUES (?1, ?2, ?3, ?4)",
-            params![title, timestamp, false, file_name],
+            "DELETE FROM transcription_versions WHERE history_id = ?1",
+            params![id],
         )?;
-        
-        let id = conn.last_insert_rowid();
-        let file_path = self.recordings_dir.join(&file_name);
-        
-        Ok((id, file_path))
+        conn.execute(
+            "DELETE FROM transcription_revisions WHERE history_id = ?1",
+            params![id],
+        )?;
+        Ok(conn.execute(
+            "DELETE FROM transcription_history WHERE id = ?1",
+            params![id],
+        )?)
     }
 
-    pub fn add_meeting_segment(
+    /// Stores an alternative transcription of the entry `history_id`.
+    pub fn save_version(
         &self,
-        meeting_id: i64,
-        speaker_id: String,
-        start_offset: f64,
-        end_offset: f64,
-        text: String,
-    ) -> Result<()> {
+        history_id: i64,
+        model_id: String,
+        language: Option<String>,
+        output: TranscriptionOutput,
+        post_processed_text: Option<String>,
+        post_process_prompt: Option<String>,
+    ) -> Result<TranscriptionVersion> {
         let conn = self.get_connection()?;
-        conn.execute(
-            "INSERT INTO meeting_segments (meeting_id, speaker_id, start_time_offset, end_time_offset, text) 
-             VALUES (?1, ?2, ?3, ?4, ?5)",
-            params![meeting_id, speaker_id, start_offset, end_offset, text],
+        let version = Self::save_version_with_conn(
+            &conn,
+            TranscriptionVersion {
+                id: 0,
+                history_id,
+                timestamp: Utc::now().timestamp(),
+                model_id,
+                language,
+                transcription_text: output.text,
+                post_processed_text,
+                post_process_prompt,
+                detected_language: output.language.map(|l| l.code),
+            },
         )?;
-        Ok(())
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(version)
+    }
+
+    fn save_version_with_conn(
+        conn: &Connection,
+        mut version: TranscriptionVersion,
+    ) -> Result<TranscriptionVersion> {
+        conn.execute(
+            "INSERT INTO transcription_versions (history_id, timestamp, model_id, language, transcription_text, post_processed_text, post_process_prompt, detected_language) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                version.history_id,
+                version.timestamp,
+                version.model_id,
+                version.language,
+                version.transcription_text,
+                version.post_processed_text,
+                version.post_process_prompt,
+                version.detected_language
+            ],
+        )?;
+        version.id = conn.last_insert_rowid();
+        Ok(version)
+    }
+
+    /// Alternative versions of a history entry, oldest first.
+    pub fn get_versions(&self, history_id: i64) -> Result<Vec<TranscriptionVersion>> {
+        let conn = self.get_connection()?;
+        Self::get_versions_with_conn(&conn, history_id)
+    }
+
+    fn get_versions_with_conn(
+        conn: &Connection,
+        history_id: i64,
+    ) -> Result<Vec<TranscriptionVersion>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, history_id, timestamp, model_id, language, transcription_text, post_processed_text, post_process_prompt, detected_language
+             FROM transcription_versions WHERE history_id = ?1 ORDER BY timestamp ASC, id ASC",
+        )?;
+        let versions = stmt
+            .query_map([history_id], transcription_version_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(versions)
+    }
+
+    /// Stores the user's correction of an entry's text as a new revision.
+    pub fn edit_entry(&self, id: i64, text: String) -> Result<TranscriptionRevision> {
+        let conn = self.get_connection()?;
+        let revision = Self::edit_entry_with_conn(&conn, id, text, Utc::now().timestamp())?;
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(revision)
+    }
+
+    fn edit_entry_with_conn(
+        conn: &Connection,
+        id: i64,
+        text: String,
+        timestamp: i64,
+    ) -> Result<TranscriptionRevision> {
+        let updated = conn.execute(
+            "UPDATE transcription_history SET edited_text = ?1 WHERE id = ?2",
+            params![text, id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("History entry {} not found", id);
+        }
+
+        conn.execute(
+            "INSERT INTO transcription_revisions (history_id, timestamp, text) VALUES (?1, ?2, ?3)",
+            params![id, timestamp, text],
+        )?;
+
+        Ok(TranscriptionRevision {
+            id: conn.last_insert_rowid(),
+            history_id: id,
+            timestamp,
+            text,
+        })
+    }
+
+    /// Corrections of a history entry, oldest first.
+    pub fn get_revisions(&self, history_id: i64) -> Result<Vec<TranscriptionRevision>> {
+        let conn = self.get_connection()?;
+        Self::get_revisions_with_conn(&conn, history_id)
+    }
+
+    fn get_revisions_with_conn(
+        conn: &Connection,
+        history_id: i64,
+    ) -> Result<Vec<TranscriptionRevision>> {
+        let mut stmt = conn.prepare(
+            "SELECT id, history_id, timestamp, text FROM transcription_revisions
+             WHERE history_id = ?1 ORDER BY timestamp ASC, id ASC",
+        )?;
+        let revisions = stmt
+            .query_map([history_id], |row| {
+                Ok(TranscriptionRevision {
+                    id: row.get(0)?,
+                    history_id: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    text: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(revisions)
+    }
+
+    /// Final text of a history entry, or of one of its versions: the user's
+    /// correction, else the post-processed text, else the transcription.
+    pub async fn get_entry_text(&self, id: i64, version_id: Option<i64>) -> Result<Option<String>> {
+        let text = match version_id {
+            Some(version_id) => self
+                .get_versions(id)?
+                .into_iter()
+                .find(|v| v.id == version_id)
+                .map(|v| v.post_processed_text.unwrap_or(v.transcription_text)),
+            None => self
+                .get_entry_by_id(id)
+                .await?
+                .map(|e| e.final_text().to_string()),
+        };
+        Ok(text)
+    }
+
+    pub fn storage_usage(&self) -> Result<RecordingStorage> {
+        let conn = self.get_connection()?;
+        let mut usage = Self::storage_usage_with_conn(&conn, &self.recordings_dir)?;
+        usage.database_bytes = fs::metadata(&self.db_path).map(|m| m.len()).unwrap_or(0);
+        Ok(usage)
+    }
+
+    fn storage_usage_with_conn(
+        conn: &Connection,
+        recordings_dir: &Path,
+    ) -> Result<RecordingStorage> {
+        let transcriptions = Self::referenced_files(conn, "transcription_history")?;
+        let meetings = Self::referenced_files(conn, "meetings")?;
+        let tts = Self::referenced_files(conn, "tts_history")?;
+
+        let mut usage = RecordingStorage::default();
+        let orphan_cutoff = SystemTime::now() - ORPHAN_GRACE_PERIOD;
+
+        for entry in fs::read_dir(recordings_dir)?.filter_map(|e| e.ok()) {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let size = metadata.len();
+
+            if transcriptions.contains(&file_name) {
+                usage.recordings_bytes += size;
+            } else if meetings.contains(&file_name) {
+                usage.meetings_bytes += size;
+            } else if tts.contains(&file_name) {
+                usage.tts_bytes += size;
+            } else if AudioFormat::from_path(&file_name).is_some()
+                && metadata.modified().is_ok_and(|t| t < orphan_cutoff)
+            {
+                usage.orphaned_files.push(file_name);
+                usage.orphaned_bytes += size;
+            }
+        }
+        usage.orphaned_files.sort();
+
+        for kind in [EntryKind::Transcription, EntryKind::Meeting, EntryKind::Tts] {
+            usage
+                .missing_audio_entries
+                .extend(Self::missing_audio_with_conn(conn, kind, recordings_dir)?);
+        }
+
+        Ok(usage)
+    }
+
+    /// Unsaved entries of `kind` that still expect an audio file that isn't there.
+    fn missing_audio_with_conn(
+        conn: &Connection,
+        kind: EntryKind,
+        recordings_dir: &Path,
+    ) -> Result<Vec<MissingAudioEntry>> {
+        let mut conditions = vec![
+            format!("{} = 0", kind.saved_column()),
+            format!("{} IS NOT NULL", kind.audio_column()),
+        ];
+        if kind == EntryKind::Meeting {
+            // Still being recorded
+            conditions.push("end_timestamp IS NOT NULL".to_string());
+        }
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, {} FROM {} WHERE {} ORDER BY id",
+            kind.audio_column(),
+            kind.table(),
+            conditions.join(" AND ")
+        ))?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?;
+
+        let mut missing = Vec::new();
+        for row in rows {
+            let (id, file_name) = row?;
+            if !recordings_dir.join(&file_name).is_file() {
+                missing.push(MissingAudioEntry { kind, id });
+            }
+        }
+        Ok(missing)
+    }
+
+    fn referenced_files(conn: &Connection, table: &str) -> Result<HashSet<String>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT file_name FROM {} WHERE file_name IS NOT NULL",
+            table
+        ))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut files = HashSet::new();
+        for row in rows {
+            files.insert(row?);
+        }
+        Ok(files)
+    }
+
+    /// Deletes orphaned audio files and/or history entries whose audio is gone,
+    /// as found by `storage_usage`. Returns (files removed, bytes freed, entries removed).
+    pub fn cleanup_storage(
+        &self,
+        remove_orphaned_files: bool,
+        remove_missing_audio_entries: bool,
+    ) -> Result<(usize, u64, usize)> {
+        let usage = self.storage_usage()?;
+        let mut files_removed = 0;
+        let mut bytes_freed = 0;
+        let mut entries_removed = 0;
+
+        if remove_orphaned_files {
+            for file_name in &usage.orphaned_files {
+                let file_path = self.recordings_dir.join(file_name);
+                let size = fs::metadata(&file_path).map(|m| m.len()).unwrap_or(0);
+                match fs::remove_file(&file_path) {
+                    Ok(()) => {
+                        debug!("Deleted orphaned audio file: {}", file_name);
+                        files_removed += 1;
+                        bytes_freed += size;
+                    }
+                    Err(e) => {
+                        error!("Failed to delete orphaned audio file {}: {}", file_name, e)
+                    }
+                }
+            }
+        }
+
+        if remove_missing_audio_entries && !usage.missing_audio_entries.is_empty() {
+            let conn = self.get_connection()?;
+            let mut kinds = HashSet::new();
+            for entry in &usage.missing_audio_entries {
+                Self::delete_rows_with_conn(&conn, entry.kind, entry.id)?;
+                entries_removed += 1;
+                kinds.insert(entry.kind);
+            }
+
+            for kind in kinds {
+                if let Err(e) = self.app_handle.emit(kind.updated_event(), ()) {
+                    error!("Failed to emit {} event: {}", kind.updated_event(), e);
+                }
+            }
+        }
+
+        info!(
+            "Storage cleanup removed {} files ({} bytes) and {} history entries",
+            files_removed, bytes_freed, entries_removed
+        );
+        Ok((files_removed, bytes_freed, entries_removed))
+    }
+
+    fn format_timestamp_title(&self, timestamp: i64) -> String {
+        if let Some(utc_datetime) = DateTime::from_timestamp(timestamp, 0) {
+            // Convert UTC to local timezone
+            let local_datetime = utc_datetime.with_timezone(&Local);
+            local_datetime.format("%B %e, %Y - %l:%M%p").to_string()
+        } else {
+            format!("Recording {}", timestamp)
+        }
+    }
+
+    // --- Meetings Support ---
+
+    pub fn create_meeting(&self, title: Option<String>) -> Result<(i64, PathBuf)> {
+        let timestamp = Utc::now().timestamp();
+        let title = title.unwrap_or_else(|| self.format_timestamp_title(timestamp));
+        let file_name = format!("meeting-{}.wav", timestamp);
+        
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO meetings (title, start_timestamp, is_pro, file_name) VALUES (?1, ?2, ?3, ?4)",
+            params![title, timestamp, false, file_name],
+        )?;
+        
+        let id = conn.last_insert_rowid();
+        let file_path = self.recordings_dir.join(&file_name);
+        
+        Ok((id, file_path))
+    }
+
+    pub fn add_meeting_segment(
+        &self,
+        meeting_id: i64,
+        speaker_id: String,
+        start_offset: f64,
+        end_offset: f64,
+        text: String,
+    ) -> Result<()> {
+        let conn = self.get_connection()?;
+        conn.execute(
+            "INSERT INTO meeting_segments (meeting_id, speaker_id, start_time_offset, end_time_offset, text) 
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![meeting_id, speaker_id, start_offset, end_offset, text],
+        )?;
+        Ok(())
     }
 
     pub fn finalize_meeting(&self, meeting_id: i64, duration: i32) -> Result<()> {
@@ -638,22 +1473,16 @@ impl HistoryManager {
         Ok(conn.last_insert_rowid())
     }
 
-    pub fn get_tts_history(&self) -> Result<Vec<TtsHistoryEntry>> {
+    /// TTS entries, newest first. With `tags`, only entries carrying all of them.
+    pub fn get_tts_history(&self, tags: &[String]) -> Result<Vec<TtsHistoryEntry>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, text, voice_id, file_name, timestamp, is_favorite FROM tts_history ORDER BY timestamp DESC"
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM tts_history {} ORDER BY timestamp DESC",
+            TTS_COLUMNS,
+            tag_filter(tags.len())
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(TtsHistoryEntry {
-                id: row.get(0)?,
-                text: row.get(1)?,
-                voice_id: row.get(2)?,
-                file_name: row.get(3)?,
-                timestamp: row.get(4)?,
-                is_favorite: row.get(5)?,
-            })
-        })?;
+        let rows = stmt.query_map(params_from_iter(tags), tts_entry_from_row)?;
 
         let mut entries = Vec::new();
         for row in rows {
@@ -690,26 +1519,20 @@ impl HistoryManager {
         Ok(())
     }
 
-    pub fn get_meetings(&self) -> Result<Vec<Meeting>> {
+    /// Meetings, newest first. With `tags`, only meetings carrying all of them.
+    pub fn get_meetings(&self, tags: &[String]) -> Result<Vec<Meeting>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, title, start_timestamp, end_timestamp, duration_seconds, summary, is_pro, file_name, is_favorite 
-             FROM meetings ORDER BY start_timestamp DESC",
-        )?;
+        Self::get_meetings_with_conn(&conn, tags)
+    }
 
-        let rows = stmt.query_map([], |row| {
-            Ok(Meeting {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                start_timestamp: row.get(2)?,
-                end_timestamp: row.get(3)?,
-                duration_seconds: row.get(4)?,
-                summary: row.get(5)?,
-                is_pro: row.get(6)?,
-                file_name: row.get(7)?,
-                is_favorite: row.get(8).unwrap_or(false),
-            })
-        })?;
+    fn get_meetings_with_conn(conn: &Connection, tags: &[String]) -> Result<Vec<Meeting>> {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM meetings {} ORDER BY start_timestamp DESC",
+            MEETING_COLUMNS,
+            tag_filter(tags.len())
+        ))?;
+
+        let rows = stmt.query_map(params_from_iter(tags), meeting_from_row)?;
 
         let mut meetings = Vec::new();
         for row in rows {
@@ -720,24 +1543,12 @@ impl HistoryManager {
 
     pub fn get_meeting_by_id(&self, id: i64) -> Result<Option<Meeting>> {
         let conn = self.get_connection()?;
-        let mut stmt = conn.prepare(
-            "SELECT id, title, start_timestamp, end_timestamp, duration_seconds, summary, is_pro, file_name, is_favorite 
-             FROM meetings WHERE id = ?1",
-        )?;
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {} FROM meetings WHERE id = ?1",
+            MEETING_COLUMNS
+        ))?;
 
-        let meeting = stmt.query_row([id], |row| {
-            Ok(Meeting {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                start_timestamp: row.get(2)?,
-                end_timestamp: row.get(3)?,
-                duration_seconds: row.get(4)?,
-                summary: row.get(5)?,
-                is_pro: row.get(6)?,
-                file_name: row.get(7)?,
-                is_favorite: row.get(8).unwrap_or(false),
-            })
-        }).optional()?;
+        let meeting = stmt.query_row([id], meeting_from_row).optional()?;
 
         Ok(meeting)
     }
@@ -763,6 +1574,13 @@ impl HistoryManager {
 
     pub fn get_meeting_segments(&self, meeting_id: i64) -> Result<Vec<MeetingSegment>> {
         let conn = self.get_connection()?;
+        Self::get_meeting_segments_with_conn(&conn, meeting_id)
+    }
+
+    fn get_meeting_segments_with_conn(
+        conn: &Connection,
+        meeting_id: i64,
+    ) -> Result<Vec<MeetingSegment>> {
         let mut stmt = conn.prepare(
             "SELECT id, meeting_id, speaker_id, start_time_offset, end_time_offset, text 
              FROM meeting_segments WHERE meeting_id = ?1 ORDER BY start_time_offset ASC",
@@ -785,6 +1603,687 @@ impl HistoryManager {
         }
         Ok(segments)
     }
+
+    // --- Titles, Tags and Notes ---
+
+    /// Renames an entry. An empty title restores the default: the timestamp
+    /// for transcriptions and meetings, the text itself for TTS entries.
+    pub fn set_title(&self, kind: EntryKind, id: i64, title: String) -> Result<()> {
+        let conn = self.get_connection()?;
+        let title = title.trim();
+        let title = if !title.is_empty() {
+            Some(title.to_string())
+        } else if kind == EntryKind::Tts {
+            None
+        } else {
+            let timestamp: i64 = conn.query_row(
+                &format!(
+                    "SELECT {} FROM {} WHERE id = ?1",
+                    kind.timestamp_column(),
+                    kind.table()
+                ),
+                [id],
+                |row| row.get(0),
+            )?;
+            Some(self.format_timestamp_title(timestamp))
+        };
+        Self::update_column_with_conn(&conn, kind, id, "title", title)?;
+        self.emit_entry_updated(kind);
+        Ok(())
+    }
+
+    /// Replaces an entry's tags, returning them as stored.
+    pub fn set_tags(&self, kind: EntryKind, id: i64, tags: Vec<String>) -> Result<Vec<String>> {
+        let conn = self.get_connection()?;
+        let tags = Self::set_tags_with_conn(&conn, kind, id, tags)?;
+        self.emit_entry_updated(kind);
+        Ok(tags)
+    }
+
+    fn set_tags_with_conn(
+        conn: &Connection,
+        kind: EntryKind,
+        id: i64,
+        tags: Vec<String>,
+    ) -> Result<Vec<String>> {
+        let tags = normalize_tags(tags);
+        Self::update_column_with_conn(conn, kind, id, "tags", serde_json::to_string(&tags)?)?;
+        Ok(tags)
+    }
+
+    pub fn set_notes(&self, kind: EntryKind, id: i64, notes: Option<String>) -> Result<()> {
+        let conn = self.get_connection()?;
+        let notes = notes.filter(|n| !n.trim().is_empty());
+        Self::update_column_with_conn(&conn, kind, id, "notes", notes)?;
+        self.emit_entry_updated(kind);
+        Ok(())
+    }
+
+    fn update_column_with_conn(
+        conn: &Connection,
+        kind: EntryKind,
+        id: i64,
+        column: &str,
+        value: impl rusqlite::ToSql,
+    ) -> Result<()> {
+        let updated = conn.execute(
+            &format!("UPDATE {} SET {} = ?1 WHERE id = ?2", kind.table(), column),
+            params![value, id],
+        )?;
+        if updated == 0 {
+            anyhow::bail!("Entry {} not found", id);
+        }
+        Ok(())
+    }
+
+    /// Every tag in use across transcriptions, meetings and TTS entries.
+    pub fn get_all_tags(&self) -> Result<Vec<String>> {
+        let conn = self.get_connection()?;
+        Self::get_all_tags_with_conn(&conn)
+    }
+
+    fn get_all_tags_with_conn(conn: &Connection) -> Result<Vec<String>> {
+        let mut stmt = conn.prepare(
+            "SELECT DISTINCT json_each.value FROM (
+                 SELECT tags FROM transcription_history
+                 UNION ALL SELECT tags FROM meetings
+                 UNION ALL SELECT tags FROM tts_history
+             ) AS tagged, json_each(tagged.tags)
+             ORDER BY json_each.value COLLATE NOCASE",
+        )?;
+        let tags = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(normalize_tags(tags))
+    }
+
+    /// The text an entry is about, used to generate a title for it.
+    pub async fn get_entry_content(&self, kind: EntryKind, id: i64) -> Result<Option<String>> {
+        let content = match kind {
+            EntryKind::Transcription => self
+                .get_entry_by_id(id)
+                .await?
+                .map(|e| e.final_text().to_string()),
+            EntryKind::Meeting => {
+                let Some(meeting) = self.get_meeting_by_id(id)? else {
+                    return Ok(None);
+                };
+                let segments = self.get_meeting_segments(id)?;
+                if segments.is_empty() {
+                    meeting.summary
+                } else {
+                    let texts: Vec<&str> = segments.iter().map(|s| s.text.trim()).collect();
+                    Some(texts.join(" "))
+                }
+            }
+            EntryKind::Tts => {
+                let conn = self.get_connection()?;
+                conn.query_row("SELECT text FROM tts_history WHERE id = ?1", [id], |row| {
+                    row.get(0)
+                })
+                .optional()?
+            }
+        };
+        Ok(content)
+    }
+
+    fn emit_entry_updated(&self, kind: EntryKind) {
+        let event = kind.updated_event();
+        if let Err(e) = self.app_handle.emit(event, ()) {
+            error!("Failed to emit {} event: {}", event, e);
+        }
+    }
+
+    // --- Backup and Restore ---
+
+    /// Copies the database to `path`, unencrypted, with SQLite's online
+    /// backup API, so the copy is consistent even while recordings are being
+    /// saved. SQLCipher can't back up into a plain file, so an encrypted
+    /// database is exported instead.
+    pub fn backup_database(&self, path: &Path) -> Result<()> {
+        let conn = self.get_connection()?;
+        if self.is_encrypted() {
+            Self::export_database_with_conn(&conn, path, None)
+        } else {
+            conn.backup(rusqlite::MAIN_DB, path, None)?;
+            Ok(())
+        }
+    }
+
+    /// File names of the recordings `selection` covers.
+    pub fn recordings_for_backup(&self, selection: RecordingSelection) -> Result<Vec<String>> {
+        let conn = self.get_connection()?;
+        Self::recordings_for_backup_with_conn(&conn, selection)
+    }
+
+    fn recordings_for_backup_with_conn(
+        conn: &Connection,
+        selection: RecordingSelection,
+    ) -> Result<Vec<String>> {
+        let query = match selection {
+            RecordingSelection::None => return Ok(Vec::new()),
+            RecordingSelection::Saved => {
+                "SELECT file_name FROM transcription_history WHERE saved = 1
+                 UNION SELECT file_name FROM meetings WHERE is_favorite = 1 AND file_name IS NOT NULL
+                 UNION SELECT file_name FROM tts_history WHERE is_favorite = 1"
+            }
+            RecordingSelection::All => {
+                "SELECT file_name FROM transcription_history
+                 UNION SELECT file_name FROM meetings WHERE file_name IS NOT NULL
+                 UNION SELECT file_name FROM tts_history"
+            }
+        };
+        let mut stmt = conn.prepare(query)?;
+        let files = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<rusqlite::Result<Vec<String>>>()?;
+        Ok(files)
+    }
+
+    /// Merges another history database, e.g. from a backup, into this one.
+    /// Rows get new ids; rows already present are skipped, so importing the
+    /// same backup twice doesn't duplicate anything.
+    /// `recordings` names the audio files the backup brings along. Entries whose
+    /// audio is neither among them nor already here are imported as having had
+    /// their audio deleted, so they don't show up as missing audio.
+    pub fn import_database(&self, path: &Path, recordings: &[String]) -> Result<HistoryImport> {
+        let mut source = Connection::open(path)?;
+        // Backups from older versions lack the newer columns
+        Migrations::new(MIGRATIONS.to_vec()).to_latest(&mut source)?;
+
+        let included: HashSet<&str> = recordings.iter().map(String::as_str).collect();
+        let has_audio =
+            |name: &str| included.contains(name) || self.recordings_dir.join(name).is_file();
+
+        let conn = self.get_connection()?;
+        let imported = Self::import_with_conn(&conn, &source, has_audio)?;
+        info!(
+            "Imported {} history entries, {} meetings and {} TTS entries ({} already present)",
+            imported.entries, imported.meetings, imported.tts_entries, imported.skipped
+        );
+
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+
+        Ok(imported)
+    }
+
+    fn import_with_conn(
+        conn: &Connection,
+        source: &Connection,
+        has_audio: impl Fn(&str) -> bool,
+    ) -> Result<HistoryImport> {
+        let tx = conn.unchecked_transaction()?;
+        let mut imported = HistoryImport::default();
+
+        let entries = Self::get_history_entries_with_conn(source, &[])?;
+        for entry in entries {
+            let exists: bool = tx.query_row(
+                "SELECT COUNT(*) > 0 FROM transcription_history WHERE file_name = ?1 AND timestamp = ?2",
+                params![entry.file_name, entry.timestamp],
+                |row| row.get(0),
+            )?;
+            if exists {
+                imported.skipped += 1;
+                continue;
+            }
+
+            tx.execute(
+                "INSERT INTO transcription_history (file_name, timestamp, saved, title, transcription_text, post_processed_text, post_process_prompt, detected_language, language_probability, edited_text, tags, notes, audio_deleted)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                params![
+                    entry.file_name,
+                    entry.timestamp,
+                    entry.saved,
+                    entry.title,
+                    entry.transcription_text,
+                    entry.post_processed_text,
+                    entry.post_process_prompt,
+                    entry.detected_language,
+                    entry.language_probability,
+                    entry.edited_text,
+                    serde_json::to_string(&entry.tags)?,
+                    entry.notes,
+                    entry.audio_deleted || !has_audio(&entry.file_name)
+                ],
+            )?;
+            let history_id = tx.last_insert_rowid();
+
+            for mut version in Self::get_versions_with_conn(source, entry.id)? {
+                version.history_id = history_id;
+                Self::save_version_with_conn(&tx, version)?;
+            }
+            for revision in Self::get_revisions_with_conn(source, entry.id)? {
+                tx.execute(
+                    "INSERT INTO transcription_revisions (history_id, timestamp, text) VALUES (?1, ?2, ?3)",
+                    params![history_id, revision.timestamp, revision.text],
+                )?;
+            }
+            imported.entries += 1;
+        }
+
+        for meeting in Self::get_meetings_with_conn(source, &[])? {
+            // A meeting imported before without its audio has no file name
+            let exists: bool = tx.query_row(
+                "SELECT COUNT(*) > 0 FROM meetings
+                 WHERE start_timestamp = ?1 AND (file_name IS ?2 OR file_name IS NULL)",
+                params![meeting.start_timestamp, meeting.file_name],
+                |row| row.get(0),
+            )?;
+            if exists {
+                imported.skipped += 1;
+                continue;
+            }
+
+            tx.execute(
+                "INSERT INTO meetings (title, start_timestamp, end_timestamp, duration_seconds, summary, is_pro, file_name, is_favorite, tags, notes)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    meeting.title,
+                    meeting.start_timestamp,
+                    meeting.end_timestamp,
+                    meeting.duration_seconds,
+                    meeting.summary,
+                    meeting.is_pro,
+                    meeting.file_name.as_deref().filter(|name| has_audio(name)),
+                    meeting.is_favorite,
+                    serde_json::to_string(&meeting.tags)?,
+                    meeting.notes
+                ],
+            )?;
+            let meeting_id = tx.last_insert_rowid();
+
+            for segment in Self::get_meeting_segments_with_conn(source, meeting.id)? {
+                tx.execute(
+                    "INSERT INTO meeting_segments (meeting_id, speaker_id, start_time_offset, end_time_offset, text)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        meeting_id,
+                        segment.speaker_id,
+                        segment.start_time_offset,
+                        segment.end_time_offset,
+                        segment.text
+                    ],
+                )?;
+            }
+            imported.meetings += 1;
+        }
+
+        let mut stmt = source.prepare(&format!("SELECT {} FROM tts_history", TTS_COLUMNS))?;
+        let tts_entries = stmt
+            .query_map([], tts_entry_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for entry in tts_entries {
+            let exists: bool = tx.query_row(
+                "SELECT COUNT(*) > 0 FROM tts_history WHERE file_name = ?1 AND timestamp = ?2",
+                params![entry.file_name, entry.timestamp],
+                |row| row.get(0),
+            )?;
+            if exists {
+                imported.skipped += 1;
+                continue;
+            }
+
+            tx.execute(
+                "INSERT INTO tts_history (text, voice_id, file_name, timestamp, is_favorite, title, tags, notes, audio_deleted)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                params![
+                    entry.text,
+                    entry.voice_id,
+                    entry.file_name,
+                    entry.timestamp,
+                    entry.is_favorite,
+                    entry.title,
+                    serde_json::to_string(&entry.tags)?,
+                    entry.notes,
+                    entry.audio_deleted || !has_audio(&entry.file_name)
+                ],
+            )?;
+            imported.tts_entries += 1;
+        }
+
+        tx.commit()?;
+        Ok(imported)
+    }
+
+    // --- Recording Formats ---
+
+    fn recording_format(&self) -> AudioFormat {
+        match crate::settings::get_settings(&self.app_handle).recording_format {
+            RecordingFormat::Wav => AudioFormat::Wav,
+            RecordingFormat::Flac => AudioFormat::Flac,
+            RecordingFormat::Opus => AudioFormat::Opus,
+        }
+    }
+
+    /// Encodes a recording to `path`, encrypted if history is.
+    fn write_recording(&self, path: &Path, format: AudioFormat, samples: &[f32]) -> Result<()> {
+        let written = fs::write(path, encode_audio(format, samples)?)
+            .map_err(anyhow::Error::from)
+            .and_then(|_| self.seal_recording(path));
+        if written.is_err() {
+            let _ = fs::remove_file(path);
+        }
+        written
+    }
+
+    /// Stores a finished meeting's recording, written as WAV while the
+    /// meeting ran, in the configured format and encrypted if history is.
+    pub fn store_meeting_recording(&self, meeting_id: i64) -> Result<()> {
+        let Some(file_name) = self
+            .get_meeting_by_id(meeting_id)?
+            .and_then(|meeting| meeting.file_name)
+        else {
+            return Ok(());
+        };
+
+        let format = self.recording_format();
+        if AudioFormat::from_path(&file_name) != Some(format) {
+            match self.reencode_recording(EntryKind::Meeting, meeting_id, &file_name, format) {
+                Ok(_) => return Ok(()),
+                Err(e) => warn!("Keeping meeting recording {} as is: {}", file_name, e),
+            }
+        }
+        self.seal_recording(&self.get_audio_file_path(&file_name))
+    }
+
+    /// Starts re-encoding the recordings of history entries and finished
+    /// meetings that aren't in the configured format yet, on a background
+    /// thread. TTS clips are left alone. Progress is reported through
+    /// `recording-reencode-progress`, and the outcome through
+    /// `recording-reencode-completed` or `recording-reencode-failed`.
+    pub fn start_reencode(self: &Arc<Self>) -> Result<()> {
+        if self.reencoding.swap(true, Ordering::SeqCst) {
+            anyhow::bail!("Recordings are already being re-encoded");
+        }
+
+        let manager = self.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let outcome = manager.reencode_recordings();
+            manager.reencoding.store(false, Ordering::SeqCst);
+            let emitted = match outcome {
+                Ok(result) => manager
+                    .app_handle
+                    .emit("recording-reencode-completed", result),
+                Err(e) => {
+                    error!("Failed to re-encode recordings: {}", e);
+                    manager
+                        .app_handle
+                        .emit("recording-reencode-failed", e.to_string())
+                }
+            };
+            if let Err(e) = emitted {
+                error!("Failed to emit re-encode outcome: {}", e);
+            }
+        });
+        Ok(())
+    }
+
+    fn reencode_recordings(&self) -> Result<RecordingReencode> {
+        let format = self.recording_format();
+        let pending = {
+            let conn = self.get_connection()?;
+            Self::recordings_to_reencode_with_conn(&conn, format)?
+        };
+
+        let total = pending.len();
+        let mut result = RecordingReencode::default();
+        for (done, (kind, id, file_name)) in pending.into_iter().enumerate() {
+            if let Ok(before) = fs::metadata(self.get_audio_file_path(&file_name)) {
+                match self.reencode_recording(kind, id, &file_name, format) {
+                    Ok(new_name) => {
+                        let after = fs::metadata(self.get_audio_file_path(&new_name))
+                            .map(|m| m.len())
+                            .unwrap_or(0);
+                        result.converted += 1;
+                        result.bytes_saved += before.len() as i64 - after as i64;
+                    }
+                    Err(e) => {
+                        warn!("Failed to re-encode recording {}: {}", file_name, e);
+                        result.failed += 1;
+                    }
+                }
+            }
+
+            let progress = RecordingReencodeProgress {
+                done: done + 1,
+                total,
+            };
+            let _ = self
+                .app_handle
+                .emit("recording-reencode-progress", progress);
+        }
+
+        info!(
+            "Re-encoded {} recordings as {} ({} failed, {} bytes saved)",
+            result.converted,
+            format.extension(),
+            result.failed,
+            result.bytes_saved
+        );
+        if let Err(e) = self.app_handle.emit("history-updated", ()) {
+            error!("Failed to emit history-updated event: {}", e);
+        }
+        Ok(result)
+    }
+
+    /// Recordings of history entries and finished meetings stored in another
+    /// format than `format`.
+    fn recordings_to_reencode_with_conn(
+        conn: &Connection,
+        format: AudioFormat,
+    ) -> Result<Vec<(EntryKind, i64, String)>> {
+        let mut stmt = conn.prepare(
+            "SELECT 'transcription', id, file_name FROM transcription_history
+             UNION ALL
+             SELECT 'meeting', id, file_name FROM meetings
+             WHERE file_name IS NOT NULL AND end_timestamp IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let kind = match row.get::<_, String>(0)?.as_str() {
+                "meeting" => EntryKind::Meeting,
+                _ => EntryKind::Transcription,
+            };
+            Ok((kind, row.get::<_, i64>(1)?, row.get::<_, String>(2)?))
+        })?;
+
+        let mut pending = Vec::new();
+        for row in rows {
+            let (kind, id, file_name) = row?;
+            if AudioFormat::from_path(&file_name).is_some_and(|current| current != format) {
+                pending.push((kind, id, file_name));
+            }
+        }
+        Ok(pending)
+    }
+
+    /// Rewrites a recording in `format`, points its row at the new file and
+    /// removes the old one. Returns the new file name.
+    fn reencode_recording(
+        &self,
+        kind: EntryKind,
+        id: i64,
+        file_name: &str,
+        format: AudioFormat,
+    ) -> Result<String> {
+        let samples = self.read_recording_samples(file_name)?;
+        let new_name = Path::new(file_name)
+            .with_extension(format.extension())
+            .to_string_lossy()
+            .into_owned();
+        self.write_recording(&self.get_audio_file_path(&new_name), format, &samples)?;
+
+        let conn = self.get_connection()?;
+        Self::update_column_with_conn(&conn, kind, id, "file_name", &new_name)?;
+        // Should this fail, the old file is left as an orphan
+        if let Err(e) = fs::remove_file(self.get_audio_file_path(file_name)) {
+            warn!("Failed to remove re-encoded recording {}: {}", file_name, e);
+        }
+        Ok(new_name)
+    }
+
+    // --- Encryption at Rest ---
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption_key.read().unwrap().is_some()
+    }
+
+    /// Encrypts a freshly written recording when history is encrypted.
+    pub fn seal_recording(&self, path: &Path) -> Result<()> {
+        if let Some(key) = self.encryption_key.read().unwrap().as_ref() {
+            encryption::encrypt_file(key, path)?;
+        }
+        Ok(())
+    }
+
+    /// A recording's samples, decrypted and decoded as needed.
+    pub fn read_recording_samples(&self, file_name: &str) -> Result<Vec<f32>> {
+        let format = AudioFormat::from_path(file_name)
+            .ok_or_else(|| anyhow::anyhow!("Unsupported recording format: {}", file_name))?;
+        let path = self.get_audio_file_path(file_name);
+        let key = self.encryption_key.read().unwrap().clone();
+        let bytes = encryption::read_decrypted(key.as_ref(), &path)?;
+        decode_audio(format, bytes.as_slice())
+    }
+
+    /// Copies a recording to `target`, decrypted.
+    pub fn export_recording(&self, file_name: &str, target: &Path) -> Result<()> {
+        let key = self.encryption_key.read().unwrap().clone();
+        encryption::decrypt_to(key.as_ref(), &self.get_audio_file_path(file_name), target)
+    }
+
+    /// A recording as the UI plays it: decrypted, and decoded to WAV when
+    /// compressed. Built in memory, so no plaintext copy reaches the disk.
+    pub fn playback_audio(&self, file_name: &str) -> Result<Vec<u8>> {
+        match AudioFormat::from_path(file_name) {
+            Some(AudioFormat::Flac | AudioFormat::Opus) => {
+                encode_audio(AudioFormat::Wav, &self.read_recording_samples(file_name)?)
+            }
+            _ => {
+                let key = self.encryption_key.read().unwrap().clone();
+                encryption::read_decrypted(key.as_ref(), &self.get_audio_file_path(file_name))
+            }
+        }
+    }
+
+    /// Turns encryption at rest on or off, converting the database and the
+    /// recordings. The key lives in the system keychain; losing it makes the
+    /// history unreadable. Returns how many recordings couldn't be converted.
+    pub fn set_encryption(&self, enabled: bool) -> Result<usize> {
+        if enabled {
+            self.enable_encryption()
+        } else {
+            self.disable_encryption()
+        }
+    }
+
+    fn enable_encryption(&self) -> Result<usize> {
+        let key = {
+            let mut current = self.encryption_key.write().unwrap();
+            if current.is_some() {
+                return Ok(0);
+            }
+
+            let key = EncryptionKey::generate();
+            secrets::set(ENCRYPTION_KEY_SECRET, &key.to_hex())?;
+            if let Err(e) = self.convert_database(None, Some(&key)) {
+                let _ = secrets::delete(ENCRYPTION_KEY_SECRET);
+                return Err(e);
+            }
+            *current = Some(key.clone());
+            key
+        };
+
+        // New recordings are sealed as they're saved from here on
+        let failed = self.convert_recordings(|path| encryption::encrypt_file(&key, path))?;
+        info!("History encrypted ({} recordings failed)", failed);
+        Ok(failed)
+    }
+
+    fn disable_encryption(&self) -> Result<usize> {
+        // Held throughout, so no recording is sealed with a key about to go
+        let mut current = self.encryption_key.write().unwrap();
+        let Some(key) = current.clone() else {
+            return Ok(0);
+        };
+
+        let failed = self.convert_recordings(|path| encryption::decrypt_file(&key, path))?;
+        if failed > 0 {
+            anyhow::bail!(
+                "{} recordings couldn't be decrypted, so history stays encrypted",
+                failed
+            );
+        }
+        self.convert_database(Some(&key), None)?;
+        *current = None;
+        secrets::delete(ENCRYPTION_KEY_SECRET)?;
+
+        info!("History decrypted");
+        Ok(0)
+    }
+
+    /// Rewrites the database with another key (or none) and swaps it in.
+    fn convert_database(
+        &self,
+        from: Option<&EncryptionKey>,
+        to: Option<&EncryptionKey>,
+    ) -> Result<()> {
+        let mut converted = self.db_path.as_os_str().to_owned();
+        converted.push(".converting");
+        let converted = PathBuf::from(converted);
+        let _ = fs::remove_file(&converted);
+
+        let conn = open_database(&self.db_path, from)?;
+        let exported = Self::export_database_with_conn(&conn, &converted, to);
+        drop(conn);
+        if let Err(e) = exported {
+            let _ = fs::remove_file(&converted);
+            return Err(e);
+        }
+        fs::rename(&converted, &self.db_path)?;
+        Ok(())
+    }
+
+    /// Copies the database into a new file, encrypted with `key` or plain.
+    fn export_database_with_conn(
+        conn: &Connection,
+        path: &Path,
+        key: Option<&EncryptionKey>,
+    ) -> Result<()> {
+        let key = key.map(EncryptionKey::sqlcipher_key).unwrap_or_default();
+        conn.execute(
+            "ATTACH DATABASE ?1 AS export KEY ?2",
+            params![path.to_string_lossy().into_owned(), key],
+        )?;
+        let exported = conn
+            .query_row("SELECT sqlcipher_export('export')", [], |_| Ok(()))
+            .and_then(|_| {
+                let version: i32 =
+                    conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+                conn.pragma_update(Some("export"), "user_version", version)
+            });
+        conn.execute("DETACH DATABASE export", [])?;
+        exported?;
+        Ok(())
+    }
+
+    /// Applies `convert` to every recording, returning how many failed.
+    fn convert_recordings(&self, convert: impl Fn(&Path) -> Result<()>) -> Result<usize> {
+        let mut failed = 0;
+        for entry in fs::read_dir(&self.recordings_dir)? {
+            let path = entry?.path();
+            // Leftovers of an interrupted conversion
+            if !path.is_file() || path.extension().is_some_and(|ext| ext == "tmp") {
+                continue;
+            }
+            if let Err(e) = convert(&path) {
+                warn!("Failed to convert recording {:?}: {}", path, e);
+                failed += 1;
+            }
+        }
+        Ok(failed)
+    }
 }
 
 #[cfg(test)]
@@ -803,10 +2302,33 @@ mod tests {
                 title TEXT NOT NULL,
                 transcription_text TEXT NOT NULL,
                 post_processed_text TEXT,
-                post_process_prompt TEXT
+                post_process_prompt TEXT,
+                detected_language TEXT,
+                language_probability REAL,
+                edited_text TEXT,
+                tags TEXT NOT NULL DEFAULT '[]',
+                notes TEXT,
+                audio_deleted BOOLEAN NOT NULL DEFAULT 0
+            );
+            CREATE TABLE transcription_revisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                history_id INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL,
+                text TEXT NOT NULL
+            );
+            CREATE TABLE transcription_versions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                history_id INTEGER NOT NULL,
+                timestamp INTEGER NOT NULL,
+                model_id TEXT NOT NULL,
+                language TEXT,
+                transcription_text TEXT NOT NULL,
+                post_processed_text TEXT,
+                post_process_prompt TEXT,
+                detected_language TEXT
             );",
         )
-        .expect("create transcription_history table");
+        .expect("create history tables");
         conn
     }
 
@@ -827,6 +2349,86 @@ mod tests {
         .expect("insert history entry");
     }
 
+    #[test]
+    fn storage_usage_classifies_recordings() {
+        let conn = migrated_conn();
+        conn.execute_batch(
+            "INSERT INTO meetings (title, start_timestamp, end_timestamp, file_name, is_favorite) VALUES
+                ('kept', 1, 2, 'meeting-1.wav', 0), ('no audio', 2, 3, NULL, 0),
+                ('audio gone', 3, 4, 'meeting-3.wav', 0), ('favorite', 4, 5, 'meeting-4.wav', 1),
+                ('recording', 5, NULL, 'meeting-5.wav', 0);
+             INSERT INTO tts_history (text, voice_id, file_name, timestamp, audio_deleted) VALUES
+                ('kept', 'voice', 'tts-1.wav', 1, 0), ('audio gone', 'voice', 'tts-2.wav', 2, 0),
+                ('audio deleted', 'voice', 'tts-3.wav', 3, 1);",
+        )
+        .expect("insert meetings and tts entries");
+        insert_entry(&conn, 100, "kept", None);
+        insert_entry(&conn, 200, "audio gone", None);
+        insert_entry(&conn, 300, "saved", None);
+        insert_entry(&conn, 400, "audio deleted", None);
+        conn.execute_batch(
+            "UPDATE transcription_history SET saved = 1 WHERE id = 3;
+             UPDATE transcription_history SET audio_deleted = 1 WHERE id = 4;",
+        )
+        .expect("mark entries");
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        for (name, len) in [
+            ("typezero-100.wav", 10),
+            ("meeting-1.wav", 20),
+            ("tts-1.wav", 30),
+            ("typezero-999.wav", 40),
+            ("meeting-999.opus", 5),
+            ("notes.txt", 50),
+        ] {
+            fs::write(dir.path().join(name), vec![0u8; len]).expect("write file");
+        }
+        let old = SystemTime::now() - ORPHAN_GRACE_PERIOD * 2;
+        for name in ["typezero-999.wav", "meeting-999.opus", "notes.txt"] {
+            fs::File::options()
+                .write(true)
+                .open(dir.path().join(name))
+                .and_then(|f| f.set_modified(old))
+                .expect("age orphan");
+        }
+
+        let usage =
+            HistoryManager::storage_usage_with_conn(&conn, dir.path()).expect("storage usage");
+        assert_eq!(usage.recordings_bytes, 10);
+        assert_eq!(usage.meetings_bytes, 20);
+        assert_eq!(usage.tts_bytes, 30);
+        assert_eq!(
+            usage.orphaned_files,
+            vec!["meeting-999.opus", "typezero-999.wav"]
+        );
+        assert_eq!(usage.orphaned_bytes, 45);
+        let missing: Vec<_> = usage
+            .missing_audio_entries
+            .iter()
+            .map(|entry| (entry.kind, entry.id))
+            .collect();
+        assert_eq!(
+            missing,
+            [
+                (EntryKind::Transcription, 2),
+                (EntryKind::Meeting, 3),
+                (EntryKind::Tts, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn fresh_unreferenced_recordings_are_not_orphans() {
+        let conn = migrated_conn();
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        fs::write(dir.path().join("typezero-1.wav"), b"RIFF").expect("write file");
+
+        let usage =
+            HistoryManager::storage_usage_with_conn(&conn, dir.path()).expect("storage usage");
+        assert!(usage.orphaned_files.is_empty());
+    }
+
     #[test]
     fn get_latest_entry_returns_none_when_empty() {
         let conn = setup_conn();
@@ -848,4 +2450,315 @@ mod tests {
         assert_eq!(entry.transcription_text, "second");
         assert_eq!(entry.post_processed_text.as_deref(), Some("processed"));
     }
+
+    fn version(history_id: i64, timestamp: i64, model_id: &str) -> TranscriptionVersion {
+        TranscriptionVersion {
+            id: 0,
+            history_id,
+            timestamp,
+            model_id: model_id.to_string(),
+            language: None,
+            transcription_text: format!("text from {}", model_id),
+            post_processed_text: None,
+            post_process_prompt: None,
+            detected_language: Some("en".to_string()),
+        }
+    }
+
+    #[test]
+    fn versions_are_listed_per_entry_and_deleted_with_it() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "original", None);
+        insert_entry(&conn, 200, "other", None);
+
+        HistoryManager::save_version_with_conn(&conn, version(1, 300, "large")).unwrap();
+        HistoryManager::save_version_with_conn(&conn, version(1, 250, "small")).unwrap();
+        HistoryManager::save_version_with_conn(&conn, version(2, 300, "small")).unwrap();
+
+        let versions = HistoryManager::get_versions_with_conn(&conn, 1).unwrap();
+        let models: Vec<&str> = versions.iter().map(|v| v.model_id.as_str()).collect();
+        assert_eq!(models, ["small", "large"]);
+        assert_eq!(versions[1].transcription_text, "text from large");
+
+        assert_eq!(HistoryManager::delete_history_rows(&conn, 1).unwrap(), 1);
+        assert!(HistoryManager::get_versions_with_conn(&conn, 1)
+            .unwrap()
+            .is_empty());
+        let other = HistoryManager::get_versions_with_conn(&conn, 2).unwrap();
+        assert_eq!(other.len(), 1);
+    }
+
+    #[test]
+    fn edits_keep_original_and_every_revision() {
+        let conn = setup_conn();
+        insert_entry(&conn, 100, "deploy to cooper netties", None);
+
+        HistoryManager::edit_entry_with_conn(&conn, 1, "deploy to kubernetes".into(), 200).unwrap();
+        HistoryManager::edit_entry_with_conn(&conn, 1, "Deploy to Kubernetes".into(), 300).unwrap();
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&conn)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.transcription_text, "deploy to cooper netties");
+        assert_eq!(entry.edited_text.as_deref(), Some("Deploy to Kubernetes"));
+
+        let revisions = HistoryManager::get_revisions_with_conn(&conn, 1).unwrap();
+        let texts: Vec<&str> = revisions.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(texts, ["deploy to kubernetes", "Deploy to Kubernetes"]);
+
+        assert!(HistoryManager::edit_entry_with_conn(&conn, 42, "x".into(), 400).is_err());
+    }
+
+    #[test]
+    fn normalizes_tags() {
+        let tags = vec![" work ".into(), "".into(), "Work".into(), "ideas".into()];
+        assert_eq!(normalize_tags(tags), ["work", "ideas"]);
+    }
+
+    #[test]
+    fn filters_entries_and_meetings_by_tags() {
+        let conn = setup_conn();
+        conn.execute_batch(
+            "CREATE TABLE meetings (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                title TEXT NOT NULL,
+                start_timestamp INTEGER NOT NULL,
+                end_timestamp INTEGER,
+                duration_seconds INTEGER DEFAULT 0,
+                summary TEXT,
+                is_pro BOOLEAN NOT NULL DEFAULT 0,
+                file_name TEXT,
+                is_favorite BOOLEAN NOT NULL DEFAULT 0,
+                tags TEXT NOT NULL DEFAULT '[]',
+                notes TEXT
+            );
+            CREATE TABLE tts_history (id INTEGER PRIMARY KEY, tags TEXT NOT NULL DEFAULT '[]');
+            INSERT INTO meetings (title, start_timestamp) VALUES ('Standup', 100);",
+        )
+        .expect("create meeting and tts tables");
+        insert_entry(&conn, 100, "first", None);
+        insert_entry(&conn, 200, "second", None);
+        insert_entry(&conn, 300, "third", None);
+
+        let set = |kind, id, tags: &[&str]| {
+            let tags = tags.iter().map(|t| t.to_string()).collect();
+            HistoryManager::set_tags_with_conn(&conn, kind, id, tags).unwrap()
+        };
+        set(EntryKind::Transcription, 1, &["work", "ideas"]);
+        set(EntryKind::Transcription, 2, &["Work"]);
+        set(EntryKind::Meeting, 1, &["team"]);
+
+        let texts = |tags: &[&str]| -> Vec<String> {
+            let tags: Vec<String> = tags.iter().map(|t| t.to_string()).collect();
+            HistoryManager::get_history_entries_with_conn(&conn, &tags)
+                .unwrap()
+                .into_iter()
+                .map(|e| e.transcription_text)
+                .collect()
+        };
+        assert_eq!(texts(&[]), ["third", "second", "first"]);
+        assert_eq!(texts(&["work"]), ["second", "first"]);
+        assert_eq!(texts(&["work", "ideas"]), ["first"]);
+        assert!(texts(&["missing"]).is_empty());
+
+        let meetings = HistoryManager::get_meetings_with_conn(&conn, &["team".into()]).unwrap();
+        assert_eq!(meetings.len(), 1);
+        assert_eq!(meetings[0].tags, ["team"]);
+
+        assert_eq!(
+            HistoryManager::get_all_tags_with_conn(&conn).unwrap(),
+            ["ideas", "team", "work"]
+        );
+        assert!(HistoryManager::set_tags_with_conn(&conn, EntryKind::Tts, 9, vec![]).is_err());
+    }
+
+    fn migrated_conn() -> Connection {
+        let mut conn = Connection::open_in_memory().expect("open in-memory db");
+        Migrations::new(MIGRATIONS.to_vec())
+            .to_latest(&mut conn)
+            .expect("run migrations");
+        conn
+    }
+
+    #[test]
+    fn import_remaps_ids_and_skips_existing_rows() {
+        let source = migrated_conn();
+        insert_entry(&source, 100, "already here", None);
+        insert_entry(&source, 200, "new", None);
+        HistoryManager::save_version_with_conn(&source, version(2, 300, "large")).unwrap();
+        HistoryManager::edit_entry_with_conn(&source, 2, "new, corrected".into(), 400).unwrap();
+        source
+            .execute_batch(
+                "INSERT INTO meetings (title, start_timestamp, file_name) VALUES ('Standup', 100, 'meeting-100.wav');
+                 INSERT INTO meeting_segments (meeting_id, start_time_offset, end_time_offset, text) VALUES (1, 0.0, 1.0, 'hello');
+                 INSERT INTO tts_history (text, voice_id, file_name, timestamp) VALUES ('hi', 'voice', 'tts-1.wav', 100);",
+            )
+            .unwrap();
+
+        let dest = migrated_conn();
+        insert_entry(&dest, 50, "local", None);
+        insert_entry(&dest, 100, "already here", None);
+
+        let imported = HistoryManager::import_with_conn(&dest, &source, |_| true).unwrap();
+        assert_eq!(
+            (imported.entries, imported.meetings, imported.tts_entries),
+            (1, 1, 1)
+        );
+        assert_eq!(imported.skipped, 1);
+
+        let entry = HistoryManager::get_latest_entry_with_conn(&dest)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.id, 3);
+        assert_eq!(entry.edited_text.as_deref(), Some("new, corrected"));
+        let versions = HistoryManager::get_versions_with_conn(&dest, 3).unwrap();
+        assert_eq!(versions[0].model_id, "large");
+        let revisions = HistoryManager::get_revisions_with_conn(&dest, 3).unwrap();
+        assert_eq!(revisions.len(), 1);
+        let meetings = HistoryManager::get_meetings_with_conn(&dest, &[]).unwrap();
+        let segments =
+            HistoryManager::get_meeting_segments_with_conn(&dest, meetings[0].id).unwrap();
+        assert_eq!(segments[0].text, "hello");
+
+        let again = HistoryManager::import_with_conn(&dest, &source, |_| true).unwrap();
+        assert_eq!(again.entries + again.meetings + again.tts_entries, 0);
+        assert_eq!(again.skipped, 4);
+    }
+
+    #[test]
+    fn import_marks_audio_left_out_of_the_backup() {
+        let source = migrated_conn();
+        insert_entry(&source, 100, "with audio", None);
+        insert_entry(&source, 200, "without audio", None);
+        source
+            .execute_batch(
+                "INSERT INTO meetings (title, start_timestamp, end_timestamp, file_name) VALUES ('Standup', 100, 200, 'meeting-100.wav');
+                 INSERT INTO tts_history (text, voice_id, file_name, timestamp) VALUES ('hi', 'voice', 'tts-1.wav', 100);",
+            )
+            .unwrap();
+
+        let dest = migrated_conn();
+        let has_audio = |name: &str| name == "typezero-100.wav";
+        HistoryManager::import_with_conn(&dest, &source, has_audio).unwrap();
+
+        let dir = tempfile::tempdir().expect("create temp dir");
+        fs::write(dir.path().join("typezero-100.wav"), b"RIFF").expect("write file");
+        let usage =
+            HistoryManager::storage_usage_with_conn(&dest, dir.path()).expect("storage usage");
+        assert!(usage.missing_audio_entries.is_empty());
+
+        let meetings = HistoryManager::get_meetings_with_conn(&dest, &[]).unwrap();
+        assert_eq!(meetings[0].file_name, None);
+        // The meeting without its audio still counts as already imported
+        let again = HistoryManager::import_with_conn(&dest, &source, has_audio).unwrap();
+        assert_eq!(again.skipped, 4);
+    }
+
+    #[test]
+    fn backup_selects_saved_or_all_recordings() {
+        let conn = migrated_conn();
+        insert_entry(&conn, 100, "unsaved", None);
+        insert_entry(&conn, 200, "saved", None);
+        conn.execute_batch(
+            "UPDATE transcription_history SET saved = 1 WHERE id = 2;
+             INSERT INTO meetings (title, start_timestamp, file_name, is_favorite) VALUES ('a', 1, 'meeting-1.wav', 1);
+             INSERT INTO tts_history (text, voice_id, file_name, timestamp) VALUES ('hi', 'voice', 'tts-1.wav', 1);",
+        )
+        .unwrap();
+
+        let files = |selection| {
+            let mut files =
+                HistoryManager::recordings_for_backup_with_conn(&conn, selection).unwrap();
+            files.sort();
+            files
+        };
+        assert!(files(RecordingSelection::None).is_empty());
+        assert_eq!(
+            files(RecordingSelection::Saved),
+            ["meeting-1.wav", "typezero-200.wav"]
+        );
+        assert_eq!(files(RecordingSelection::All).len(), 4);
+    }
+
+    #[test]
+    fn reencoding_skips_converted_recordings_and_running_meetings() {
+        let conn = migrated_conn();
+        insert_entry(&conn, 100, "wav", None);
+        insert_entry(&conn, 200, "opus", None);
+        conn.execute_batch(
+            "UPDATE transcription_history SET file_name = 'typezero-200.opus' WHERE id = 2;
+             INSERT INTO meetings (title, start_timestamp, end_timestamp, file_name) VALUES ('done', 1, 2, 'meeting-1.wav');
+             INSERT INTO meetings (title, start_timestamp, file_name) VALUES ('running', 3, 'meeting-3.wav');
+             INSERT INTO tts_history (text, voice_id, file_name, timestamp) VALUES ('hi', 'voice', 'tts-1.wav', 1);",
+        )
+        .unwrap();
+
+        let pending =
+            HistoryManager::recordings_to_reencode_with_conn(&conn, AudioFormat::Opus).unwrap();
+        let names: Vec<&str> = pending.iter().map(|(_, _, name)| name.as_str()).collect();
+        assert_eq!(names, ["typezero-100.wav", "meeting-1.wav"]);
+        assert_eq!(pending[1].0, EntryKind::Meeting);
+
+        let pending =
+            HistoryManager::recordings_to_reencode_with_conn(&conn, AudioFormat::Wav).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].2, "typezero-200.opus");
+    }
+
+    #[test]
+    fn retention_plan_follows_each_kinds_policy() {
+        const DAY: i64 = 24 * 60 * 60;
+        let now = 100 * DAY;
+        let conn = migrated_conn();
+        for days_ago in [50, 20, 10, 1] {
+            insert_entry(&conn, now - days_ago * DAY, "dictation", None);
+        }
+        conn.execute_batch(&format!(
+            "UPDATE transcription_history SET saved = 1 WHERE id = 1;
+             INSERT INTO meetings (title, start_timestamp, end_timestamp, file_name) VALUES ('old', {old}, {old}, 'meeting-1.wav');
+             INSERT INTO meetings (title, start_timestamp, file_name) VALUES ('running', {old}, 'meeting-2.wav');
+             INSERT INTO tts_history (text, voice_id, file_name, timestamp) VALUES ('hi', 'voice', 'tts-1.wav', {old});",
+            old = now - 50 * DAY
+        ))
+        .unwrap();
+
+        let policies = RetentionPolicies {
+            dictations: RetentionPolicy {
+                max_entries: Some(2),
+                audio_max_age_days: Some(5),
+                ..Default::default()
+            },
+            meetings: RetentionPolicy {
+                max_age_days: Some(30),
+                ..Default::default()
+            },
+            tts: RetentionPolicy::default(),
+        };
+        let plan = |conn: &Connection| {
+            HistoryManager::plan_cleanup_with_conn(conn, &policies, now)
+                .unwrap()
+                .into_iter()
+                .map(|c| (c.kind, c.id, c.action))
+                .collect::<Vec<_>>()
+        };
+        // The saved dictation and the running meeting are left alone
+        assert_eq!(
+            plan(&conn),
+            [
+                (EntryKind::Transcription, 3, CleanupAction::DeleteAudio),
+                (EntryKind::Transcription, 2, CleanupAction::DeleteEntry),
+                (EntryKind::Meeting, 1, CleanupAction::DeleteEntry),
+            ]
+        );
+
+        HistoryManager::mark_audio_deleted_with_conn(&conn, EntryKind::Transcription, 3).unwrap();
+        HistoryManager::delete_rows_with_conn(&conn, EntryKind::Transcription, 2).unwrap();
+        HistoryManager::delete_rows_with_conn(&conn, EntryKind::Meeting, 1).unwrap();
+        assert!(plan(&conn).is_empty());
+
+        let entries = HistoryManager::get_history_entries_with_conn(&conn, &[]).unwrap();
+        let ids: Vec<i64> = entries.iter().map(|e| e.id).collect();
+        assert_eq!(ids, [4, 3, 1]);
+        assert!(entries[1].audio_deleted);
+    }
 }
@@ -212,9 +212,16 @@ impl MeetingManager {
                  // Process final chunk if needed. 
                  // For now we assume the loop captured most relevant audio.
             }
+            crate::hands_free::rearm_if_enabled(&self.app_handle);
 
             let duration = session.start_time.elapsed().as_secs() as i32;
             self.history_manager.finalize_meeting(session.id, duration)?;
+            if let Err(e) = self.history_manager.store_meeting_recording(session.id) {
+                error!("Failed to store recording of meeting {}: {}", session.id, e);
+            }
+            if let Err(e) = self.history_manager.cleanup_old_entries() {
+                error!("Failed to clean up old history entries: {}", e);
+            }
             
             info!("Meeting {} stopped after {}s", session.id, duration);
             let _ = self.app_handle.emit("meeting-stopped", session.id);
@@ -236,24 +243,19 @@ impl MeetingManager {
                     if !full_text.trim().is_empty() {
                          if let Ok(Some(meeting)) = history_manager.get_meeting_by_id(session_id) {
                              if let Some(filename) = meeting.file_name {
-                                 let meeting_wav_path = history_manager.get_audio_file_path(&filename);
-                                 if let Ok(mut reader) = hound::WavReader::open(&meeting_wav_path) {
-                                     let samples: Vec<f32> = reader.samples::<i16>()
-                                        .filter_map(Result::ok)
-                                        .map(|s| s as f32 / i16::MAX as f32)
-                                        .collect();
-                                     
+                                 if let Ok(samples) = history_manager.read_recording_samples(&filename) {
                                      match history_manager.save_transcription(
                                          samples,
                                          full_text,
                                          None,
+                                         None,
                                          None
                                      ).await {
                                          Ok(_) => info!("Successfully saved meeting {} to history", session_id),
                                          Err(e) => error!("Failed to save meeting to history: {}", e),
                                      }
                                  } else {
-                                     error!("Failed to open WAV file for meeting {}", session_id);
+                                     error!("Failed to read the recording of meeting {}", session_id);
                                  }
                              }
                          }
@@ -264,8 +266,8 @@ impl MeetingManager {
         Ok(())
     }
 
-    pub fn get_meetings(&self) -> Result<Vec<crate::managers::history::Meeting>> {
-        self.history_manager.get_meetings()
+    pub fn get_meetings(&self, tags: &[String]) -> Result<Vec<crate::managers::history::Meeting>> {
+        self.history_manager.get_meetings(tags)
     }
 
     pub fn is_meeting_active(&self) -> bool {
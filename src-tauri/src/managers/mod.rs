@@ -1,8 +1,12 @@
 pub mod audio;
+pub mod benchmark;
+pub mod download_queue;
 pub mod history;
 pub mod meetings;
 pub mod model;
+pub mod model_catalog;
 pub mod transcription;
+pub mod whisper_engine;
 pub mod translation;
 pub mod tts;
 pub mod diagnostics;
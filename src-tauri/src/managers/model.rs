@@ -1,20 +1,29 @@
-use crate::settings::{get_settings, write_settings};
+use crate::managers::benchmark::{self, ModelBenchmark, ModelRecommendation};
+use crate::managers::diagnostics::DiagnosticManager;
+use crate::managers::download_queue::{
+    BandwidthLimiter, DownloadQueue, QueuedDownload, QueuedDownloadStatus,
+};
+use crate::managers::model_catalog::{self, CatalogSource};
+use crate::settings::{get_settings, write_settings, AppSettings};
 use anyhow::Result;
 use log::{debug, error, info, trace, warn};
 use flate2::read::GzDecoder;
 use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use specta::Type;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 use tar::Archive;
 use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+use transcribe_rs::engines::moonshine::ModelVariant;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub enum EngineType {
@@ -45,8 +54,386 @@ pub struct ModelInfo {
     pub is_recommended: bool,       // Whether this is the recommended model for new users
     pub supported_languages: Vec<String>, // Languages this model can transcribe
     pub is_custom: bool,            // Whether this is a user-provided custom model
+    /// Expected SHA-256 (hex) of the downloaded file, or of the archive for
+    /// directory-based models. Downloads are verified against it when set.
+    #[serde(default)]
+    pub sha256: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityStatus {
+    /// Matches a digest published in the catalog
+    Verified,
+    /// No digest is published for the model. It may still match the one
+    /// recorded at install time, which only shows it hasn't changed since.
+    Unverified,
+    /// Differs from the published or recorded digest
+    Corrupt,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ModelVerification {
+    pub model_id: String,
+    pub status: IntegrityStatus,
+    pub expected: Option<String>,
+    pub actual: String,
+    /// Set when a corrupt model is being downloaded again
+    pub redownloading: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Imported,
+    /// The model was already installed and was left untouched
+    AlreadyInstalled,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct ModelImport {
+    pub source: String,
+    pub model_id: Option<String>,
+    pub status: ImportStatus,
+    pub error: Option<String>,
+}
+
+/// A download finished but its digest doesn't match `ModelInfo::sha256`.
+#[derive(Debug)]
+pub struct ChecksumMismatch {
+    pub model_id: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl std::fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Checksum mismatch for {}: expected {}, got {}",
+            self.model_id, self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// Share of the catalog's `size_mb` a download must reach to count as complete
+/// when no digest is published. `size_mb` is rounded, so this leaves slack.
+const MIN_DOWNLOAD_SIZE_RATIO: f64 = 0.8;
+
+/// Whether a download of `actual_bytes` falls well short of the catalog size.
+fn looks_truncated(size_mb: u64, actual_bytes: u64) -> bool {
+    (actual_bytes as f64) < (size_mb * 1_000_000) as f64 * MIN_DOWNLOAD_SIZE_RATIO
+}
+
+/// Feeds everything `reader` yields into `hasher`.
+fn hash_reader(mut reader: impl Read, hasher: &mut Sha256) -> std::io::Result<()> {
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = reader.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// SHA-256 of a file as lowercase hex.
+pub fn sha256_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hash_reader(File::open(path)?, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// SHA-256 over every file in a directory tree, in path order, covering both
+/// relative paths and contents so renames and missing files are caught too.
+pub fn sha256_dir(path: &Path) -> Result<String> {
+    fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                collect(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    collect(path, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(path).unwrap_or(&file);
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0u8]);
+        hash_reader(File::open(&file)?, &mut hasher)?;
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Display name for a custom model: `-` and `_` become spaces and words are capitalized.
+fn custom_display_name(model_id: &str) -> String {
+    model_id
+        .replace(['-', '_'], " ")
+        .split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Optional `model.json` inside a custom model directory. Every field is
+/// optional; anything missing falls back to what discovery can infer.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CustomModelMetadata {
+    name: Option<String>,
+    description: Option<String>,
+    engine_type: Option<EngineType>,
+    supported_languages: Vec<String>,
+    supports_translation: bool,
+    accuracy_score: Option<f32>,
+    speed_score: Option<f32>,
+    /// Moonshine variant, e.g. `tiny` or `base-es`
+    variant: Option<String>,
+}
+
+const CUSTOM_MODEL_METADATA: &str = "model.json";
+
+fn read_custom_metadata(path: &Path) -> Result<CustomModelMetadata> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn parse_moonshine_variant(name: &str) -> Option<ModelVariant> {
+    Some(match name.to_ascii_lowercase().replace('_', "-").as_str() {
+        "tiny" => ModelVariant::Tiny,
+        "tiny-ar" => ModelVariant::TinyAr,
+        "tiny-zh" => ModelVariant::TinyZh,
+        "tiny-ja" => ModelVariant::TinyJa,
+        "tiny-ko" => ModelVariant::TinyKo,
+        "tiny-uk" => ModelVariant::TinyUk,
+        "base" => ModelVariant::Base,
+        "base-es" => ModelVariant::BaseEs,
+        _ => return None,
+    })
+}
+
+/// Which Moonshine variant a model directory holds: the `variant` from its
+/// `model.json`, otherwise Tiny or Base going by the decoder's layer count.
+/// The language-specific variants can only be told apart by metadata.
+pub fn moonshine_variant(dir: &Path) -> ModelVariant {
+    let declared = read_custom_metadata(&dir.join(CUSTOM_MODEL_METADATA))
+        .ok()
+        .and_then(|metadata| metadata.variant);
+    if let Some(name) = declared {
+        match parse_moonshine_variant(&name) {
+            Some(variant) => return variant,
+            None => warn!("Unknown Moonshine variant '{}' in {:?}", name, dir),
+        }
+    }
+
+    // Tiny has 6 decoder layers and Base 8, each with its own cache inputs
+    let decoder = dir.join("decoder_model_merged.onnx");
+    match file_contains(&decoder, b"past_key_values.7.") {
+        Ok(true) => ModelVariant::Base,
+        Ok(false) => ModelVariant::Tiny,
+        Err(e) => {
+            warn!(
+                "Failed to inspect {:?}, assuming Moonshine Base: {}",
+                decoder, e
+            );
+            ModelVariant::Base
+        }
+    }
+}
+
+fn file_contains(path: &Path, needle: &[u8]) -> std::io::Result<bool> {
+    let mut file = File::open(path)?;
+    let mut buf = vec![0u8; 1024 * 1024];
+    // Bytes kept from the previous read so a match can straddle reads
+    let mut carried = 0;
+    loop {
+        let read = file.read(&mut buf[carried..])?;
+        if read == 0 {
+            return Ok(false);
+        }
+        let filled = carried + read;
+        if buf[..filled].windows(needle.len()).any(|w| w == needle) {
+            return Ok(true);
+        }
+        carried = (needle.len() - 1).min(filled);
+        buf.copy_within(filled - carried..filled, 0);
+    }
+}
+
+/// Works out which engine a model directory is for from the files it contains,
+/// matching the layouts the engines load: Parakeet and SenseVoice are loaded
+/// as int8 exports and Moonshine as the merged-decoder export.
+fn detect_engine_type(dir: &Path) -> Option<EngineType> {
+    let files: HashSet<String> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .collect();
+    let has = |name: &str| files.contains(name);
+
+    if has("encoder-model.int8.onnx")
+        && has("decoder_joint-model.int8.onnx")
+        && has("nemo128.onnx")
+        && has("vocab.txt")
+    {
+        Some(EngineType::Parakeet)
+    } else if has("encoder_model.onnx")
+        && has("decoder_model_merged.onnx")
+        && has("tokenizer.json")
+    {
+        Some(EngineType::Moonshine)
+    } else if has("model.int8.onnx") && has("tokens.txt") {
+        Some(EngineType::SenseVoice)
+    } else if has("config.json") && has("model.pth") && has("vocab.json") {
+        Some(EngineType::XTTS)
+    } else if files
+        .iter()
+        .any(|f| f.ends_with(".onnx") && has(&format!("{}.json", f)))
+    {
+        // Piper voices ship as `<voice>.onnx` next to `<voice>.onnx.json`
+        Some(EngineType::Piper)
+    } else {
+        None
+    }
+}
+
+/// Whether `path` looks like a gzipped tarball, the format model archives ship in.
+fn is_model_archive(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(archive_extension_len)
+        .is_some()
+}
+
+/// Length of the archive extension `name` ends with, matched in any case.
+fn archive_extension_len(name: &str) -> Option<usize> {
+    let lower = name.to_ascii_lowercase();
+    [".tar.gz", ".tgz"]
+        .into_iter()
+        .find(|ext| lower.ends_with(ext))
+        .map(str::len)
+}
+
+/// `name` without its `.bin` extension, matched in any case like archives are.
+fn bin_stem(name: &str) -> Option<&str> {
+    let len = name.len().checked_sub(".bin".len())?;
+    // Cuts on a character boundary only if the tail is ASCII, which `.bin` is
+    let (stem, ext) = (name.get(..len)?, name.get(len..)?);
+    (ext.eq_ignore_ascii_case(".bin") && !stem.is_empty()).then_some(stem)
+}
+
+/// File name of `path` with any archive extension removed.
+fn import_stem(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    // The extension is ASCII, so this cuts on a character boundary
+    let stem = match archive_extension_len(name) {
+        Some(len) => &name[..name.len() - len],
+        None => name,
+    };
+    (!stem.is_empty()).then(|| stem.to_string())
+}
+
+/// Unpacks a `.tar.gz` model archive into `models_dir/filename` through a
+/// temporary `.extracting` directory. An archive holding a single directory
+/// has that directory's contents installed, not the directory itself.
+fn unpack_model_archive(archive: &Path, models_dir: &Path, filename: &str) -> Result<PathBuf> {
+    let temp_extract_dir = models_dir.join(format!("{}.extracting", filename));
+    let final_model_dir = models_dir.join(filename);
+
+    // Clean up any previous incomplete extraction
+    if temp_extract_dir.exists() {
+        let _ = fs::remove_dir_all(&temp_extract_dir);
+    }
+    fs::create_dir_all(&temp_extract_dir)?;
+
+    let mut tar = Archive::new(GzDecoder::new(File::open(archive)?));
+    if let Err(e) = tar.unpack(&temp_extract_dir) {
+        let _ = fs::remove_dir_all(&temp_extract_dir);
+        return Err(anyhow::anyhow!("Failed to extract archive: {}", e));
+    }
+
+    // Find the actual extracted directory (archive might have a nested structure)
+    let extracted_dirs: Vec<_> = fs::read_dir(&temp_extract_dir)?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false))
+        .collect();
+
+    if final_model_dir.exists() {
+        fs::remove_dir_all(&final_model_dir)?;
+    }
+    if extracted_dirs.len() == 1 {
+        // Single directory extracted, move it to the final location
+        fs::rename(extracted_dirs[0].path(), &final_model_dir)?;
+        let _ = fs::remove_dir_all(&temp_extract_dir);
+    } else {
+        // Multiple items or no directories, rename the temp directory itself
+        fs::rename(&temp_extract_dir, &final_model_dir)?;
+    }
+    Ok(final_model_dir)
+}
+
+fn copy_dir_all(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_all(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Total size of the files under `dir`, in bytes.
+fn dir_size(dir: &Path) -> u64 {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .map(|e| {
+            let path = e.path();
+            if path.is_dir() {
+                dir_size(&path)
+            } else {
+                e.metadata().map(|m| m.len()).unwrap_or(0)
+            }
+        })
+        .sum()
+}
+
+/// Disk usage of the models directory.
+#[derive(Debug, Clone, Default, Serialize, Type)]
+pub struct ModelStorage {
+    pub installed_bytes: u64,
+    /// `.partial` files from unfinished downloads and imports
+    pub partial_download_bytes: u64,
+    /// `.extracting` directories left behind by interrupted extractions
+    pub extraction_leftover_bytes: u64,
+}
+
+/// Leftovers touched more recently than this may belong to an import that is
+/// still copying, so cleanup leaves them alone.
+const STALE_LEFTOVER_AGE: Duration = Duration::from_secs(10 * 60);
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct DownloadProgress {
     pub model_id: String,
@@ -55,12 +442,24 @@ pub struct DownloadProgress {
     pub percentage: f64,
 }
 
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DownloadQueueState {
+    pub max_concurrent_downloads: usize,
+    pub bandwidth_limit_kib_per_sec: Option<u64>,
+    pub downloads: Vec<QueuedDownload>,
+}
+
 pub struct ModelManager {
     app_handle: AppHandle,
     models_dir: PathBuf,
     available_models: Mutex<HashMap<String, ModelInfo>>,
     cancel_flags: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     extracting_models: Arc<Mutex<HashSet<String>>>,
+    download_queue: Mutex<DownloadQueue>,
+    /// Wakes downloads waiting for a slot whenever the queue changes
+    queue_notify: Notify,
+    bandwidth_limiter: BandwidthLimiter,
+    benchmarks: Mutex<HashMap<String, ModelBenchmark>>,
 }
 
 impl ModelManager {
@@ -76,362 +475,28 @@ impl ModelManager {
             fs::create_dir_all(&models_dir)?;
         }
 
-        let mut available_models = HashMap::new();
-
-        // Whisper supported languages (99 languages from tokenizer)
-        // Including zh-Hans and zh-Hant variants to match frontend language codes
-        let whisper_languages: Vec<String> = vec![
-            "en", "zh", "zh-Hans", "zh-Hant", "de", "es", "ru", "ko", "fr", "ja", "pt", "tr", "pl",
-            "ca", "nl", "ar", "sv", "it", "id", "hi", "fi", "vi", "he", "uk", "el", "ms", "cs",
-            "ro", "da", "hu", "ta", "no", "th", "ur", "hr", "bg", "lt", "la", "mi", "ml", "cy",
-            "sk", "te", "fa", "lv", "bn", "sr", "az", "sl", "kn", "et", "mk", "br", "eu", "is",
-            "hy", "ne", "mn", "bs", "kk", "sq", "sw", "gl", "mr", "pa", "si", "km", "sn", "yo",
-            "so", "af", "oc", "ka", "be", "tg", "sd", "gu", "am", "yi", "lo", "uz", "fo", "ht",
-            "ps", "tk", "nn", "mt", "sa", "lb", "my", "bo", "tl", "mg", "as", "tt", "haw", "ln",
-            "ha", "ba", "jw", "su", "yue",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
-
-        // TODO this should be read from a JSON file or something..
-        available_models.insert(
-            "small".to_string(),
-            ModelInfo {
-                id: "small".to_string(),
-                name: "Whisper Small".to_string(),
-                description: "Fast and fairly accurate.".to_string(),
-                filename: "ggml-small.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-small.bin".to_string()),
-                size_mb: 487,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.60,
-                speed_score: 0.85,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        // Add downloadable models
-        available_models.insert(
-            "medium".to_string(),
-            ModelInfo {
-                id: "medium".to_string(),
-                name: "Whisper Medium".to_string(),
-                description: "Good accuracy, medium speed".to_string(),
-                filename: "whisper-medium-q4_1.bin".to_string(),
-                url: Some("https://blob.handy.computer/whisper-medium-q4_1.bin".to_string()),
-                size_mb: 492, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.75,
-                speed_score: 0.60,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "turbo".to_string(),
-            ModelInfo {
-                id: "turbo".to_string(),
-                name: "Whisper Turbo".to_string(),
-                description: "Balanced accuracy and speed.".to_string(),
-                filename: "ggml-large-v3-turbo.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-turbo.bin".to_string()),
-                size_mb: 1600, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.80,
-                speed_score: 0.40,
-                supports_translation: false, // Turbo doesn't support translation
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "large".to_string(),
-            ModelInfo {
-                id: "large".to_string(),
-                name: "Whisper Large".to_string(),
-                description: "Good accuracy, but slow.".to_string(),
-                filename: "ggml-large-v3-q5_0.bin".to_string(),
-                url: Some("https://blob.handy.computer/ggml-large-v3-q5_0.bin".to_string()),
-                size_mb: 1100, // Approximate size
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.30,
-                supports_translation: true,
-                is_recommended: false,
-                supported_languages: whisper_languages.clone(),
-                is_custom: false,
-            },
-        );
+        let catalog = Self::load_catalog(app_handle);
+        let mut available_models: HashMap<String, ModelInfo> =
+            catalog.into_iter().map(|m| (m.id.clone(), m)).collect();
 
-        available_models.insert(
-            "breeze-asr".to_string(),
-            ModelInfo {
-                id: "breeze-asr".to_string(),
-                name: "Breeze ASR".to_string(),
-                description: "Optimized for Taiwanese Mandarin. Code-switching support."
-                    .to_string(),
-                filename: "breeze-asr-q5_k.bin".to_string(),
-                url: Some("https://blob.handy.computer/breeze-asr-q5_k.bin".to_string()),
-                size_mb: 1080,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Whisper,
-                accuracy_score: 0.85,
-                speed_score: 0.35,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: whisper_languages,
-                is_custom: false,
-            },
-        );
-
-        // Add NVIDIA Parakeet models (directory-based)
-        available_models.insert(
-            "parakeet-tdt-0.6b-v2".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v2".to_string(),
-                name: "Parakeet V2".to_string(),
-                description: "English only. The best model for English speakers.".to_string(),
-                filename: "parakeet-tdt-0.6b-v2-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v2-int8.tar.gz".to_string()),
-                size_mb: 473, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.85,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        // Parakeet V3 supported languages (25 EU languages + Russian/Ukrainian):
-        // bg, hr, cs, da, nl, en, et, fi, fr, de, el, hu, it, lv, lt, mt, pl, pt, ro, sk, sl, es, sv, ru, uk
-        let parakeet_v3_languages: Vec<String> = vec![
-            "bg", "hr", "cs", "da", "nl", "en", "et", "fi", "fr", "de", "el", "hu", "it", "lv",
-            "lt", "mt", "pl", "pt", "ro", "sk", "sl", "es", "sv", "ru", "uk",
-        ]
-        .into_iter()
-        .map(String::from)
-        .collect();
-
-        available_models.insert(
-            "parakeet-tdt-0.6b-v3".to_string(),
-            ModelInfo {
-                id: "parakeet-tdt-0.6b-v3".to_string(),
-                name: "Parakeet V3".to_string(),
-                description: "Fast and accurate. Supports 25 European languages.".to_string(),
-                filename: "parakeet-tdt-0.6b-v3-int8".to_string(), // Directory name
-                url: Some("https://blob.handy.computer/parakeet-v3-int8.tar.gz".to_string()),
-                size_mb: 478, // Approximate size for int8 quantized model
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Parakeet,
-                accuracy_score: 0.80,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: true,
-                supported_languages: parakeet_v3_languages,
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "moonshine-base".to_string(),
-            ModelInfo {
-                id: "moonshine-base".to_string(),
-                name: "Moonshine Base".to_string(),
-                description: "Very fast, English only. Handles accents well.".to_string(),
-                filename: "moonshine-base".to_string(),
-                url: Some("https://blob.handy.computer/moonshine-base.tar.gz".to_string()),
-                size_mb: 58,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::Moonshine,
-                accuracy_score: 0.70,
-                speed_score: 0.90,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        // SenseVoice supported languages
-        let sense_voice_languages: Vec<String> =
-            vec!["zh", "zh-Hans", "zh-Hant", "en", "yue", "ja", "ko"]
-                .into_iter()
-                .map(String::from)
-                .collect();
-
-        available_models.insert(
-            "sense-voice-int8".to_string(),
-            ModelInfo {
-                id: "sense-voice-int8".to_string(),
-                name: "SenseVoice".to_string(),
-                description: "Very fast. Chinese, English, Japanese, Korean, Cantonese."
-                    .to_string(),
-                filename: "sense-voice-int8".to_string(),
-                url: Some("https://blob.handy.computer/sense-voice-int8.tar.gz".to_string()),
-                size_mb: 160,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::SenseVoice,
-                accuracy_score: 0.65,
-                speed_score: 0.95,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: sense_voice_languages,
-                is_custom: false,
-            },
-        );
-
-        // --- TTS MODELS ---
-
-        available_models.insert(
-            "en_US-joy-medium".to_string(),
-            ModelInfo {
-                id: "en_US-joy-medium".to_string(),
-                name: "Speech: Piper Joy (EN)".to_string(),
-                description: "Lightweight neural TTS. High quality, very fast.".to_string(),
-                filename: "en_US-joy-medium.onnx".to_string(),
-                url: Some("https://huggingface.co/rhasspy/piper-voices/resolve/main/en/en_US/joy/medium/en_US-joy-medium.onnx".to_string()),
-                size_mb: 50,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Piper,
-                accuracy_score: 0.85,
-                speed_score: 0.90,
-                supports_translation: false,
-                is_recommended: true,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "en_US-lessac-high".to_string(),
-            ModelInfo {
-                id: "en_US-lessac-high".to_string(),
-                name: "Speech: Piper Lessac (EN-Studio)".to_string(),
-                description: "Professional studio-quality English voice. Clear and expressive.".to_string(),
-                filename: "en_US-lessac-high.onnx".to_string(),
-                url: Some("https://huggingface.co/rhasspy/piper-voices/resolve/main/en/en_US/lessac/high/en_US-lessac-high.onnx".to_string()),
-                size_mb: 85,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Piper,
-                accuracy_score: 0.92,
-                speed_score: 0.80,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "en_US-arctic-medium".to_string(),
-            ModelInfo {
-                id: "en_US-arctic-medium".to_string(),
-                name: "Speech: Piper Arctic (Natural)".to_string(),
-                description: "Natural sounding English male voice. Great for long-form content.".to_string(),
-                filename: "en_US-arctic-medium.onnx".to_string(),
-                url: Some("https://huggingface.co/rhasspy/piper-voices/resolve/main/en/en_US/arctic/medium/en_US-arctic-medium.onnx".to_string()),
-                size_mb: 55,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: false,
-                engine_type: EngineType::Piper,
-                accuracy_score: 0.88,
-                speed_score: 0.85,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en".to_string()],
-                is_custom: false,
-            },
-        );
-
-        available_models.insert(
-            "xtts-v2".to_string(),
-            ModelInfo {
-                id: "xtts-v2".to_string(),
-                name: "Speech: XTTS v2 (Cloning)".to_string(),
-                description: "Pro Level. Supports Voice Cloning and 16+ languages.".to_string(),
-                filename: "xtts-v2".to_string(),
-                url: Some("https://blob.handy.computer/xtts-v2.tar.gz".to_string()),
-                size_mb: 2048,
-                is_downloaded: false,
-                is_downloading: false,
-                partial_size: 0,
-                is_directory: true,
-                engine_type: EngineType::XTTS,
-                accuracy_score: 0.95,
-                speed_score: 0.20,
-                supports_translation: false,
-                is_recommended: false,
-                supported_languages: vec!["en", "de", "fr", "es", "it", "pl", "pt", "tr", "ru", "nl", "cs", "ar", "zh", "hu", "ko", "ja"]
-                    .into_iter()
-                    .map(String::from)
-                    .collect(),
-                is_custom: false,
-            },
-        );
-
-        // Auto-discover custom Whisper models (.bin files) in the models directory
-        if let Err(e) = Self::discover_custom_whisper_models(&models_dir, &mut available_models) {
+        // Auto-discover custom models (Whisper .bin files and model directories)
+        if let Err(e) = Self::discover_custom_models(&models_dir, &mut available_models) {
             warn!("Failed to discover custom models: {}", e);
         }
 
+        let settings = get_settings(app_handle);
         let manager = Self {
             app_handle: app_handle.clone(),
             models_dir,
             available_models: Mutex::new(available_models),
             cancel_flags: Arc::new(Mutex::new(HashMap::new())),
             extracting_models: Arc::new(Mutex::new(HashSet::new())),
+            download_queue: Mutex::new(DownloadQueue::default()),
+            queue_notify: Notify::new(),
+            bandwidth_limiter: BandwidthLimiter::new(settings.download_bandwidth_limit_kib),
+            benchmarks: Mutex::new(HashMap::new()),
         };
+        *manager.benchmarks.lock().unwrap() = benchmark::load_results(&manager.benchmarks_path());
 
         // Migrate any bundled models to user directory
         manager.migrate_bundled_models()?;
@@ -442,6 +507,9 @@ impl ModelManager {
         // Auto-select a model if none is currently selected
         manager.auto_select_model_if_needed()?;
 
+        // Bring back downloads that were queued or paused before the last exit
+        manager.restore_download_queue();
+
         Ok(manager)
     }
 
@@ -455,6 +523,87 @@ impl ModelManager {
         models.get(model_id).cloned()
     }
 
+    /// Last validated copy of the remote catalog, so startup doesn't need the network
+    fn catalog_cache_path(app_handle: &AppHandle) -> Option<PathBuf> {
+        app_handle
+            .path()
+            .app_data_dir()
+            .ok()
+            .map(|dir| dir.join("model_catalog.json"))
+    }
+
+    /// Loads the catalog for the configured source without touching the network.
+    /// Remote sources use the cached copy; anything that fails to load falls back
+    /// to the bundled catalog.
+    fn load_catalog(app_handle: &AppHandle) -> Vec<ModelInfo> {
+        let settings = get_settings(app_handle);
+        let loaded = match CatalogSource::from_setting(settings.model_catalog_source.as_deref()) {
+            CatalogSource::Bundled => return model_catalog::bundled(),
+            CatalogSource::File(path) => model_catalog::load_file(Path::new(&path)),
+            CatalogSource::Remote(_) => match Self::catalog_cache_path(app_handle) {
+                Some(cache) if cache.exists() => model_catalog::load_cached(&cache),
+                _ => return model_catalog::bundled(),
+            },
+        };
+
+        loaded.unwrap_or_else(|e| {
+            warn!("Falling back to bundled model catalog: {}", e);
+            model_catalog::bundled()
+        })
+    }
+
+    /// Reloads the catalog from the configured source, fetching it first when the
+    /// source is a URL. Unlike startup, errors with an override are returned
+    /// instead of falling back so the caller can report it. The published
+    /// manifest is best-effort: when it can't be fetched, the cached or bundled
+    /// catalog stays in use.
+    pub async fn refresh_catalog(&self) -> Result<()> {
+        let settings = get_settings(&self.app_handle);
+        let source = CatalogSource::from_setting(settings.model_catalog_source.as_deref());
+        let catalog = match &source {
+            CatalogSource::Bundled => model_catalog::bundled(),
+            CatalogSource::File(path) => model_catalog::load_file(Path::new(path))?,
+            CatalogSource::Remote(url) => match self.fetch_catalog(url, &settings).await {
+                Ok(catalog) => catalog,
+                Err(e) if source.is_published() => {
+                    warn!("Keeping the cached or bundled model catalog: {}", e);
+                    Self::load_catalog(&self.app_handle)
+                }
+                Err(e) => return Err(e),
+            },
+        };
+
+        info!("Loaded model catalog with {} models", catalog.len());
+        self.apply_catalog(catalog)
+    }
+
+    /// Fetches and validates a remote catalog, caching it for the next start.
+    async fn fetch_catalog(&self, url: &str, settings: &AppSettings) -> Result<Vec<ModelInfo>> {
+        let json = model_catalog::fetch(url, &settings.network).await?;
+        let catalog = model_catalog::parse_remote(&json)?;
+        if let Some(cache) = Self::catalog_cache_path(&self.app_handle) {
+            if let Err(e) = fs::write(&cache, &json) {
+                warn!("Failed to cache model catalog: {}", e);
+            }
+        }
+        Ok(catalog)
+    }
+
+    fn apply_catalog(&self, catalog: Vec<ModelInfo>) -> Result<()> {
+        {
+            let mut models = self.available_models.lock().unwrap();
+            let mut merged = model_catalog::merge(catalog, &models);
+            if let Err(e) = Self::discover_custom_models(&self.models_dir, &mut merged) {
+                warn!("Failed to discover custom models: {}", e);
+            }
+            *models = merged;
+        }
+
+        self.update_download_status()?;
+        let _ = self.app_handle.emit("model-catalog-updated", ());
+        Ok(())
+    }
+
     fn migrate_bundled_models(&self) -> Result<()> {
         // Check for bundled models and copy them to user directory
         let bundled_models = ["ggml-small.bin"]; // Add other bundled models here if any
@@ -560,11 +709,24 @@ impl ModelManager {
             }
         }
 
-        // If no model is selected, pick the first downloaded one
+        // If no model is selected, pick the best downloaded one for this machine,
+        // falling back to the first downloaded one
         if settings.selected_model.is_empty() {
-            // Find the first available (downloaded) model
+            let memory_total_gb = self
+                .app_handle
+                .try_state::<Arc<DiagnosticManager>>()
+                .map(|diagnostics| diagnostics.get_system_info().memory_total_gb);
+            let recommended = self
+                .recommendations(memory_total_gb, true)
+                .into_iter()
+                .find(|r| r.recommended)
+                .map(|r| r.model_id);
+
             let models = self.available_models.lock().unwrap();
-            if let Some(available_model) = models.values().find(|model| model.is_downloaded) {
+            let best = recommended.and_then(|id| models.get(&id));
+            if let Some(available_model) =
+                best.or_else(|| models.values().find(|model| model.is_downloaded))
+            {
                 info!(
                     "Auto-selecting model: {} ({})",
                     available_model.id, available_model.name
@@ -582,6 +744,132 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Discover user-provided models in the models directory: loose Whisper
+    /// `.bin` files and model directories for the other engines.
+    fn discover_custom_models(
+        models_dir: &Path,
+        available_models: &mut HashMap<String, ModelInfo>,
+    ) -> Result<()> {
+        Self::discover_custom_whisper_models(models_dir, available_models)?;
+        Self::discover_custom_model_dirs(models_dir, available_models)
+    }
+
+    /// Discover custom directory-based models, detecting the engine from the
+    /// file layout. A `model.json` inside the directory can override the engine
+    /// and supply the name, languages and translation support.
+    /// Skips directories that belong to predefined models.
+    fn discover_custom_model_dirs(
+        models_dir: &Path,
+        available_models: &mut HashMap<String, ModelInfo>,
+    ) -> Result<()> {
+        if !models_dir.exists() {
+            return Ok(());
+        }
+
+        let predefined_filenames: HashSet<String> = available_models
+            .values()
+            .filter(|m| !m.is_custom)
+            .map(|m| m.filename.clone())
+            .collect();
+
+        for entry in fs::read_dir(models_dir)? {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(e) => {
+                    warn!("Failed to read directory entry: {}", e);
+                    continue;
+                }
+            };
+
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            let dirname = match path.file_name().and_then(|s| s.to_str()) {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+
+            // Skip hidden directories and in-progress downloads/extractions
+            if dirname.starts_with('.')
+                || dirname.ends_with(".partial")
+                || dirname.ends_with(".extracting")
+                || predefined_filenames.contains(&dirname)
+                || available_models.contains_key(&dirname)
+            {
+                continue;
+            }
+
+            let metadata_path = path.join(CUSTOM_MODEL_METADATA);
+            let metadata = if metadata_path.exists() {
+                match read_custom_metadata(&metadata_path) {
+                    Ok(metadata) => metadata,
+                    Err(e) => {
+                        warn!("Ignoring invalid {:?}: {}", metadata_path, e);
+                        CustomModelMetadata::default()
+                    }
+                }
+            } else {
+                CustomModelMetadata::default()
+            };
+
+            let engine_type = match metadata.engine_type.or_else(|| detect_engine_type(&path)) {
+                Some(engine_type) => engine_type,
+                None => {
+                    debug!("No known model layout in {:?}, skipping", path);
+                    continue;
+                }
+            };
+
+            // Whisper loads a single GGML file, which discovery picks up as a loose .bin
+            if matches!(engine_type, EngineType::Whisper) {
+                warn!(
+                    "Whisper models must be placed as .bin files, not directories: {:?}",
+                    path
+                );
+                continue;
+            }
+
+            let size_mb = dir_size(&path) / (1024 * 1024);
+
+            info!(
+                "Discovered custom {:?} model: {} ({} MB)",
+                engine_type, dirname, size_mb
+            );
+
+            available_models.insert(
+                dirname.clone(),
+                ModelInfo {
+                    id: dirname.clone(),
+                    name: metadata
+                        .name
+                        .unwrap_or_else(|| custom_display_name(&dirname)),
+                    description: metadata
+                        .description
+                        .unwrap_or_else(|| "Not officially supported".to_string()),
+                    filename: dirname,
+                    url: None,
+                    size_mb,
+                    is_downloaded: true,
+                    is_downloading: false,
+                    partial_size: 0,
+                    is_directory: true,
+                    engine_type,
+                    accuracy_score: metadata.accuracy_score.unwrap_or(0.0).clamp(0.0, 1.0),
+                    speed_score: metadata.speed_score.unwrap_or(0.0).clamp(0.0, 1.0),
+                    supports_translation: metadata.supports_translation,
+                    is_recommended: false,
+                    supported_languages: metadata.supported_languages,
+                    is_custom: true,
+                    sha256: None,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
     /// Discover custom Whisper models (.bin files) in the models directory.
     /// Skips files that match predefined model filenames.
     fn discover_custom_whisper_models(
@@ -646,19 +934,7 @@ impl ModelManager {
                 continue;
             }
 
-            // Generate display name: replace - and _ with space, capitalize words
-            let display_name = model_id
-                .replace(['-', '_'], " ")
-                .split_whitespace()
-                .map(|word| {
-                    let mut chars = word.chars();
-                    match chars.next() {
-                        None => String::new(),
-                        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(" ");
+            let display_name = custom_display_name(&model_id);
 
             // Get file size in MB
             let size_mb = match path.metadata() {
@@ -694,6 +970,7 @@ impl ModelManager {
                     is_recommended: false,
                     supported_languages: vec![],
                     is_custom: true,
+                    sha256: None,
                 },
             );
         }
@@ -701,7 +978,318 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Digest recorded when a model was installed, next to the model itself
+    fn digest_record_path(&self, filename: &str) -> PathBuf {
+        self.models_dir.join(format!("{}.sha256", filename))
+    }
+
+    fn record_digest(&self, filename: &str, digest: &str) {
+        if let Err(e) = fs::write(self.digest_record_path(filename), digest) {
+            warn!("Failed to record digest for {}: {}", filename, e);
+        }
+    }
+
+    fn recorded_digest(&self, filename: &str) -> Option<String> {
+        fs::read_to_string(self.digest_record_path(filename))
+            .ok()
+            .map(|d| d.trim().to_lowercase())
+            .filter(|d| !d.is_empty())
+    }
+
+    /// Checks an installed model against its expected or recorded digest.
+    pub fn verify_model(&self, model_id: &str) -> Result<ModelVerification> {
+        let model_info = self
+            .get_model_info(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Model not found: {}", model_id))?;
+        if !model_info.is_downloaded || model_info.is_downloading {
+            return Err(anyhow::anyhow!("Model not installed: {}", model_id));
+        }
+
+        let model_path = self.models_dir.join(&model_info.filename);
+        let actual = if model_info.is_directory {
+            sha256_dir(&model_path)?
+        } else {
+            sha256_file(&model_path)?
+        };
+
+        // The catalog digest of a directory model describes its archive,
+        // which is gone after extraction. Its tree was recorded once the
+        // archive matched, so the recorded digest stands in for it.
+        let published = model_info.sha256.is_some();
+        let expected = match (&model_info.sha256, model_info.is_directory) {
+            (Some(digest), false) => Some(digest.to_lowercase()),
+            _ => self.recorded_digest(&model_info.filename),
+        };
+        let status = match &expected {
+            Some(expected) if *expected != actual => IntegrityStatus::Corrupt,
+            // A digest recorded from the file itself can't vouch for it
+            Some(_) if published => IntegrityStatus::Verified,
+            _ => IntegrityStatus::Unverified,
+        };
+        if status == IntegrityStatus::Corrupt {
+            warn!("Model {} failed verification", model_id);
+        }
+
+        Ok(ModelVerification {
+            model_id: model_id.to_string(),
+            status,
+            expected,
+            actual,
+            redownloading: false,
+        })
+    }
+
+    /// Verifies every installed model, skipping ones that can't be read.
+    pub fn verify_installed_models(&self) -> Vec<ModelVerification> {
+        let installed: Vec<String> = self
+            .get_available_models()
+            .into_iter()
+            .filter(|m| m.is_downloaded && !m.is_downloading)
+            .map(|m| m.id)
+            .collect();
+
+        installed
+            .iter()
+            .filter_map(|id| match self.verify_model(id) {
+                Ok(verification) => Some(verification),
+                Err(e) => {
+                    error!("Failed to verify model {}: {}", id, e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Deletes a corrupt model and downloads it again.
+    pub async fn redownload_model(&self, model_id: &str) -> Result<()> {
+        let has_url = self
+            .get_model_info(model_id)
+            .is_some_and(|m| m.url.is_some());
+        if !has_url {
+            return Err(anyhow::anyhow!(
+                "Model {} has no download URL and can't be re-downloaded",
+                model_id
+            ));
+        }
+
+        info!("Re-downloading model {}", model_id);
+        self.delete_model(model_id)?;
+        self.download_model(model_id).await
+    }
+
+    fn benchmarks_path(&self) -> PathBuf {
+        self.models_dir
+            .parent()
+            .unwrap_or(&self.models_dir)
+            .join("model_benchmarks.json")
+    }
+
+    pub fn get_benchmarks(&self) -> Vec<ModelBenchmark> {
+        let mut results: Vec<ModelBenchmark> =
+            self.benchmarks.lock().unwrap().values().cloned().collect();
+        results.sort_by(|a, b| a.model_id.cmp(&b.model_id));
+        results
+    }
+
+    pub fn get_benchmark(&self, model_id: &str) -> Option<ModelBenchmark> {
+        self.benchmarks.lock().unwrap().get(model_id).cloned()
+    }
+
+    /// Stores new results, replacing earlier ones for the same models.
+    pub fn record_benchmarks(&self, results: Vec<ModelBenchmark>) -> Result<()> {
+        let mut benchmarks = self.benchmarks.lock().unwrap();
+        for result in results {
+            benchmarks.insert(result.model_id.clone(), result);
+        }
+        benchmark::save_results(&self.benchmarks_path(), &benchmarks)
+    }
+
+    /// Recommendations from benchmark results and total RAM, for every
+    /// speech-to-text model or only the downloaded ones.
+    pub fn recommendations(
+        &self,
+        memory_total_gb: Option<f64>,
+        downloaded_only: bool,
+    ) -> Vec<ModelRecommendation> {
+        let models: HashMap<String, ModelInfo> = self
+            .available_models
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, m)| !downloaded_only || m.is_downloaded)
+            .map(|(id, m)| (id.clone(), m.clone()))
+            .collect();
+        let benchmarks = self.benchmarks.lock().unwrap();
+        benchmark::recommend(&models, &benchmarks, memory_total_gb)
+    }
+
+    fn download_queue_path(&self) -> PathBuf {
+        self.models_dir
+            .parent()
+            .unwrap_or(&self.models_dir)
+            .join("download_queue.json")
+    }
+
+    fn restore_download_queue(&self) {
+        let path = self.download_queue_path();
+        let entries: Vec<QueuedDownload> = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Ignoring invalid download queue: {}", e);
+                Vec::new()
+            }),
+            Err(_) => return,
+        };
+
+        // Drop entries for models that are gone or finished in the meantime
+        let entries = {
+            let models = self.available_models.lock().unwrap();
+            entries
+                .into_iter()
+                .filter(|e| {
+                    models
+                        .get(&e.model_id)
+                        .is_some_and(|m| !m.is_downloaded && m.url.is_some())
+                })
+                .collect()
+        };
+
+        *self.download_queue.lock().unwrap() = DownloadQueue::restore(entries);
+        self.save_download_queue();
+    }
+
+    fn save_download_queue(&self) {
+        let entries = self.download_queue.lock().unwrap().entries().to_vec();
+        let result = serde_json::to_string_pretty(&entries)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(fs::write(self.download_queue_path(), json)?));
+        if let Err(e) = result {
+            warn!("Failed to save download queue: {}", e);
+        }
+    }
+
+    pub fn get_download_queue(&self) -> DownloadQueueState {
+        let settings = get_settings(&self.app_handle);
+        DownloadQueueState {
+            max_concurrent_downloads: settings.max_concurrent_downloads,
+            bandwidth_limit_kib_per_sec: settings.download_bandwidth_limit_kib,
+            downloads: self.download_queue.lock().unwrap().entries().to_vec(),
+        }
+    }
+
+    /// Persists the queue, tells the UI and wakes downloads waiting for a slot.
+    /// Also call this after changing the concurrency limit.
+    pub fn download_queue_changed(&self) {
+        self.save_download_queue();
+        let _ = self
+            .app_handle
+            .emit("download-queue-changed", self.get_download_queue());
+        self.queue_notify.notify_waiters();
+    }
+
+    pub fn set_bandwidth_limit(&self, kib_per_sec: Option<u64>) {
+        self.bandwidth_limiter.set_limit(kib_per_sec);
+        let _ = self
+            .app_handle
+            .emit("download-queue-changed", self.get_download_queue());
+    }
+
+    /// Downloads that should be running but have no task yet, e.g. after a
+    /// restart. Paused downloads stay paused until the user resumes them.
+    pub fn pending_downloads(&self) -> Vec<String> {
+        self.download_queue
+            .lock()
+            .unwrap()
+            .entries()
+            .iter()
+            .filter(|e| e.status == QueuedDownloadStatus::Queued)
+            .map(|e| e.model_id.clone())
+            .collect()
+    }
+
+    /// Queues a download (or resumes a paused one) and waits for it to finish.
+    /// Downloads start in queue order, at most `max_concurrent_downloads` at a
+    /// time. Returns early without an error if the download is paused or
+    /// cancelled before it completes.
     pub async fn download_model(&self, model_id: &str) -> Result<()> {
+        if self.get_model_info(model_id).is_none() {
+            return Err(anyhow::anyhow!("Model not found: {}", model_id));
+        }
+
+        self.download_queue.lock().unwrap().enqueue(model_id);
+        self.download_queue_changed();
+
+        loop {
+            // Register for wake-ups before checking so a change in between isn't missed
+            let notified = self.queue_notify.notified();
+            {
+                let max_concurrent = get_settings(&self.app_handle).max_concurrent_downloads;
+                let mut queue = self.download_queue.lock().unwrap();
+                match queue.status(model_id) {
+                    None | Some(QueuedDownloadStatus::Paused) => return Ok(()),
+                    Some(QueuedDownloadStatus::Queued)
+                        if queue.can_start(model_id, max_concurrent) =>
+                    {
+                        queue.start(model_id);
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+            notified.await;
+        }
+        self.download_queue_changed();
+
+        let result = self.download_model_verified(model_id).await;
+
+        {
+            let mut queue = self.download_queue.lock().unwrap();
+            // A paused download keeps its place; anything else is done with
+            if result.is_err() || queue.status(model_id) != Some(QueuedDownloadStatus::Paused) {
+                queue.remove(model_id);
+            }
+        }
+        self.download_queue_changed();
+
+        result
+    }
+
+    /// Stops a download but keeps its partial file and queue position so it can
+    /// be resumed later, including after a restart.
+    pub fn pause_download(&self, model_id: &str) -> Result<()> {
+        if !self.download_queue.lock().unwrap().pause(model_id) {
+            return Err(anyhow::anyhow!("No queued download for {}", model_id));
+        }
+
+        if let Some(flag) = self.cancel_flags.lock().unwrap().get(model_id) {
+            flag.store(true, Ordering::Relaxed);
+        }
+        {
+            let mut models = self.available_models.lock().unwrap();
+            if let Some(model) = models.get_mut(model_id) {
+                model.is_downloading = false;
+            }
+        }
+        self.update_download_status()?;
+
+        self.download_queue_changed();
+        let _ = self.app_handle.emit("model-download-paused", model_id);
+        info!("Download paused for: {}", model_id);
+        Ok(())
+    }
+
+    /// Downloads a model, retrying once from scratch if the result fails
+    /// checksum verification (a resumed partial may have been the culprit).
+    async fn download_model_verified(&self, model_id: &str) -> Result<()> {
+        match self.download_model_once(model_id).await {
+            Err(e) if e.downcast_ref::<ChecksumMismatch>().is_some() => {
+                warn!("{}; retrying download from scratch", e);
+                self.download_model_once(model_id).await
+            }
+            result => result,
+        }
+    }
+
+    async fn download_model_once(&self, model_id: &str) -> Result<()> {
         info!("download_model command received for ID: {}", model_id);
         let model_info = {
             let models = self.available_models.lock().unwrap();
@@ -716,7 +1304,7 @@ impl ModelManager {
 
         info!("Starting download_model for ID: {}", model_id);
 
-        let url = model_info
+        let mut url = model_info
             .url
             .ok_or_else(|| anyhow::anyhow!("No download URL for model"))?;
         let model_path = self.models_dir.join(&model_info.filename);
@@ -759,22 +1347,74 @@ impl ModelManager {
             flags.insert(model_id.to_string(), cancel_flag.clone());
         }
 
-        // Create HTTP client with range request for resuming
-        let client = reqwest::Client::new();
-        let mut request = client.get(&url);
-
+        // Hash what is already on disk so the digest covers the whole file
+        let mut hasher = Sha256::new();
         if resume_from > 0 {
-            request = request.header("Range", format!("bytes={}-", resume_from));
+            if let Err(e) = File::open(&partial_path).and_then(|f| hash_reader(f, &mut hasher)) {
+                warn!("Failed to hash partial download, starting over: {}", e);
+                let _ = fs::remove_file(&partial_path);
+                resume_from = 0;
+                hasher = Sha256::new();
+            }
         }
 
-        let mut response = match request.send().await {
-            Ok(resp) => {
-                info!("Network request successful for {}. Status: {}", model_id, resp.status());
-                resp
-            }
+        // Create HTTP client with the user's proxy and CA settings
+        let network = get_settings(&self.app_handle).network;
+        let client = match crate::http::client(&network) {
+            Ok(client) => client,
             Err(e) => {
-                error!("Network request failed for {}: {}", model_id, e);
-                return Err(e.into());
+                self.clear_downloading(model_id);
+                return Err(e);
+            }
+        };
+
+        // Try each mirror in order, then the model's own URL
+        let mut response = None;
+        let mut last_error = None;
+        for candidate in crate::http::mirror_urls(&url, &network.model_mirrors) {
+            let mut request = client.get(&candidate);
+            if resume_from > 0 {
+                request = request.header("Range", format!("bytes={}-", resume_from));
+            }
+
+            match request.send().await {
+                Ok(resp) if resp.status().is_success() => {
+                    info!(
+                        "Network request successful for {} via {}. Status: {}",
+                        model_id,
+                        candidate,
+                        resp.status()
+                    );
+                    url = candidate;
+                    response = Some(resp);
+                    break;
+                }
+                Ok(resp) => {
+                    warn!(
+                        "Download source {} for {} returned HTTP {}",
+                        candidate,
+                        model_id,
+                        resp.status()
+                    );
+                    last_error = Some(anyhow::anyhow!(
+                        "Failed to download model: HTTP {}",
+                        resp.status()
+                    ));
+                }
+                Err(e) => {
+                    warn!("Download source {} for {} failed: {}", candidate, model_id, e);
+                    last_error = Some(e.into());
+                }
+            }
+        }
+
+        let mut response = match response {
+            Some(resp) => resp,
+            None => {
+                error!("All download sources failed for {}", model_id);
+                self.clear_downloading(model_id);
+                return Err(last_error
+                    .unwrap_or_else(|| anyhow::anyhow!("No download source for {}", model_id)));
             }
         };
 
@@ -791,6 +1431,7 @@ impl ModelManager {
 
             // Reset resume_from since we're starting fresh
             resume_from = 0;
+            hasher = Sha256::new();
 
             // Restart download without range header
             response = client.get(&url).send().await?;
@@ -914,8 +1555,14 @@ impl ModelManager {
 
             let chunk_len = chunk.len() as u64;
             file.write_all(&chunk)?;
+            hasher.update(&chunk);
             downloaded += chunk_len;
 
+            let delay = self.bandwidth_limiter.reserve(chunk_len);
+            if !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+
             // If total_size was unknown (0), we can update it if we have hints, 
             // but for now we just track downloaded.
             let percentage = if total_size > 0 {
@@ -975,94 +1622,76 @@ impl ModelManager {
             }
         }
 
-        // Handle directory-based models (extract tar.gz) vs file-based models
-        if model_info.is_directory {
-            // Track that this model is being extracted
-            {
-                let mut extracting = self.extracting_models.lock().unwrap();
-                extracting.insert(model_id.to_string());
-            }
-
-            // Emit extraction started event
-            let _ = self.app_handle.emit("model-extraction-started", model_id);
-            info!("Extracting archive for directory-based model: {}", model_id);
-
-            // Use a temporary extraction directory to ensure atomic operations
-            let temp_extract_dir = self
-                .models_dir
-                .join(format!("{}.extracting", &model_info.filename));
-            let final_model_dir = self.models_dir.join(&model_info.filename);
-
-            // Clean up any previous incomplete extraction
-            if temp_extract_dir.exists() {
-                let _ = fs::remove_dir_all(&temp_extract_dir);
+        // Without a digest, the catalog size is the only check left. Archives
+        // are skipped here since extracting a truncated one already fails.
+        if model_info.sha256.is_none() && !model_info.is_directory {
+            let actual_size = partial_path.metadata()?.len();
+            if looks_truncated(model_info.size_mb, actual_size) {
+                let _ = fs::remove_file(&partial_path);
+                {
+                    let mut models = self.available_models.lock().unwrap();
+                    if let Some(model) = models.get_mut(model_id) {
+                        model.is_downloading = false;
+                    }
+                }
+                return Err(anyhow::anyhow!(
+                    "Download incomplete: expected about {} MB, got {} bytes",
+                    model_info.size_mb,
+                    actual_size
+                ));
             }
+        }
 
-            // Create temporary extraction directory
-            fs::create_dir_all(&temp_extract_dir)?;
-
-            // Open the downloaded tar.gz file
-            let tar_gz = File::open(&partial_path)?;
-            let tar = GzDecoder::new(tar_gz);
-            let mut archive = Archive::new(tar);
-
-            // Extract to the temporary directory first
-            archive.unpack(&temp_extract_dir).map_err(|e| {
-                let error_msg = format!("Failed to extract archive: {}", e);
-                // Clean up failed extraction
-                let _ = fs::remove_dir_all(&temp_extract_dir);
-                // Remove from extracting set
+        let digest = format!("{:x}", hasher.finalize());
+        if let Some(expected) = &model_info.sha256 {
+            if !expected.eq_ignore_ascii_case(&digest) {
+                // Corrupt download - delete it so a retry starts from scratch
+                let _ = fs::remove_file(&partial_path);
+                {
+                    let mut models = self.available_models.lock().unwrap();
+                    if let Some(model) = models.get_mut(model_id) {
+                        model.is_downloading = false;
+                    }
+                }
                 {
-                    let mut extracting = self.extracting_models.lock().unwrap();
-                    extracting.remove(model_id);
+                    let mut flags = self.cancel_flags.lock().unwrap();
+                    flags.remove(model_id);
                 }
                 let _ = self.app_handle.emit(
-                    "model-extraction-failed",
+                    "model-verification-failed",
                     &serde_json::json!({
                         "model_id": model_id,
-                        "error": error_msg
+                        "expected": expected,
+                        "actual": digest
                     }),
                 );
-                anyhow::anyhow!(error_msg)
-            })?;
-
-            // Find the actual extracted directory (archive might have a nested structure)
-            let extracted_dirs: Vec<_> = fs::read_dir(&temp_extract_dir)?
-                .filter_map(|entry| entry.ok())
-                .filter(|entry| entry.file_type().map(|ft| ft.is_dir()).unwrap_or(false))
-                .collect();
-
-            if extracted_dirs.len() == 1 {
-                // Single directory extracted, move it to the final location
-                let source_dir = extracted_dirs[0].path();
-                if final_model_dir.exists() {
-                    fs::remove_dir_all(&final_model_dir)?;
-                }
-                fs::rename(&source_dir, &final_model_dir)?;
-                // Clean up temp directory
-                let _ = fs::remove_dir_all(&temp_extract_dir);
-            } else {
-                // Multiple items or no directories, rename the temp directory itself
-                if final_model_dir.exists() {
-                    fs::remove_dir_all(&final_model_dir)?;
+                return Err(ChecksumMismatch {
+                    model_id: model_id.to_string(),
+                    expected: expected.to_lowercase(),
+                    actual: digest,
                 }
-                fs::rename(&temp_extract_dir, &final_model_dir)?;
+                .into());
             }
+            info!("Checksum verified for {}", model_id);
+        }
 
-            info!("Successfully extracted archive for model: {}", model_id);
-            // Remove from extracting set
-            {
-                let mut extracting = self.extracting_models.lock().unwrap();
-                extracting.remove(model_id);
-            }
-            // Emit extraction completed event
-            let _ = self.app_handle.emit("model-extraction-completed", model_id);
+        // Handle directory-based models (extract tar.gz) vs file-based models
+        if model_info.is_directory {
+            let final_model_dir =
+                self.extract_model_archive(model_id, &model_info.filename, &partial_path)?;
 
             // Remove the downloaded tar.gz file
             let _ = fs::remove_file(&partial_path);
+
+            // Record the extracted tree's digest for later verification
+            match sha256_dir(&final_model_dir) {
+                Ok(tree_digest) => self.record_digest(&model_info.filename, &tree_digest),
+                Err(e) => warn!("Failed to hash extracted model {}: {}", model_id, e),
+            }
         } else {
             // Move partial file to final location for file-based models
             fs::rename(&partial_path, &model_path)?;
+            self.record_digest(&model_info.filename, &digest);
         }
 
         // Update download status
@@ -1092,6 +1721,490 @@ impl ModelManager {
         Ok(())
     }
 
+    /// Clears the downloading flag and cancel flag after a download gives up.
+    fn clear_downloading(&self, model_id: &str) {
+        {
+            let mut models = self.available_models.lock().unwrap();
+            if let Some(model) = models.get_mut(model_id) {
+                model.is_downloading = false;
+            }
+        }
+        let mut flags = self.cancel_flags.lock().unwrap();
+        flags.remove(model_id);
+    }
+
+    /// Extracts a model archive into `<filename>.extracting` and then renames it
+    /// into place, so a failed or interrupted extraction never leaves a
+    /// half-populated model directory behind.
+    fn extract_model_archive(
+        &self,
+        model_id: &str,
+        filename: &str,
+        archive: &Path,
+    ) -> Result<PathBuf> {
+        // Track that this model is being extracted
+        {
+            let mut extracting = self.extracting_models.lock().unwrap();
+            extracting.insert(model_id.to_string());
+        }
+
+        // Emit extraction started event
+        let _ = self.app_handle.emit("model-extraction-started", model_id);
+        info!("Extracting archive for directory-based model: {}", model_id);
+
+        let result = unpack_model_archive(archive, &self.models_dir, filename);
+
+        // Remove from extracting set
+        {
+            let mut extracting = self.extracting_models.lock().unwrap();
+            extracting.remove(model_id);
+        }
+        match &result {
+            Ok(_) => {
+                info!("Successfully extracted archive for model: {}", model_id);
+                let _ = self.app_handle.emit("model-extraction-completed", model_id);
+            }
+            Err(e) => {
+                let _ = self.app_handle.emit(
+                    "model-extraction-failed",
+                    &serde_json::json!({
+                        "model_id": model_id,
+                        "error": e.to_string()
+                    }),
+                );
+            }
+        }
+        result
+    }
+
+    /// Finds the catalog model an import source belongs to: by model file name,
+    /// by the archive name in the download URL, or by directory name.
+    fn match_catalog_model(&self, source: &Path) -> Option<ModelInfo> {
+        let name = source.file_name()?.to_str()?;
+        let models = self.available_models.lock().unwrap();
+        models
+            .values()
+            .filter(|m| !m.is_custom)
+            .find(|m| {
+                if !m.is_directory {
+                    source.is_file() && m.filename == name
+                } else if source.is_dir() {
+                    m.filename == name
+                } else {
+                    m.url.as_deref().and_then(|u| u.rsplit('/').next()) == Some(name)
+                }
+            })
+            .cloned()
+    }
+
+    /// Imports a model from a local `.bin` file, `.tar.gz` archive or directory
+    /// without touching the network. With `model_id` the source is installed as
+    /// that catalog model; otherwise it's matched to the catalog by name, and
+    /// anything that doesn't match is installed as a custom model.
+    pub fn import_model(&self, source: &Path, model_id: Option<&str>) -> Result<ModelImport> {
+        if !source.exists() {
+            return Err(anyhow::anyhow!("Import source not found: {:?}", source));
+        }
+
+        let model_info = match model_id {
+            Some(id) => Some(
+                self.get_model_info(id)
+                    .ok_or_else(|| anyhow::anyhow!("Model not found: {}", id))?,
+            ),
+            None => self.match_catalog_model(source),
+        };
+
+        let model_id = match model_info {
+            Some(info) if info.is_custom => {
+                return Err(anyhow::anyhow!(
+                    "{} is a custom model; delete it before importing a replacement",
+                    info.id
+                ));
+            }
+            Some(info) if info.is_downloaded => {
+                info!("Model {} is already installed, skipping import", info.id);
+                return Ok(ModelImport {
+                    source: source.to_string_lossy().to_string(),
+                    model_id: Some(info.id),
+                    status: ImportStatus::AlreadyInstalled,
+                    error: None,
+                });
+            }
+            Some(info) => {
+                self.import_catalog_model(source, &info)?;
+                info.id
+            }
+            None => self.import_custom_model(source)?,
+        };
+
+        info!("Imported model {} from {:?}", model_id, source);
+        let _ = self.app_handle.emit("model-imported", &model_id);
+
+        Ok(ModelImport {
+            source: source.to_string_lossy().to_string(),
+            model_id: Some(model_id),
+            status: ImportStatus::Imported,
+            error: None,
+        })
+    }
+
+    /// Imports everything in an offline bundle directory: model files,
+    /// `.tar.gz` archives and model directories, matched to the catalog by name.
+    /// One bad entry doesn't stop the rest; each gets its own result.
+    pub fn import_model_bundle(&self, bundle_dir: &Path) -> Result<Vec<ModelImport>> {
+        let mut sources: Vec<PathBuf> = fs::read_dir(bundle_dir)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.file_name().and_then(|n| n.to_str()).unwrap_or(".");
+                if name.starts_with('.') {
+                    return false;
+                }
+                path.is_dir()
+                    || is_model_archive(path)
+                    || bin_stem(name).is_some()
+                    || self.match_catalog_model(path).is_some()
+            })
+            .collect();
+        sources.sort();
+
+        Ok(sources
+            .iter()
+            .map(|source| {
+                self.import_model(source, None).unwrap_or_else(|e| {
+                    warn!("Failed to import {:?}: {}", source, e);
+                    ModelImport {
+                        source: source.to_string_lossy().to_string(),
+                        model_id: None,
+                        status: ImportStatus::Failed,
+                        error: Some(e.to_string()),
+                    }
+                })
+            })
+            .collect())
+    }
+
+    fn import_catalog_model(&self, source: &Path, model_info: &ModelInfo) -> Result<()> {
+        let model_id = model_info.id.as_str();
+        if model_info.is_downloading {
+            return Err(anyhow::anyhow!(
+                "Model is currently downloading: {}",
+                model_id
+            ));
+        }
+
+        if model_info.is_directory {
+            let final_dir = if source.is_dir() {
+                self.check_model_layout(model_info, source)?;
+                self.copy_model_dir(source, &model_info.filename)?
+            } else if is_model_archive(source) {
+                if let Some(expected) = &model_info.sha256 {
+                    let actual = sha256_file(source)?;
+                    if !expected.eq_ignore_ascii_case(&actual) {
+                        return Err(ChecksumMismatch {
+                            model_id: model_id.to_string(),
+                            expected: expected.to_lowercase(),
+                            actual,
+                        }
+                        .into());
+                    }
+                }
+                let final_dir =
+                    self.extract_model_archive(model_id, &model_info.filename, source)?;
+                if let Err(e) = self.check_model_layout(model_info, &final_dir) {
+                    let _ = fs::remove_dir_all(&final_dir);
+                    return Err(e);
+                }
+                final_dir
+            } else {
+                return Err(anyhow::anyhow!(
+                    "{} must be imported from a .tar.gz archive or a directory",
+                    model_info.name
+                ));
+            };
+
+            match sha256_dir(&final_dir) {
+                Ok(tree_digest) => self.record_digest(&model_info.filename, &tree_digest),
+                Err(e) => warn!("Failed to hash imported model {}: {}", model_id, e),
+            }
+        } else {
+            if !source.is_file() || is_model_archive(source) {
+                return Err(anyhow::anyhow!(
+                    "{} must be imported from its model file ({})",
+                    model_info.name,
+                    model_info.filename
+                ));
+            }
+            let digest = self.copy_model_file(
+                source,
+                &model_info.filename,
+                model_id,
+                model_info.sha256.as_deref(),
+            )?;
+            self.record_digest(&model_info.filename, &digest);
+        }
+
+        let mut models = self.available_models.lock().unwrap();
+        if let Some(model) = models.get_mut(model_id) {
+            model.is_downloaded = true;
+            model.partial_size = 0;
+        }
+        Ok(())
+    }
+
+    /// Installs an import that matches no catalog model. Loose `.bin` files are
+    /// custom Whisper models; archives and directories must have a layout that
+    /// discovery recognises, or a `model.json` naming the engine.
+    fn import_custom_model(&self, source: &Path) -> Result<String> {
+        let stem = import_stem(source)
+            .ok_or_else(|| anyhow::anyhow!("Invalid import source: {:?}", source))?;
+        let whisper_stem = bin_stem(&stem).filter(|_| source.is_file());
+        let is_bin = whisper_stem.is_some();
+        let model_id = whisper_stem.unwrap_or(&stem).to_string();
+        // Discovery only picks up lowercase `.bin` files, so `Model.BIN` is
+        // installed as `Model.bin`
+        let filename = if is_bin {
+            format!("{}.bin", model_id)
+        } else {
+            stem.clone()
+        };
+
+        let taken = {
+            let models = self.available_models.lock().unwrap();
+            models.contains_key(&model_id) || models.values().any(|m| m.filename == filename)
+        };
+        if taken || self.models_dir.join(&filename).exists() {
+            return Err(anyhow::anyhow!(
+                "A model named {} already exists; pass a model id to import it as that model",
+                filename
+            ));
+        }
+
+        if is_bin {
+            let digest = self.copy_model_file(source, &filename, &model_id, None)?;
+            self.record_digest(&filename, &digest);
+        } else if source.is_dir() || is_model_archive(source) {
+            let final_dir = if source.is_dir() {
+                self.copy_model_dir(source, &stem)?
+            } else {
+                self.extract_model_archive(&model_id, &stem, source)?
+            };
+
+            if detect_engine_type(&final_dir).is_none()
+                && !final_dir.join(CUSTOM_MODEL_METADATA).exists()
+            {
+                let _ = fs::remove_dir_all(&final_dir);
+                return Err(anyhow::anyhow!(
+                    "Unrecognised model layout in {:?}; add a {} naming the engine",
+                    source,
+                    CUSTOM_MODEL_METADATA
+                ));
+            }
+
+            match sha256_dir(&final_dir) {
+                Ok(tree_digest) => self.record_digest(&stem, &tree_digest),
+                Err(e) => warn!("Failed to hash imported model {}: {}", model_id, e),
+            }
+        } else {
+            return Err(anyhow::anyhow!(
+                "Unsupported import source {:?}: expected a .bin file, archive or directory",
+                source
+            ));
+        }
+
+        let mut models = self.available_models.lock().unwrap();
+        Self::discover_custom_models(&self.models_dir, &mut models)?;
+        if !models.contains_key(&model_id) {
+            return Err(anyhow::anyhow!(
+                "Imported model {} was not recognised",
+                model_id
+            ));
+        }
+        Ok(model_id)
+    }
+
+    /// Rejects a directory whose layout is clearly for a different engine.
+    fn check_model_layout(&self, model_info: &ModelInfo, dir: &Path) -> Result<()> {
+        match detect_engine_type(dir) {
+            Some(detected)
+                if std::mem::discriminant(&detected)
+                    != std::mem::discriminant(&model_info.engine_type) =>
+            {
+                Err(anyhow::anyhow!(
+                    "{:?} looks like a {:?} model, not {}",
+                    dir,
+                    detected,
+                    model_info.name
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Copies a model file into place through `<filename>.partial`, the same
+    /// way downloads land, verifying it against the catalog digest if one is set.
+    /// Returns the file's digest.
+    fn copy_model_file(
+        &self,
+        source: &Path,
+        filename: &str,
+        model_id: &str,
+        expected: Option<&str>,
+    ) -> Result<String> {
+        let partial_path = self.models_dir.join(format!("{}.partial", filename));
+        let model_path = self.models_dir.join(filename);
+
+        let mut hasher = Sha256::new();
+        {
+            let mut reader = File::open(source)?;
+            let mut writer = File::create(&partial_path)?;
+            let mut buf = vec![0u8; 1024 * 1024];
+            loop {
+                let n = reader.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                writer.write_all(&buf[..n])?;
+                hasher.update(&buf[..n]);
+            }
+            writer.flush()?;
+        }
+
+        let digest = format!("{:x}", hasher.finalize());
+        if let Some(expected) = expected {
+            if !expected.eq_ignore_ascii_case(&digest) {
+                let _ = fs::remove_file(&partial_path);
+                return Err(ChecksumMismatch {
+                    model_id: model_id.to_string(),
+                    expected: expected.to_lowercase(),
+                    actual: digest,
+                }
+                .into());
+            }
+        }
+
+        fs::rename(&partial_path, &model_path)?;
+        Ok(digest)
+    }
+
+    /// Copies a model directory into `<filename>.extracting` and renames it into
+    /// place, matching how archives are extracted.
+    fn copy_model_dir(&self, source: &Path, filename: &str) -> Result<PathBuf> {
+        let temp_dir = self.models_dir.join(format!("{}.extracting", filename));
+        let final_dir = self.models_dir.join(filename);
+
+        if temp_dir.exists() {
+            fs::remove_dir_all(&temp_dir)?;
+        }
+        if let Err(e) = copy_dir_all(source, &temp_dir) {
+            let _ = fs::remove_dir_all(&temp_dir);
+            return Err(e.into());
+        }
+
+        if final_dir.exists() {
+            fs::remove_dir_all(&final_dir)?;
+        }
+        fs::rename(&temp_dir, &final_dir)?;
+        Ok(final_dir)
+    }
+
+    pub fn storage_usage(&self) -> ModelStorage {
+        let mut usage = ModelStorage::default();
+        for entry in fs::read_dir(&self.models_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let size = if path.is_dir() {
+                dir_size(&path)
+            } else {
+                entry.metadata().map(|m| m.len()).unwrap_or(0)
+            };
+
+            if name.ends_with(".partial") {
+                usage.partial_download_bytes += size;
+            } else if name.ends_with(".extracting") {
+                usage.extraction_leftover_bytes += size;
+            } else {
+                usage.installed_bytes += size;
+            }
+        }
+        usage
+    }
+
+    /// Removes `.extracting` leftovers and/or `.partial` files. Anything
+    /// belonging to a running, queued or paused download is kept.
+    /// Returns (files removed, bytes freed).
+    pub fn remove_stale_downloads(
+        &self,
+        extraction_leftovers: bool,
+        partial_downloads: bool,
+    ) -> Result<(usize, u64)> {
+        let in_use: HashSet<String> = {
+            let models = self.available_models.lock().unwrap();
+            let flags = self.cancel_flags.lock().unwrap();
+            let extracting = self.extracting_models.lock().unwrap();
+            let queue = self.download_queue.lock().unwrap();
+            models
+                .values()
+                .filter(|m| {
+                    flags.contains_key(&m.id)
+                        || extracting.contains(&m.id)
+                        || queue.status(&m.id).is_some()
+                })
+                .flat_map(|m| {
+                    [
+                        format!("{}.partial", m.filename),
+                        format!("{}.extracting", m.filename),
+                    ]
+                })
+                .collect()
+        };
+
+        let cutoff = SystemTime::now() - STALE_LEFTOVER_AGE;
+        let mut removed = 0;
+        let mut bytes_freed = 0;
+
+        for entry in fs::read_dir(&self.models_dir)?.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stale = (extraction_leftovers && name.ends_with(".extracting"))
+                || (partial_downloads && name.ends_with(".partial"));
+            if !stale || in_use.contains(&name) {
+                continue;
+            }
+            let recent = entry
+                .metadata()
+                .and_then(|m| m.modified())
+                .map_or(true, |t| t > cutoff);
+            if recent {
+                continue;
+            }
+
+            let (size, result) = if path.is_dir() {
+                (dir_size(&path), fs::remove_dir_all(&path))
+            } else {
+                (
+                    entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    fs::remove_file(&path),
+                )
+            };
+            match result {
+                Ok(()) => {
+                    info!("Removed stale download leftover: {}", name);
+                    removed += 1;
+                    bytes_freed += size;
+                }
+                Err(e) => warn!("Failed to remove {}: {}", path.display(), e),
+            }
+        }
+
+        self.update_download_status()?;
+        Ok((removed, bytes_freed))
+    }
+
     pub fn delete_model(&self, model_id: &str) -> Result<()> {
         debug!("ModelManager: delete_model called for: {}", model_id);
 
@@ -1144,6 +2257,8 @@ impl ModelManager {
             return Err(anyhow::anyhow!("No model files found to delete"));
         }
 
+        let _ = fs::remove_file(self.digest_record_path(&model_info.filename));
+
         // Custom models should be removed from the list entirely since they
         // have no download URL and can't be re-downloaded
         if model_info.is_custom {
@@ -1229,6 +2344,11 @@ impl ModelManager {
             }
         }
 
+        // Drop it from the queue, which also releases a download still waiting for a slot
+        if self.download_queue.lock().unwrap().remove(model_id) {
+            self.download_queue_changed();
+        }
+
         // Update download status to reflect current state
         self.update_download_status()?;
 
@@ -1286,6 +2406,7 @@ mod tests {
                 is_recommended: false,
                 supported_languages: vec!["en".to_string()],
                 is_custom: false,
+                sha256: None,
             },
         );
 
@@ -1343,4 +2464,289 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(models.len(), count_before);
     }
+
+    fn create_model_dir(models_dir: &Path, name: &str, files: &[&str]) -> PathBuf {
+        let dir = models_dir.join(name);
+        fs::create_dir(&dir).unwrap();
+        for file in files {
+            fs::write(dir.join(file), b"fake model data").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_detect_engine_type() {
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path();
+
+        let cases = [
+            (
+                "parakeet",
+                &[
+                    "encoder-model.int8.onnx",
+                    "decoder_joint-model.int8.onnx",
+                    "nemo128.onnx",
+                    "vocab.txt",
+                ][..],
+                Some("Parakeet"),
+            ),
+            (
+                "moonshine",
+                &[
+                    "encoder_model.onnx",
+                    "decoder_model_merged.onnx",
+                    "tokenizer.json",
+                ][..],
+                Some("Moonshine"),
+            ),
+            (
+                "sense-voice",
+                &["model.int8.onnx", "tokens.txt"][..],
+                Some("SenseVoice"),
+            ),
+            (
+                "piper",
+                &["voice.onnx", "voice.onnx.json"][..],
+                Some("Piper"),
+            ),
+            (
+                "xtts",
+                &["config.json", "model.pth", "vocab.json"][..],
+                Some("XTTS"),
+            ),
+            ("unknown", &["weights.onnx", "notes.txt"][..], None),
+        ];
+
+        for (name, files, expected) in cases {
+            let dir = create_model_dir(models_dir, name, files);
+            let detected = detect_engine_type(&dir).map(|e| format!("{:?}", e));
+            assert_eq!(detected.as_deref(), expected, "layout {}", name);
+        }
+    }
+
+    #[test]
+    fn test_discover_custom_model_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path().to_path_buf();
+
+        create_model_dir(&models_dir, "my_sense-voice", &["model.int8.onnx", "tokens.txt"]);
+
+        let with_sidecar = create_model_dir(
+            &models_dir,
+            "german-parakeet",
+            &[
+                "encoder-model.int8.onnx",
+                "decoder_joint-model.int8.onnx",
+                "nemo128.onnx",
+                "vocab.txt",
+            ],
+        );
+        fs::write(
+            with_sidecar.join("model.json"),
+            r#"{"name": "Parakeet German", "supported_languages": ["de"]}"#,
+        )
+        .unwrap();
+
+        // Sidecar can name the engine when the layout isn't recognised
+        let declared = create_model_dir(&models_dir, "declared", &["weights.onnx"]);
+        fs::write(declared.join("model.json"), r#"{"engine_type": "Moonshine"}"#).unwrap();
+
+        // Should be skipped
+        create_model_dir(&models_dir, "random-folder", &["notes.txt"]);
+        create_model_dir(&models_dir, ".hidden", &["model.int8.onnx", "tokens.txt"]);
+        create_model_dir(
+            &models_dir,
+            "sense-voice-int8",
+            &["model.int8.onnx", "tokens.txt"],
+        );
+        create_model_dir(
+            &models_dir,
+            "other.partial",
+            &["model.int8.onnx", "tokens.txt"],
+        );
+
+        let mut models = HashMap::new();
+        let mut predefined = model_catalog::bundled()
+            .into_iter()
+            .find(|m| m.id == "sense-voice-int8")
+            .unwrap();
+        predefined.is_downloaded = true;
+        models.insert(predefined.id.clone(), predefined);
+
+        ModelManager::discover_custom_model_dirs(&models_dir, &mut models).unwrap();
+
+        let sense_voice = models.get("my_sense-voice").unwrap();
+        assert!(matches!(sense_voice.engine_type, EngineType::SenseVoice));
+        assert_eq!(sense_voice.name, "My Sense Voice");
+        assert!(sense_voice.is_custom && sense_voice.is_directory && sense_voice.is_downloaded);
+
+        let parakeet = models.get("german-parakeet").unwrap();
+        assert!(matches!(parakeet.engine_type, EngineType::Parakeet));
+        assert_eq!(parakeet.name, "Parakeet German");
+        assert_eq!(parakeet.supported_languages, vec!["de"]);
+
+        assert!(matches!(
+            models.get("declared").unwrap().engine_type,
+            EngineType::Moonshine
+        ));
+
+        assert!(!models.contains_key("random-folder"));
+        assert!(!models.contains_key(".hidden"));
+        assert!(!models.contains_key("other.partial"));
+        assert!(!models["sense-voice-int8"].is_custom);
+        assert_eq!(models.len(), 4);
+    }
+
+    #[test]
+    fn test_moonshine_variant_detection() {
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path();
+        let files = ["encoder_model.onnx", "tokenizer.json"];
+
+        let decoder = |layers: usize| {
+            (0..layers)
+                .map(|i| format!("past_key_values.{}.decoder.key", i))
+                .collect::<String>()
+        };
+        let tiny = create_model_dir(models_dir, "tiny", &files);
+        fs::write(tiny.join("decoder_model_merged.onnx"), decoder(6)).unwrap();
+        let base = create_model_dir(models_dir, "base", &files);
+        fs::write(base.join("decoder_model_merged.onnx"), decoder(8)).unwrap();
+        let chinese = create_model_dir(models_dir, "chinese", &files);
+        fs::write(chinese.join("decoder_model_merged.onnx"), decoder(6)).unwrap();
+        fs::write(chinese.join("model.json"), r#"{"variant": "tiny-zh"}"#).unwrap();
+
+        assert_eq!(moonshine_variant(&tiny), ModelVariant::Tiny);
+        assert_eq!(moonshine_variant(&base), ModelVariant::Base);
+        assert_eq!(moonshine_variant(&chinese), ModelVariant::TinyZh);
+    }
+
+    #[test]
+    fn test_import_custom_archive_with_uppercase_extension() {
+        let temp_dir = TempDir::new().unwrap();
+        let models_dir = temp_dir.path().join("models");
+        fs::create_dir(&models_dir).unwrap();
+        let source = create_model_dir(
+            temp_dir.path(),
+            "moonshine-tiny",
+            &["encoder_model.onnx", "tokenizer.json"],
+        );
+        fs::write(
+            source.join("decoder_model_merged.onnx"),
+            "past_key_values.5.decoder.key",
+        )
+        .unwrap();
+
+        let archive_path = temp_dir.path().join("Model.TAR.GZ");
+        let encoder = flate2::write::GzEncoder::new(
+            File::create(&archive_path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        builder.append_dir_all("moonshine-tiny", &source).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert!(is_model_archive(&archive_path));
+        let stem = import_stem(&archive_path).unwrap();
+        assert_eq!(stem, "Model");
+
+        let installed = unpack_model_archive(&archive_path, &models_dir, &stem).unwrap();
+        assert_eq!(installed, models_dir.join("Model"));
+        assert!(installed.join("tokenizer.json").is_file());
+        assert!(!models_dir.join("Model.extracting").exists());
+
+        let mut models = HashMap::new();
+        ModelManager::discover_custom_model_dirs(&models_dir, &mut models).unwrap();
+        let model = models.get("Model").unwrap();
+        assert!(matches!(model.engine_type, EngineType::Moonshine));
+        assert!(model.is_custom && model.is_downloaded);
+        assert_eq!(moonshine_variant(&installed), ModelVariant::Tiny);
+    }
+
+    #[test]
+    fn test_import_source_helpers() {
+        assert!(is_model_archive(Path::new(
+            "/bundle/parakeet-v3-int8.tar.gz"
+        )));
+        assert!(is_model_archive(Path::new("/bundle/MODEL.TGZ")));
+        assert!(!is_model_archive(Path::new("/bundle/ggml-small.bin")));
+
+        assert_eq!(
+            import_stem(Path::new("/bundle/moonshine-base.tar.gz")).as_deref(),
+            Some("moonshine-base")
+        );
+        assert_eq!(
+            import_stem(Path::new("/bundle/ggml-small.bin")).as_deref(),
+            Some("ggml-small.bin")
+        );
+        assert_eq!(
+            import_stem(Path::new("/bundle/MODEL.TGZ")).as_deref(),
+            Some("MODEL")
+        );
+
+        assert_eq!(bin_stem("ggml-small.bin"), Some("ggml-small"));
+        assert_eq!(bin_stem("Whisper-Medical.BIN"), Some("Whisper-Medical"));
+        assert_eq!(bin_stem(".bin"), None);
+        assert_eq!(bin_stem("model.onnx"), None);
+        assert_eq!(bin_stem("модель.bin"), Some("модель"));
+    }
+
+    #[test]
+    fn test_copy_dir_all() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = create_model_dir(temp_dir.path(), "source", &["tokens.txt"]);
+        create_model_dir(&source, "nested", &["model.int8.onnx"]);
+
+        let target = temp_dir.path().join("target");
+        copy_dir_all(&source, &target).unwrap();
+
+        assert_eq!(sha256_dir(&source).unwrap(), sha256_dir(&target).unwrap());
+    }
+
+    #[test]
+    fn test_looks_truncated() {
+        assert!(!looks_truncated(100, 100_000_000));
+        // size_mb is rounded, so a little under still passes
+        assert!(!looks_truncated(100, 95_000_000));
+        assert!(looks_truncated(100, 50_000_000));
+        assert!(looks_truncated(100, 0));
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("model.bin");
+        fs::write(&path, b"abc").unwrap();
+
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_sha256_dir_detects_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let model_dir = temp_dir.path().join("model");
+        fs::create_dir_all(model_dir.join("nested")).unwrap();
+        fs::write(model_dir.join("encoder.onnx"), b"encoder").unwrap();
+        fs::write(model_dir.join("nested").join("vocab.txt"), b"vocab").unwrap();
+
+        let original = sha256_dir(&model_dir).unwrap();
+        assert_eq!(sha256_dir(&model_dir).unwrap(), original);
+
+        // Truncated file
+        fs::write(model_dir.join("encoder.onnx"), b"enc").unwrap();
+        let truncated = sha256_dir(&model_dir).unwrap();
+        assert_ne!(truncated, original);
+
+        // Same contents under a different name
+        fs::write(model_dir.join("encoder.onnx"), b"encoder").unwrap();
+        fs::rename(
+            model_dir.join("nested").join("vocab.txt"),
+            model_dir.join("nested").join("tokens.txt"),
+        )
+        .unwrap();
+        assert_ne!(sha256_dir(&model_dir).unwrap(), original);
+    }
 }
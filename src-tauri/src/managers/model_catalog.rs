@@ -0,0 +1,476 @@
+use crate::managers::model::{EngineType, ModelInfo};
+use crate::settings::NetworkSettings;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Manifest schema version this build understands.
+pub const CATALOG_VERSION: u32 = 1;
+
+/// Catalog shipped with the app. It is used unless another source is configured,
+/// and whenever a catalog can't be loaded.
+const BUNDLED_CATALOG: &str = include_str!("../../resources/model_catalog.json");
+
+/// Manifest published alongside the models, so new models don't need an app
+/// release. Only fetched once the user opts in.
+pub const PUBLISHED_CATALOG_URL: &str = "https://blob.handy.computer/model_catalog.json";
+
+/// `model_catalog_source` value that opts in to the published manifest.
+pub const PUBLISHED_SOURCE: &str = "published";
+
+/// `model_catalog_source` value for the bundled catalog, same as leaving it unset.
+pub const BUNDLED_SOURCE: &str = "bundled";
+
+/// Hosts that models listed in a remote catalog may be downloaded from. A
+/// remote manifest also supplies the digests downloads are checked against,
+/// so it must not be able to point them anywhere else.
+const TRUSTED_MODEL_HOSTS: &[&str] = &["blob.handy.computer", "huggingface.co"];
+
+/// Remote manifests larger than this are rejected rather than parsed.
+const MAX_CATALOG_BYTES: usize = 4 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct CatalogManifest {
+    version: u32,
+    /// Named language lists that entries can share instead of repeating them
+    #[serde(default)]
+    language_sets: HashMap<String, Vec<String>>,
+    models: Vec<CatalogEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatalogEntry {
+    id: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    filename: String,
+    url: Option<String>,
+    size_mb: u64,
+    #[serde(default)]
+    is_directory: bool,
+    engine_type: EngineType,
+    accuracy_score: f32,
+    speed_score: f32,
+    #[serde(default)]
+    supports_translation: bool,
+    #[serde(default)]
+    is_recommended: bool,
+    #[serde(default)]
+    language_set: Option<String>,
+    #[serde(default)]
+    supported_languages: Vec<String>,
+    #[serde(default)]
+    sha256: Option<String>,
+}
+
+/// Where the current catalog came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogSource {
+    Bundled,
+    File(String),
+    Remote(String),
+}
+
+impl CatalogSource {
+    /// Interprets the `model_catalog_source` setting: unset means the bundled
+    /// catalog, `published` the published manifest, URLs are remote and
+    /// anything else is a local file path.
+    pub fn from_setting(setting: Option<&str>) -> Self {
+        match setting.map(str::trim).filter(|s| !s.is_empty()) {
+            None => Self::Bundled,
+            Some(s) if s.eq_ignore_ascii_case(BUNDLED_SOURCE) => Self::Bundled,
+            Some(s) if s.eq_ignore_ascii_case(PUBLISHED_SOURCE) => {
+                Self::Remote(PUBLISHED_CATALOG_URL.to_string())
+            }
+            Some(s) if is_url(s) => Self::Remote(s.to_string()),
+            Some(s) => Self::File(s.to_string()),
+        }
+    }
+
+    pub fn is_published(&self) -> bool {
+        matches!(self, Self::Remote(url) if url == PUBLISHED_CATALOG_URL)
+    }
+}
+
+/// Whether `source` looks like a URL rather than a file path. Plain http URLs
+/// count so they are rejected when fetched instead of read as a path.
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+fn require_https(url: &str) -> Result<()> {
+    if !url.starts_with("https://") {
+        bail!("'{}' must be an https:// URL", url);
+    }
+    Ok(())
+}
+
+/// Whether `url` points at one of the `TRUSTED_MODEL_HOSTS`.
+fn is_trusted_host(url: &str) -> bool {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| TRUSTED_MODEL_HOSTS.contains(&host.as_str()))
+}
+
+/// Parses and validates a manifest, returning the models it describes.
+pub fn parse(json: &str) -> Result<Vec<ModelInfo>> {
+    let manifest: CatalogManifest =
+        serde_json::from_str(json).context("Model catalog is not valid JSON")?;
+
+    if manifest.version != CATALOG_VERSION {
+        bail!(
+            "Unsupported model catalog version {} (expected {})",
+            manifest.version,
+            CATALOG_VERSION
+        );
+    }
+    if manifest.models.is_empty() {
+        bail!("Model catalog contains no models");
+    }
+
+    let mut ids = HashSet::new();
+    let mut filenames = HashSet::new();
+    let mut models = Vec::with_capacity(manifest.models.len());
+
+    for entry in manifest.models {
+        validate_entry(&entry)?;
+        if !ids.insert(entry.id.clone()) {
+            bail!("Duplicate model id '{}' in catalog", entry.id);
+        }
+        if !filenames.insert(entry.filename.clone()) {
+            bail!("Duplicate filename '{}' in catalog", entry.filename);
+        }
+
+        let supported_languages = match &entry.language_set {
+            Some(set) => manifest.language_sets.get(set).cloned().with_context(|| {
+                format!("Model '{}' uses unknown language set '{}'", entry.id, set)
+            })?,
+            None => entry.supported_languages,
+        };
+
+        models.push(ModelInfo {
+            id: entry.id,
+            name: entry.name,
+            description: entry.description,
+            filename: entry.filename,
+            url: entry.url,
+            size_mb: entry.size_mb,
+            is_downloaded: false,
+            is_downloading: false,
+            partial_size: 0,
+            is_directory: entry.is_directory,
+            engine_type: entry.engine_type,
+            accuracy_score: entry.accuracy_score,
+            speed_score: entry.speed_score,
+            supports_translation: entry.supports_translation,
+            is_recommended: entry.is_recommended,
+            supported_languages,
+            is_custom: false,
+            sha256: entry.sha256.map(|d| d.to_lowercase()),
+        });
+    }
+
+    Ok(models)
+}
+
+fn validate_entry(entry: &CatalogEntry) -> Result<()> {
+    let id = &entry.id;
+    if id.trim().is_empty() {
+        bail!("Model catalog entry has an empty id");
+    }
+    if entry.name.trim().is_empty() {
+        bail!("Model '{}' has an empty name", id);
+    }
+
+    // The filename is joined onto the models directory, so it must not escape it
+    let filename = &entry.filename;
+    if filename.is_empty()
+        || filename == "."
+        || filename.contains("..")
+        || filename.contains('/')
+        || filename.contains('\\')
+        || filename.starts_with('.')
+    {
+        bail!("Model '{}' has an invalid filename '{}'", id, filename);
+    }
+
+    if let Some(url) = &entry.url {
+        require_https(url)
+            .with_context(|| format!("Model '{}' has an invalid download URL", id))?;
+    }
+
+    for (label, score) in [
+        ("accuracy_score", entry.accuracy_score),
+        ("speed_score", entry.speed_score),
+    ] {
+        if !(0.0..=1.0).contains(&score) {
+            bail!("Model '{}' has {} {} outside 0..=1", id, label, score);
+        }
+    }
+
+    if let Some(digest) = &entry.sha256 {
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            bail!("Model '{}' has a malformed sha256 '{}'", id, digest);
+        }
+    }
+
+    if entry.language_set.is_some() && !entry.supported_languages.is_empty() {
+        bail!(
+            "Model '{}' sets both language_set and supported_languages",
+            id
+        );
+    }
+
+    Ok(())
+}
+
+/// The catalog bundled with this build.
+pub fn bundled() -> Vec<ModelInfo> {
+    parse(BUNDLED_CATALOG).expect("Bundled model catalog is invalid")
+}
+
+pub fn load_file(path: &Path) -> Result<Vec<ModelInfo>> {
+    parse(&read_file(path)?)
+}
+
+/// Parses a manifest that came from a URL. Its models must download from a
+/// trusted host, since the manifest also vouches for their digests.
+pub fn parse_remote(json: &str) -> Result<Vec<ModelInfo>> {
+    let models = parse(json)?;
+    if let Some(model) = models
+        .iter()
+        .find(|m| m.url.as_deref().is_some_and(|url| !is_trusted_host(url)))
+    {
+        bail!(
+            "Model '{}' downloads from an untrusted host: {}",
+            model.id,
+            model.url.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(models)
+}
+
+/// Loads the cached copy of a remote manifest.
+pub fn load_cached(path: &Path) -> Result<Vec<ModelInfo>> {
+    parse_remote(&read_file(path)?)
+}
+
+fn read_file(path: &Path) -> Result<String> {
+    fs::read_to_string(path)
+        .with_context(|| format!("Failed to read model catalog {}", path.display()))
+}
+
+/// Downloads a manifest. It is returned unparsed so the caller can cache the
+/// exact bytes once they've been validated.
+pub async fn fetch(url: &str, network: &NetworkSettings) -> Result<String> {
+    require_https(url)?;
+    let client = crate::http::client_builder(network)?
+        .timeout(Duration::from_secs(30))
+        .build()?;
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        bail!("Failed to fetch model catalog: HTTP {}", response.status());
+    }
+
+    let body = response.bytes().await?;
+    if body.len() > MAX_CATALOG_BYTES {
+        bail!("Model catalog is too large ({} bytes)", body.len());
+    }
+    String::from_utf8(body.to_vec()).context("Model catalog is not valid UTF-8")
+}
+
+/// Builds the model map for a freshly loaded catalog.
+///
+/// Download state is carried over for models that are still listed. Models that
+/// were dropped from the catalog but are installed or mid-download are kept so
+/// they stay usable and deletable. Custom models are left out; the caller
+/// rediscovers them from disk afterwards.
+pub fn merge(
+    catalog: Vec<ModelInfo>,
+    previous: &HashMap<String, ModelInfo>,
+) -> HashMap<String, ModelInfo> {
+    let mut merged: HashMap<String, ModelInfo> = catalog
+        .into_iter()
+        .map(|mut model| {
+            if let Some(old) = previous.get(&model.id) {
+                model.is_downloaded = old.is_downloaded;
+                model.is_downloading = old.is_downloading;
+                model.partial_size = old.partial_size;
+            }
+            (model.id.clone(), model)
+        })
+        .collect();
+
+    for (id, model) in previous {
+        if model.is_custom || merged.contains_key(id) {
+            continue;
+        }
+        if model.is_downloaded || model.is_downloading {
+            merged.insert(id.clone(), model.clone());
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn manifest(models: &str) -> String {
+        format!(
+            r#"{{"version": 1, "language_sets": {{"set": ["en", "de"]}}, "models": [{}]}}"#,
+            models
+        )
+    }
+
+    const ENTRY: &str = r#"{
+        "id": "test", "name": "Test", "filename": "test.bin",
+        "url": "https://example.com/test.bin", "size_mb": 10,
+        "engine_type": "Whisper", "accuracy_score": 0.5, "speed_score": 0.5,
+        "language_set": "set"
+    }"#;
+
+    #[test]
+    fn bundled_catalog_is_valid() {
+        let models = bundled();
+        assert!(models.iter().any(|m| m.id == "small"));
+        assert!(models.iter().any(|m| m.is_recommended));
+        // The published manifest mirrors the bundled one, so it must pass too
+        parse_remote(BUNDLED_CATALOG).unwrap();
+    }
+
+    /// Bundled downloads published before digests were required. Remove an
+    /// id once its `sha256` is in the catalog; new entries must ship with one.
+    const UNPINNED_DOWNLOADS: &[&str] = &[
+        "small",
+        "medium",
+        "turbo",
+        "large",
+        "breeze-asr",
+        "parakeet-tdt-0.6b-v2",
+        "parakeet-tdt-0.6b-v3",
+        "moonshine-base",
+        "sense-voice-int8",
+        "en_US-joy-medium",
+        "en_US-lessac-high",
+        "en_US-arctic-medium",
+        "xtts-v2",
+    ];
+
+    #[test]
+    fn bundled_downloads_have_digests() {
+        for model in bundled().iter().filter(|m| m.url.is_some()) {
+            let unpinned = UNPINNED_DOWNLOADS.contains(&model.id.as_str());
+            match (&model.sha256, unpinned) {
+                (None, false) => panic!("{} has no sha256", model.id),
+                (Some(_), true) => {
+                    panic!("{} has a sha256, drop it from UNPINNED_DOWNLOADS", model.id)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn loads_catalog_from_local_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("catalog.json");
+        fs::write(&path, manifest(ENTRY)).unwrap();
+
+        let models = load_file(&path).unwrap();
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "test");
+        assert_eq!(models[0].supported_languages, vec!["en", "de"]);
+        assert!(!models[0].is_custom);
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let cases = [
+            r#"{"version": 2, "models": []}"#.to_string(),
+            manifest(""),
+            manifest(&format!("{},{}", ENTRY, ENTRY)),
+            manifest(&ENTRY.replace("test.bin\",", "../test.bin\",")),
+            manifest(&ENTRY.replace("https://example.com", "file:///tmp")),
+            manifest(&ENTRY.replace("https://", "http://")),
+            manifest(&ENTRY.replace("\"accuracy_score\": 0.5", "\"accuracy_score\": 1.5")),
+            manifest(&ENTRY.replace("\"set\"", "\"missing\"")),
+            manifest(&ENTRY.replace("Whisper", "Unknown")),
+        ];
+        for case in cases {
+            assert!(parse(&case).is_err(), "accepted {}", case);
+        }
+    }
+
+    #[test]
+    fn remote_catalogs_only_download_from_trusted_hosts() {
+        assert!(parse_remote(&manifest(ENTRY)).is_err());
+        let trusted = ENTRY.replace("example.com", "blob.handy.computer");
+        assert!(parse_remote(&manifest(&trusted)).is_ok());
+        let lookalike = ENTRY.replace("example.com", "blob.handy.computer.example.com");
+        assert!(parse_remote(&manifest(&lookalike)).is_err());
+        // Local catalogs may point at a mirror
+        assert!(parse(&manifest(ENTRY)).is_ok());
+    }
+
+    #[test]
+    fn catalog_urls_must_use_https() {
+        assert!(require_https("https://example.com/catalog.json").is_ok());
+        assert!(require_https("http://example.com/catalog.json").is_err());
+        assert_eq!(
+            CatalogSource::from_setting(Some("http://example.com/catalog.json")),
+            CatalogSource::Remote("http://example.com/catalog.json".to_string())
+        );
+    }
+
+    #[test]
+    fn unset_source_uses_the_bundled_catalog() {
+        assert_eq!(CatalogSource::from_setting(None), CatalogSource::Bundled);
+        assert_eq!(
+            CatalogSource::from_setting(Some("  ")),
+            CatalogSource::Bundled
+        );
+        assert_eq!(
+            CatalogSource::from_setting(Some("Bundled")),
+            CatalogSource::Bundled
+        );
+        assert!(CatalogSource::from_setting(Some("published")).is_published());
+        assert_eq!(
+            CatalogSource::from_setting(Some("https://example.com/catalog.json")),
+            CatalogSource::Remote("https://example.com/catalog.json".to_string())
+        );
+        assert_eq!(
+            CatalogSource::from_setting(Some("/tmp/catalog.json")),
+            CatalogSource::File("/tmp/catalog.json".to_string())
+        );
+    }
+
+    #[test]
+    fn merge_keeps_installed_models_missing_from_catalog() {
+        let mut previous: HashMap<String, ModelInfo> =
+            bundled().into_iter().map(|m| (m.id.clone(), m)).collect();
+        previous.get_mut("small").unwrap().is_downloaded = true;
+        previous.get_mut("medium").unwrap().is_downloaded = true;
+
+        let mut custom = previous["small"].clone();
+        custom.id = "my-model".to_string();
+        custom.is_custom = true;
+        previous.insert(custom.id.clone(), custom);
+
+        let catalog = parse(&manifest(&ENTRY.replace("\"test", "\"small"))).unwrap();
+        let merged = merge(catalog, &previous);
+
+        assert!(merged["small"].is_downloaded);
+        assert_eq!(merged["small"].name, "Test");
+        assert!(merged.contains_key("medium"));
+        assert!(!merged.contains_key("large"));
+        assert!(!merged.contains_key("my-model"));
+    }
+}
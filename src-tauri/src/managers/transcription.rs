@@ -1,9 +1,14 @@
+use crate::audio_toolkit::constants::WHISPER_SAMPLE_RATE;
 use crate::audio_toolkit::{apply_custom_words, filter_transcription_output};
-use crate::managers::model::{EngineType, ModelManager};
-use crate::settings::{get_settings, ModelUnloadTimeout};
+use crate::managers::benchmark::{self, ModelBenchmark};
+use crate::managers::model::{moonshine_variant, EngineType, ModelInfo, ModelManager};
+use crate::managers::whisper_engine::{WhisperEngine, WhisperRequest};
+use crate::settings::{get_settings, AppSettings, ModelUnloadTimeout};
 use anyhow::Result;
 use log::{debug, error, info, warn};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
@@ -11,7 +16,7 @@ use std::time::{Duration, SystemTime};
 use tauri::{AppHandle, Emitter};
 use transcribe_rs::{
     engines::{
-        moonshine::{ModelVariant, MoonshineEngine, MoonshineModelParams},
+        moonshine::{MoonshineEngine, MoonshineModelParams},
         parakeet::{
             ParakeetEngine, ParakeetInferenceParams, ParakeetModelParams, TimestampGranularity,
         },
@@ -19,7 +24,6 @@ use transcribe_rs::{
             Language as SenseVoiceLanguage, SenseVoiceEngine, SenseVoiceInferenceParams,
             SenseVoiceModelParams,
         },
-        whisper::{WhisperEngine, WhisperInferenceParams},
     },
     TranscriptionEngine,
 };
@@ -30,6 +34,21 @@ pub struct TranscribeParams {
     pub translate: Option<bool>,
 }
 
+/// Language a transcription was detected to be in.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Type)]
+pub struct DetectedLanguage {
+    /// ISO 639-1 code as reported by the engine, e.g. "en" or "zh"
+    pub code: String,
+    pub probability: Option<f32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct TranscriptionOutput {
+    pub text: String,
+    /// Only set when the language was auto-detected
+    pub language: Option<DetectedLanguage>,
+}
+
 #[derive(Clone, Debug, Serialize)]
 pub struct ModelStateEvent {
     pub event_type: String,
@@ -56,6 +75,21 @@ pub struct TranscriptionManager {
     watcher_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
     is_loading: Arc<Mutex<bool>>,
     loading_condvar: Arc<Condvar>,
+    /// A model load was requested while a model swap held the engine
+    load_requested: Arc<AtomicBool>,
+    /// Small model loaded next to the main one for `transcribe_quick`
+    quick_engine: Arc<Mutex<Option<(String, LoadedEngine)>>>,
+}
+
+/// Ends a model swap even if the work inside it panics.
+struct SwapGuard<'a>(&'a TranscriptionManager);
+
+impl Drop for SwapGuard<'_> {
+    fn drop(&mut self) {
+        let mut is_loading = self.0.is_loading.lock().unwrap();
+        *is_loading = false;
+        self.0.loading_condvar.notify_all();
+    }
 }
 
 impl TranscriptionManager {
@@ -75,6 +109,8 @@ impl TranscriptionManager {
             watcher_handle: Arc::new(Mutex::new(None)),
             is_loading: Arc::new(Mutex::new(false)),
             loading_condvar: Arc::new(Condvar::new()),
+            load_requested: Arc::new(AtomicBool::new(false)),
+            quick_engine: Arc::new(Mutex::new(None)),
         };
 
         // Start the idle watcher
@@ -153,7 +189,8 @@ impl TranscriptionManager {
             let mut engine = self.engine.lock().unwrap();
             if let Some(ref mut loaded_engine) = *engine {
                 match loaded_engine {
-                    LoadedEngine::Whisper(ref mut e) => e.unload_model(),
+                    // Dropping the engine below frees the whisper context
+                    LoadedEngine::Whisper(_) => {}
                     LoadedEngine::Parakeet(ref mut e) => e.unload_model(),
                     LoadedEngine::Moonshine(ref mut e) => e.unload_model(),
                     LoadedEngine::SenseVoice(ref mut e) => e.unload_model(),
@@ -161,6 +198,7 @@ impl TranscriptionManager {
             }
             *engine = None; // Drop the engine to free memory
         }
+        *self.quick_engine.lock().unwrap() = None;
         {
             let mut current_model = self.current_model_id.lock().unwrap();
             *current_model = None;
@@ -234,92 +272,20 @@ impl TranscriptionManager {
 
         let model_path = self.model_manager.get_model_path(model_id)?;
 
-        // Create appropriate engine based on model type
-        let loaded_engine = match model_info.engine_type {
-            EngineType::Whisper => {
-                let mut engine = WhisperEngine::new();
-                engine.load_model(&model_path).map_err(|e| {
-                    let error_msg = format!("Failed to load whisper model {}: {}", model_id, e);
-                    let _ = self.app_handle.emit(
-                        "model-state-changed",
-                        ModelStateEvent {
-                            event_type: "loading_failed".to_string(),
-                            model_id: Some(model_id.to_string()),
-                            model_name: Some(model_info.name.clone()),
-                            error: Some(error_msg.clone()),
-                        },
-                    );
-                    anyhow::anyhow!(error_msg)
-                })?;
-                LoadedEngine::Whisper(engine)
-            }
-            EngineType::Parakeet => {
-                let mut engine = ParakeetEngine::new();
-                engine
-                    .load_model_with_params(&model_path, ParakeetModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load parakeet model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Parakeet(engine)
-            }
-            EngineType::Moonshine => {
-                let mut engine = MoonshineEngine::new();
-                engine
-                    .load_model_with_params(
-                        &model_path,
-                        MoonshineModelParams::variant(ModelVariant::Base),
-                    )
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load moonshine model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::Moonshine(engine)
-            }
-            EngineType::SenseVoice => {
-                let mut engine = SenseVoiceEngine::new();
-                engine
-                    .load_model_with_params(&model_path, SenseVoiceModelParams::int8())
-                    .map_err(|e| {
-                        let error_msg =
-                            format!("Failed to load SenseVoice model {}: {}", model_id, e);
-                        let _ = self.app_handle.emit(
-                            "model-state-changed",
-                            ModelStateEvent {
-                                event_type: "loading_failed".to_string(),
-                                model_id: Some(model_id.to_string()),
-                                model_name: Some(model_info.name.clone()),
-                                error: Some(error_msg.clone()),
-                            },
-                        );
-                        anyhow::anyhow!(error_msg)
-                    })?;
-                LoadedEngine::SenseVoice(engine)
-            }
-            _ => {
-                return Err(anyhow::anyhow!("Engine type not supported for transcription."));
-            }
-        };
+        let loaded_engine =
+            Self::open_engine(&model_info.engine_type, &model_path).map_err(|e| {
+                let error_msg = format!("Failed to load model {}: {}", model_id, e);
+                let _ = self.app_handle.emit(
+                    "model-state-changed",
+                    ModelStateEvent {
+                        event_type: "loading_failed".to_string(),
+                        model_id: Some(model_id.to_string()),
+                        model_name: Some(model_info.name.clone()),
+                        error: Some(error_msg.clone()),
+                    },
+                );
+                anyhow::anyhow!(error_msg)
+            })?;
 
         // Update the current engine and model ID
         {
@@ -351,10 +317,48 @@ impl TranscriptionManager {
         Ok(())
     }
 
+    /// Creates the engine for a model of `engine_type` stored at `model_path`.
+    fn open_engine(engine_type: &EngineType, model_path: &Path) -> Result<LoadedEngine> {
+        let engine = match engine_type {
+            EngineType::Whisper => LoadedEngine::Whisper(WhisperEngine::load(model_path)?),
+            EngineType::Parakeet => {
+                let mut engine = ParakeetEngine::new();
+                engine
+                    .load_model_with_params(model_path, ParakeetModelParams::int8())
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+                LoadedEngine::Parakeet(engine)
+            }
+            EngineType::Moonshine => {
+                let mut engine = MoonshineEngine::new();
+                engine
+                    .load_model_with_params(
+                        model_path,
+                        MoonshineModelParams::variant(moonshine_variant(model_path)),
+                    )
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+                LoadedEngine::Moonshine(engine)
+            }
+            EngineType::SenseVoice => {
+                let mut engine = SenseVoiceEngine::new();
+                engine
+                    .load_model_with_params(model_path, SenseVoiceModelParams::int8())
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+                LoadedEngine::SenseVoice(engine)
+            }
+            _ => anyhow::bail!("Engine type not supported for transcription."),
+        };
+        Ok(engine)
+    }
+
     /// Kicks off the model loading in a background thread if it's not already loaded
     pub fn initiate_model_load(&self) {
         let mut is_loading = self.is_loading.lock().unwrap();
-        if *is_loading || self.is_model_loaded() {
+        if *is_loading {
+            // A model swap loads one for this request when it finishes
+            self.load_requested.store(true, Ordering::Relaxed);
+            return;
+        }
+        if self.is_model_loaded() {
             return;
         }
 
@@ -376,6 +380,83 @@ impl TranscriptionManager {
         current_model.clone()
     }
 
+    /// Runs `f`, which may load other models, e.g. for a benchmark or a
+    /// re-transcription, and then loads the previous model again. Model loads
+    /// and transcriptions that come in meanwhile wait for the swap to finish,
+    /// so a dictation never runs on a borrowed model or finds none loaded.
+    pub fn with_model_swap<T>(&self, f: impl FnOnce() -> T) -> T {
+        {
+            let mut is_loading = self.is_loading.lock().unwrap();
+            while *is_loading {
+                is_loading = self.loading_condvar.wait(is_loading).unwrap();
+            }
+            *is_loading = true;
+        }
+        let _guard = SwapGuard(self);
+        self.load_requested.store(false, Ordering::Relaxed);
+        let previous_model = self.get_current_model();
+
+        let result = f();
+
+        let restore = match previous_model {
+            Some(model_id) => Some(model_id),
+            // A recording started meanwhile and needs the selected model
+            None if self.load_requested.load(Ordering::Relaxed) => {
+                Some(get_settings(&self.app_handle).selected_model)
+            }
+            None => None,
+        };
+        if self.get_current_model() != restore {
+            let restored = match &restore {
+                Some(model_id) => self.load_model(model_id),
+                None => self.unload_model(),
+            };
+            if let Err(e) = restored {
+                warn!("Failed to restore model after model swap: {}", e);
+            }
+        }
+        result
+    }
+
+    /// Loads `model_id` and measures how long it takes to transcribe `audio`.
+    /// Must run inside `with_model_swap`, which restores the previous model.
+    pub fn benchmark_model(&self, model_id: &str, audio: &[f32]) -> Result<ModelBenchmark> {
+        let load_start = std::time::Instant::now();
+        self.load_model(model_id)?;
+        let load_ms = load_start.elapsed().as_millis() as u64;
+
+        let params = TranscribeParams {
+            language: None,
+            translate: Some(false),
+        };
+
+        // The first inference pays for one-off setup, so warm up on a second of audio
+        let warm_up = audio.len().min(WHISPER_SAMPLE_RATE as usize);
+        self.transcribe_during_swap(audio[..warm_up].to_vec(), Some(params.clone()))?;
+        if !self.is_model_loaded() {
+            // "Unload immediately" dropped it after the warm-up
+            self.load_model(model_id)?;
+        }
+
+        let transcribe_start = std::time::Instant::now();
+        self.transcribe_during_swap(audio.to_vec(), Some(params))?;
+        let elapsed = transcribe_start.elapsed();
+
+        let audio_secs = audio.len() as f64 / WHISPER_SAMPLE_RATE as f64;
+        let benchmark = ModelBenchmark {
+            model_id: model_id.to_string(),
+            real_time_factor: elapsed.as_secs_f64() / audio_secs.max(f64::EPSILON),
+            load_ms,
+            transcribe_ms: elapsed.as_millis() as u64,
+            timestamp: chrono::Utc::now().timestamp(),
+        };
+        info!(
+            "Benchmarked {}: RTF {:.3} ({}ms load, {}ms for {:.1}s of audio)",
+            model_id, benchmark.real_time_factor, load_ms, benchmark.transcribe_ms, audio_secs
+        );
+        Ok(benchmark)
+    }
+
     pub fn transcribe(&self, audio: Vec<f32>) -> Result<String> {
         self.transcribe_with_params(audio, None)
     }
@@ -385,6 +466,149 @@ impl TranscriptionManager {
         audio: Vec<f32>,
         custom_params: Option<TranscribeParams>,
     ) -> Result<String> {
+        self.transcribe_detailed(audio, custom_params)
+            .map(|output| output.text)
+    }
+
+    /// Transcribes a short clip with the smallest downloaded speech-to-text
+    /// model, loaded next to the main one, for checks like the hands-free
+    /// wake phrase that shouldn't wait on a large model. Uses the main model
+    /// when no smaller one is downloaded. Custom words aren't applied.
+    pub fn transcribe_quick(&self, audio: Vec<f32>) -> Result<String> {
+        let settings = get_settings(&self.app_handle);
+        let Some(model) = self.quick_model(&settings) else {
+            return self.transcribe(audio);
+        };
+
+        let mut quick_engine = self.quick_engine.lock().unwrap();
+        if quick_engine.as_ref().map(|(id, _)| id) != Some(&model.id) {
+            debug!("Loading {} for quick transcriptions", model.id);
+            let model_path = self.model_manager.get_model_path(&model.id)?;
+            *quick_engine = Some((
+                model.id.clone(),
+                Self::open_engine(&model.engine_type, &model_path)?,
+            ));
+        }
+        let (_, engine) = quick_engine.as_mut().unwrap();
+
+        let language = Some(settings.selected_language.as_str()).filter(|l| *l != "auto");
+        Self::run_engine(engine, audio, language, false, &settings).map(|(text, _)| text)
+    }
+
+    /// The smallest downloaded speech-to-text model that is smaller than the
+    /// selected one and covers the selected language.
+    fn quick_model(&self, settings: &AppSettings) -> Option<ModelInfo> {
+        let models = self.model_manager.get_available_models();
+        let selected_size = models
+            .iter()
+            .find(|m| m.id == settings.selected_model)
+            .map(|m| m.size_mb)?;
+        let language = settings.selected_language.as_str();
+
+        models
+            .into_iter()
+            .filter(|m| m.is_downloaded && benchmark::is_benchmarkable(m))
+            .filter(|m| m.size_mb < selected_size)
+            .filter(|m| {
+                language == "auto"
+                    || m.supported_languages.is_empty()
+                    || m.supported_languages.iter().any(|l| l == language)
+            })
+            .min_by_key(|m| m.size_mb)
+    }
+
+    /// Like `transcribe_with_params`, but also returns the language the engine
+    /// detected when none was requested.
+    pub fn transcribe_detailed(
+        &self,
+        audio: Vec<f32>,
+        custom_params: Option<TranscribeParams>,
+    ) -> Result<TranscriptionOutput> {
+        self.run_transcription(audio, custom_params, true)
+    }
+
+    /// Transcribes with whatever model is loaded, for use inside
+    /// `with_model_swap`, where waiting for loads would never finish.
+    pub fn transcribe_during_swap(
+        &self,
+        audio: Vec<f32>,
+        custom_params: Option<TranscribeParams>,
+    ) -> Result<TranscriptionOutput> {
+        self.run_transcription(audio, custom_params, false)
+    }
+
+    /// Runs `audio` through `engine`, returning the raw text and the language
+    /// Whisper detected, if any.
+    fn run_engine(
+        engine: &mut LoadedEngine,
+        audio: Vec<f32>,
+        requested_language: Option<&str>,
+        requested_translate: bool,
+        settings: &AppSettings,
+    ) -> Result<(String, Option<DetectedLanguage>)> {
+        let transcript = match engine {
+            LoadedEngine::Whisper(whisper_engine) => {
+                let initial_prompt = settings.whisper_decoding.prompt(&settings.custom_words);
+                let request = WhisperRequest {
+                    language: requested_language,
+                    translate: requested_translate,
+                    allowed_languages: &settings.auto_detect_languages,
+                    initial_prompt: initial_prompt.as_deref(),
+                    decoding: &settings.whisper_decoding,
+                };
+
+                let transcript = whisper_engine
+                    .transcribe(&audio, &request)
+                    .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?;
+                (transcript.text, transcript.language)
+            }
+            LoadedEngine::Parakeet(parakeet_engine) => {
+                let params = ParakeetInferenceParams {
+                    timestamp_granularity: TimestampGranularity::Segment,
+                    ..Default::default()
+                };
+                let text = parakeet_engine
+                    .transcribe_samples(audio, Some(params))
+                    .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
+                    .text;
+                (text, None)
+            }
+            LoadedEngine::Moonshine(moonshine_engine) => {
+                let text = moonshine_engine
+                    .transcribe_samples(audio, None)
+                    .map_err(|e| anyhow::anyhow!("Moonshine transcription failed: {}", e))?
+                    .text;
+                (text, None)
+            }
+            LoadedEngine::SenseVoice(sense_voice_engine) => {
+                let language = match requested_language.unwrap_or("auto") {
+                    "zh" | "zh-Hans" | "zh-Hant" => SenseVoiceLanguage::Chinese,
+                    "en" => SenseVoiceLanguage::English,
+                    "ja" => SenseVoiceLanguage::Japanese,
+                    "ko" => SenseVoiceLanguage::Korean,
+                    "yue" => SenseVoiceLanguage::Cantonese,
+                    _ => SenseVoiceLanguage::Auto,
+                };
+                let params = SenseVoiceInferenceParams {
+                    language,
+                    use_itn: true,
+                };
+                let text = sense_voice_engine
+                    .transcribe_samples(audio, Some(params))
+                    .map_err(|e| anyhow::anyhow!("SenseVoice transcription failed: {}", e))?
+                    .text;
+                (text, None)
+            }
+        };
+        Ok(transcript)
+    }
+
+    fn run_transcription(
+        &self,
+        audio: Vec<f32>,
+        custom_params: Option<TranscribeParams>,
+        wait_for_load: bool,
+    ) -> Result<TranscriptionOutput> {
         // Update last activity timestamp
         self.last_activity.store(
             SystemTime::now()
@@ -401,14 +625,17 @@ impl TranscriptionManager {
         if audio.is_empty() {
             debug!("Empty audio vector");
             self.maybe_unload_immediately("empty audio");
-            return Ok(String::new());
+            return Ok(TranscriptionOutput {
+                text: String::new(),
+                language: None,
+            });
         }
 
         // Check if model is loaded, if not try to load it
         {
             // If the model is loading, wait for it to complete.
             let mut is_loading = self.is_loading.lock().unwrap();
-            while *is_loading {
+            while wait_for_load && *is_loading {
                 is_loading = self.loading_condvar.wait(is_loading).unwrap();
             }
 
@@ -436,81 +663,31 @@ impl TranscriptionManager {
         };
 
         // Perform transcription with the appropriate engine
-        let result = {
+        let (result, detected_language) = {
             let mut engine_guard = self.engine.lock().unwrap();
             let engine = engine_guard.as_mut().ok_or_else(|| {
                 anyhow::anyhow!(
                     "Model failed to load after auto-load attempt. Please check your model settings."
                 )
             })?;
-
-            match engine {
-                LoadedEngine::Whisper(whisper_engine) => {
-                    // Normalize language code for Whisper
-                    // Convert zh-Hans and zh-Hant to zh since Whisper uses ISO 639-1 codes
-                    let whisper_language = if let Some(lang) = &requested_language {
-                        let normalized = if lang == "zh-Hans" || lang == "zh-Hant" {
-                            "zh".to_string()
-                        } else {
-                            lang.clone()
-                        };
-                        Some(normalized)
-                    } else {
-                        None
-                    };
-
-                    let params = WhisperInferenceParams {
-                        language: whisper_language,
-                        translate: requested_translate,
-                        ..Default::default()
-                    };
-
-                    whisper_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Whisper transcription failed: {}", e))?
-                }
-                LoadedEngine::Parakeet(parakeet_engine) => {
-                    let params = ParakeetInferenceParams {
-                        timestamp_granularity: TimestampGranularity::Segment,
-                        ..Default::default()
-                    };
-                    parakeet_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("Parakeet transcription failed: {}", e))?
-                }
-                LoadedEngine::Moonshine(moonshine_engine) => moonshine_engine
-                    .transcribe_samples(audio, None)
-                    .map_err(|e| anyhow::anyhow!("Moonshine transcription failed: {}", e))?,
-                LoadedEngine::SenseVoice(sense_voice_engine) => {
-                    let lang_str = requested_language.as_deref().unwrap_or("auto");
-                    let language = match lang_str {
-                        "zh" | "zh-Hans" | "zh-Hant" => SenseVoiceLanguage::Chinese,
-                        "en" => SenseVoiceLanguage::English,
-                        "ja" => SenseVoiceLanguage::Japanese,
-                        "ko" => SenseVoiceLanguage::Korean,
-                        "yue" => SenseVoiceLanguage::Cantonese,
-                        _ => SenseVoiceLanguage::Auto,
-                    };
-                    let params = SenseVoiceInferenceParams {
-                        language,
-                        use_itn: true,
-                    };
-                    sense_voice_engine
-                        .transcribe_samples(audio, Some(params))
-                        .map_err(|e| anyhow::anyhow!("SenseVoice transcription failed: {}", e))?
-                }
-            }
+            Self::run_engine(
+                engine,
+                audio,
+                requested_language.as_deref(),
+                requested_translate,
+                &settings,
+            )?
         };
 
         // Apply word correction if custom words are configured
         let corrected_result = if !settings.custom_words.is_empty() {
             apply_custom_words(
-                &result.text,
+                &result,
                 &settings.custom_words,
                 settings.word_correction_threshold,
             )
         } else {
-            result.text
+            result
         };
 
         // Filter out filler words and hallucinations
@@ -527,6 +704,13 @@ impl TranscriptionManager {
             (et - st).as_millis(),
             translation_note
         );
+        if let Some(language) = &detected_language {
+            info!(
+                "Detected language: {} ({:.2})",
+                language.code,
+                language.probability.unwrap_or_default()
+            );
+        }
 
         let final_result = filtered_result;
 
@@ -538,7 +722,10 @@ impl TranscriptionManager {
 
         self.maybe_unload_immediately("transcription");
 
-        Ok(final_result)
+        Ok(TranscriptionOutput {
+            text: final_result,
+            language: detected_language,
+        })
     }
 }
 
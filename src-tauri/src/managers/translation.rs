@@ -39,7 +39,9 @@ impl TranslationManager {
     pub async fn stop_capture_and_translate(&self, target_lang: String) -> Result<(String, String)> {
         info!("Stopping translation capture and translating to {}", target_lang);
         
-        let samples = self.audio_manager.stop_recording("translation")
+        let samples = self.audio_manager.stop_recording("translation");
+        crate::hands_free::rearm_if_enabled(&self.app_handle);
+        let samples = samples
             .ok_or_else(|| anyhow::anyhow!("No audio samples captured for translation"))?;
 
         if samples.is_empty() {
@@ -83,7 +85,7 @@ impl TranslationManager {
                  );
                  
                  let start = std::time::Instant::now();
-                 let result = crate::llm_client::send_chat_completion(p, api_key, &model, prompt).await;
+                 let result = crate::llm_client::send_chat_completion(p, api_key, &model, prompt, &settings.network).await;
                  let duration = start.elapsed();
 
                  match result {
@@ -288,8 +288,10 @@ impl TtsManager {
 
             info!("Starting TTS service at {:?}", tts_path);
 
+            let network = crate::settings::get_settings(&app_handle).network;
             let mut cmd = Command::new(Self::get_python_command());
             cmd.arg(&tts_path)
+                .envs(crate::http::child_env(&network))
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
 
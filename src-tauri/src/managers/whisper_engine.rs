@@ -0,0 +1,180 @@
+use crate::managers::transcription::DetectedLanguage;
+use crate::settings::WhisperDecoding;
+use anyhow::{Context, Result};
+use log::debug;
+use std::path::Path;
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+};
+
+/// Whisper engine built directly on whisper-rs. transcribe-rs's Whisper engine
+/// keeps the language Whisper detects to itself, so auto-detection runs here
+/// and its result is returned with the text.
+pub struct WhisperEngine {
+    context: WhisperContext,
+    state: WhisperState,
+}
+
+pub struct WhisperRequest<'a> {
+    /// Language to transcribe in; `None` detects it
+    pub language: Option<&'a str>,
+    pub translate: bool,
+    /// Languages auto-detection may pick from; empty allows all of them
+    pub allowed_languages: &'a [String],
+    pub initial_prompt: Option<&'a str>,
+    pub decoding: &'a WhisperDecoding,
+}
+
+pub struct WhisperTranscript {
+    pub text: String,
+    /// Set when the language was detected rather than requested
+    pub language: Option<DetectedLanguage>,
+}
+
+/// Maps an app language code to Whisper's ISO 639-1 code. Whisper doesn't
+/// distinguish Chinese scripts, so zh-Hans and zh-Hant are both "zh".
+pub fn whisper_language_code(language: &str) -> &str {
+    match language {
+        "zh-Hans" | "zh-Hant" => "zh",
+        other => other,
+    }
+}
+
+/// The most likely language among `allowed` (all when empty), with its probability.
+fn pick_language<'a>(candidates: &[(&'a str, f32)], allowed: &[String]) -> Option<(&'a str, f32)> {
+    candidates
+        .iter()
+        .filter(|(code, _)| {
+            allowed.is_empty() || allowed.iter().any(|a| whisper_language_code(a) == *code)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .copied()
+}
+
+fn threads(decoding: &WhisperDecoding) -> usize {
+    match decoding.threads {
+        Some(threads) if threads > 0 => threads as usize,
+        _ => std::thread::available_parallelism()
+            .map(|n| n.get().min(8))
+            .unwrap_or(4),
+    }
+}
+
+fn sampling_strategy(decoding: &WhisperDecoding) -> SamplingStrategy {
+    if decoding.beam_size > 1 {
+        SamplingStrategy::BeamSearch {
+            beam_size: decoding.beam_size as i32,
+            // Not implemented by whisper.cpp
+            patience: -1.0,
+        }
+    } else {
+        SamplingStrategy::Greedy { best_of: 1 }
+    }
+}
+
+impl WhisperEngine {
+    pub fn load(model_path: &Path) -> Result<Self> {
+        let path = model_path
+            .to_str()
+            .context("Model path is not valid UTF-8")?;
+        let context = WhisperContext::new_with_params(path, WhisperContextParameters::default())?;
+        let state = context.create_state()?;
+        Ok(Self { context, state })
+    }
+
+    fn detect_language(
+        &mut self,
+        samples: &[f32],
+        allowed: &[String],
+        threads: usize,
+    ) -> Result<Option<DetectedLanguage>> {
+        self.state.pcm_to_mel(samples, threads)?;
+        let (_, probabilities) = self.state.lang_detect(0, threads)?;
+
+        let candidates: Vec<(&str, f32)> = probabilities
+            .iter()
+            .enumerate()
+            .filter_map(|(id, &p)| whisper_rs::get_lang_str(id as i32).map(|code| (code, p)))
+            .collect();
+
+        Ok(
+            pick_language(&candidates, allowed).map(|(code, probability)| {
+                debug!("Detected language {} (p={:.2})", code, probability);
+                DetectedLanguage {
+                    code: code.to_string(),
+                    probability: Some(probability),
+                }
+            }),
+        )
+    }
+
+    pub fn transcribe(
+        &mut self,
+        samples: &[f32],
+        request: &WhisperRequest,
+    ) -> Result<WhisperTranscript> {
+        let threads = threads(request.decoding);
+
+        let detected = match request.language {
+            None if self.context.is_multilingual() => {
+                self.detect_language(samples, request.allowed_languages, threads)?
+            }
+            _ => None,
+        };
+        let language = match (request.language, &detected) {
+            (Some(language), _) => Some(whisper_language_code(language)),
+            (None, Some(detected)) => Some(detected.code.as_str()),
+            // English-only models don't detect anything
+            (None, None) if !self.context.is_multilingual() => Some("en"),
+            (None, None) => None,
+        };
+
+        let mut params = FullParams::new(sampling_strategy(request.decoding));
+        params.set_language(language);
+        params.set_translate(request.translate);
+        params.set_n_threads(threads as i32);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_temperature(request.decoding.temperature);
+        params.set_temperature_inc(request.decoding.temperature_increment);
+        params.set_no_speech_thold(request.decoding.no_speech_threshold);
+        if let Some(prompt) = request.initial_prompt {
+            params.set_initial_prompt(prompt);
+        }
+
+        self.state.full(params, samples)?;
+
+        let mut text = String::new();
+        for segment in 0..self.state.full_n_segments()? {
+            text.push_str(&self.state.full_get_segment_text_lossy(segment)?);
+        }
+
+        Ok(WhisperTranscript {
+            text: text.trim().to_string(),
+            language: detected,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANDIDATES: &[(&str, f32)] = &[("en", 0.2), ("de", 0.5), ("zh", 0.3)];
+
+    #[test]
+    fn picks_most_likely_language() {
+        assert_eq!(pick_language(CANDIDATES, &[]), Some(("de", 0.5)));
+    }
+
+    #[test]
+    fn restricts_detection_to_allowed_languages() {
+        let allowed = vec!["en".to_string(), "zh-Hant".to_string()];
+        assert_eq!(pick_language(CANDIDATES, &allowed), Some(("zh", 0.3)));
+
+        let unknown = vec!["xx".to_string()];
+        assert_eq!(pick_language(CANDIDATES, &unknown), None);
+    }
+}
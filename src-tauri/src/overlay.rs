@@ -363,3 +363,10 @@ pub fn emit_levels(app_handle: &AppHandle, levels: &Vec<f32>) {
         let _ = overlay_window.emit("mic-level", levels);
     }
 }
+
+/// Shows the seconds left before a recording stops on its own, or clears it
+pub fn emit_countdown(app_handle: &AppHandle, seconds: Option<u64>) {
+    if let Some(overlay_window) = app_handle.get_webview_window("recording_overlay") {
+        let _ = overlay_window.emit("recording-countdown", seconds);
+    }
+}
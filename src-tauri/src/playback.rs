@@ -0,0 +1,221 @@
+//! The `recording://` protocol the UI plays recordings through. Recordings
+//! are decrypted and decoded in memory, so playing one never leaves a
+//! plaintext copy on disk, and range requests are served so players can seek.
+
+use log::warn;
+use once_cell::sync::Lazy;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager};
+
+use crate::managers::history::HistoryManager;
+
+pub const SCHEME: &str = "recording";
+
+/// How long the decrypted audio of the last recording is kept after its
+/// last request.
+const CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Most bytes sent for an open-ended range; players ask for the rest.
+const MAX_RANGE_BYTES: usize = 1024 * 1024;
+
+/// A recording kept in memory, as players fetch a file in several ranges.
+struct Served {
+    file_name: String,
+    modified: Option<SystemTime>,
+    audio: Arc<Vec<u8>>,
+    last_used: Instant,
+}
+
+static LAST_SERVED: Lazy<Mutex<Option<Served>>> = Lazy::new(|| Mutex::new(None));
+
+/// Drops the cached recording, e.g. once history is locked or deleted.
+pub fn clear_cache() {
+    *LAST_SERVED.lock().unwrap() = None;
+}
+
+/// Answers a `recording://localhost/<file name>` request.
+pub fn handle_request(app: &AppHandle, request: &Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let Some(file_name) = file_name_from_path(request.uri().path()) else {
+        return error_response(StatusCode::BAD_REQUEST);
+    };
+
+    let history_manager = app.state::<Arc<HistoryManager>>();
+    let audio = match load_audio(&history_manager, &file_name) {
+        Ok(audio) => audio,
+        Err(e) => {
+            warn!("Failed to open recording {} for playback: {}", file_name, e);
+            return error_response(StatusCode::NOT_FOUND);
+        }
+    };
+
+    let builder = Response::builder()
+        // Recordings play as WAV, compressed ones decoded by `playback_audio`
+        .header(header::CONTENT_TYPE, "audio/wav")
+        .header(header::ACCEPT_RANGES, "bytes")
+        // The UI fetches recordings from its own origin
+        .header(header::ACCESS_CONTROL_ALLOW_ORIGIN, "*");
+    let range = request
+        .headers()
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok());
+    let response = match range.map(|range| parse_range(range, audio.len(), MAX_RANGE_BYTES)) {
+        None => builder.body(audio.to_vec()),
+        Some(Some((start, end))) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", start, end, audio.len()),
+            )
+            .body(audio[start..=end].to_vec()),
+        Some(None) => builder
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", audio.len()))
+            .body(Vec::new()),
+    };
+    response.unwrap_or_else(|_| error_response(StatusCode::INTERNAL_SERVER_ERROR))
+}
+
+fn load_audio(history_manager: &HistoryManager, file_name: &str) -> anyhow::Result<Arc<Vec<u8>>> {
+    let modified = std::fs::metadata(history_manager.get_audio_file_path(file_name))?
+        .modified()
+        .ok();
+    let mut last = LAST_SERVED.lock().unwrap();
+    if let Some(served) = last.as_mut() {
+        if served.file_name == file_name && served.modified == modified {
+            served.last_used = Instant::now();
+            return Ok(served.audio.clone());
+        }
+    }
+
+    let audio = Arc::new(history_manager.playback_audio(file_name)?);
+    let replaced = last.replace(Served {
+        file_name: file_name.to_string(),
+        modified,
+        audio: audio.clone(),
+        last_used: Instant::now(),
+    });
+    // A recording already cached has an expiry task running
+    if replaced.is_none() {
+        tauri::async_runtime::spawn(expire_cache());
+    }
+    Ok(audio)
+}
+
+/// Waits until the cached recording has gone unused for `CACHE_TTL`, then
+/// drops it so decrypted audio doesn't stay in memory.
+async fn expire_cache() {
+    loop {
+        let wait = {
+            let mut last = LAST_SERVED.lock().unwrap();
+            let Some(served) = last.as_ref() else {
+                return;
+            };
+            let idle = served.last_used.elapsed();
+            if idle >= CACHE_TTL {
+                *last = None;
+                return;
+            }
+            CACHE_TTL - idle
+        };
+        tokio::time::sleep(wait).await;
+    }
+}
+
+fn error_response(status: StatusCode) -> Response<Vec<u8>> {
+    let mut response = Response::new(Vec::new());
+    *response.status_mut() = status;
+    response
+}
+
+/// The percent-decoded file name in a request path. Anything that could
+/// reach outside the recordings directory is rejected.
+fn file_name_from_path(path: &str) -> Option<String> {
+    let encoded = path.strip_prefix('/').unwrap_or(path).as_bytes();
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%' {
+            let hex = std::str::from_utf8(encoded.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(encoded[i]);
+            i += 1;
+        }
+    }
+
+    let file_name = String::from_utf8(decoded).ok()?;
+    let is_plain_name =
+        !file_name.is_empty() && file_name != ".." && !file_name.contains(['/', '\\', '\0']);
+    is_plain_name.then_some(file_name)
+}
+
+/// Resolves a single `bytes=` range against a body of `len` bytes into
+/// inclusive offsets, sending at most `max_open` bytes of an open-ended
+/// range. `None` means the range can't be satisfied.
+fn parse_range(range: &str, len: usize, max_open: usize) -> Option<(usize, usize)> {
+    let (start, end) = range.trim().strip_prefix("bytes=")?.split_once('-')?;
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", suffix) => {
+            let suffix: usize = suffix.parse().ok()?;
+            (len.checked_sub(suffix.min(len))?, len.checked_sub(1)?)
+        }
+        (start, "") => {
+            let start: usize = start.parse().ok()?;
+            let last = len.checked_sub(1)?;
+            (start, last.min(start.saturating_add(max_open.max(1) - 1)))
+        }
+        (start, end) => (
+            start.parse().ok()?,
+            end.parse::<usize>().ok()?.min(len.checked_sub(1)?),
+        ),
+    };
+    (start <= end && end < len).then_some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_resolve_to_inclusive_offsets() {
+        assert_eq!(parse_range("bytes=0-", 100, 1000), Some((0, 99)));
+        assert_eq!(parse_range("bytes=10-19", 100, 1000), Some((10, 19)));
+        assert_eq!(parse_range("bytes=90-500", 100, 1000), Some((90, 99)));
+        assert_eq!(parse_range("bytes=-10", 100, 1000), Some((90, 99)));
+        assert_eq!(parse_range("bytes=-500", 100, 1000), Some((0, 99)));
+
+        assert_eq!(parse_range("bytes=100-", 100, 1000), None);
+        assert_eq!(parse_range("bytes=20-10", 100, 1000), None);
+        assert_eq!(parse_range("bytes=0-", 0, 1000), None);
+        assert_eq!(parse_range("items=0-10", 100, 1000), None);
+    }
+
+    #[test]
+    fn open_ended_ranges_are_capped() {
+        assert_eq!(parse_range("bytes=0-", 100, 10), Some((0, 9)));
+        assert_eq!(parse_range("bytes=95-", 100, 10), Some((95, 99)));
+        // Explicit ranges are served in full
+        assert_eq!(parse_range("bytes=0-49", 100, 10), Some((0, 49)));
+    }
+
+    #[test]
+    fn request_paths_decode_to_plain_file_names() {
+        assert_eq!(
+            file_name_from_path("/typezero-1700000000.wav"),
+            Some("typezero-1700000000.wav".to_string())
+        );
+        assert_eq!(
+            file_name_from_path("/meeting%201.opus"),
+            Some("meeting 1.opus".to_string())
+        );
+
+        assert_eq!(file_name_from_path("/..%2Fhistory.db"), None);
+        assert_eq!(file_name_from_path("/%2E%2E"), None);
+        assert_eq!(file_name_from_path("/a%5Cb.wav"), None);
+        assert_eq!(file_name_from_path("/bad%2"), None);
+        assert_eq!(file_name_from_path("/"), None);
+    }
+}
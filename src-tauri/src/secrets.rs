@@ -0,0 +1,124 @@
+//! Secrets kept in the OS credential store: the Keychain on macOS, the
+//! Credential Manager on Windows and the Secret Service on Linux.
+//!
+//! Lookups are cached for the lifetime of the process, as the credential
+//! store can be slow and settings are read often. So is a failure: once the
+//! store errors, e.g. with no Secret Service running, it isn't asked again.
+//! Tests use an in-memory stand-in instead of the real store.
+
+use anyhow::{bail, Result};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Service name the secrets are filed under, matching the app identifier.
+#[cfg(not(test))]
+const SERVICE: &str = "com.pais.typezero";
+
+/// Known secrets, with `None` for ones known to be absent.
+static CACHE: Lazy<Mutex<HashMap<String, Option<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Set when the credential store fails, for the rest of the session.
+static UNAVAILABLE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the credential store has failed this session.
+pub fn is_unavailable() -> bool {
+    UNAVAILABLE.load(Ordering::Relaxed)
+}
+
+/// Runs `op` against the store unless it already failed, remembering a failure.
+fn with_store<T>(op: impl FnOnce() -> Result<T>) -> Result<T> {
+    if is_unavailable() {
+        bail!("The keychain is unavailable");
+    }
+    op().inspect_err(|_| UNAVAILABLE.store(true, Ordering::Relaxed))
+}
+
+pub fn get(name: &str) -> Result<Option<String>> {
+    if let Some(cached) = CACHE.lock().unwrap().get(name) {
+        return Ok(cached.clone());
+    }
+    let secret = with_store(|| store::get(name))?;
+    CACHE
+        .lock()
+        .unwrap()
+        .insert(name.to_string(), secret.clone());
+    Ok(secret)
+}
+
+pub fn set(name: &str, secret: &str) -> Result<()> {
+    if get(name).ok().flatten().as_deref() == Some(secret) {
+        return Ok(());
+    }
+    with_store(|| store::set(name, secret))?;
+    CACHE
+        .lock()
+        .unwrap()
+        .insert(name.to_string(), Some(secret.to_string()));
+    Ok(())
+}
+
+/// Removes a secret; a missing one is not an error.
+pub fn delete(name: &str) -> Result<()> {
+    if matches!(CACHE.lock().unwrap().get(name), Some(None)) {
+        return Ok(());
+    }
+    with_store(|| store::delete(name))?;
+    CACHE.lock().unwrap().insert(name.to_string(), None);
+    Ok(())
+}
+
+#[cfg(not(test))]
+mod store {
+    use super::SERVICE;
+    use anyhow::{Context, Result};
+    use keyring::Entry;
+
+    fn entry(name: &str) -> Result<Entry> {
+        Entry::new(SERVICE, name)
+            .with_context(|| format!("Failed to access keychain entry {}", name))
+    }
+
+    pub fn get(name: &str) -> Result<Option<String>> {
+        match entry(name)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {} from the keychain", name)),
+        }
+    }
+
+    pub fn set(name: &str, secret: &str) -> Result<()> {
+        entry(name)?
+            .set_password(secret)
+            .with_context(|| format!("Failed to store {} in the keychain", name))
+    }
+
+    pub fn delete(name: &str) -> Result<()> {
+        match entry(name)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to remove {} from the keychain", name))
+            }
+        }
+    }
+}
+
+/// Stand-in for tests: the cache alone holds the secrets.
+#[cfg(test)]
+mod store {
+    use anyhow::Result;
+
+    pub fn get(_name: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    pub fn set(_name: &str, _secret: &str) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn delete(_name: &str) -> Result<()> {
+        Ok(())
+    }
+}
@@ -17,6 +17,7 @@ use crate::ManagedToggleState;
 /// This function contains the shared logic for:
 /// - Looking up the action in ACTION_MAP
 /// - Handling the cancel binding (only fires when recording)
+/// - Handling the undo binding (fires once per key release)
 /// - Handling push-to-talk mode (start on press, stop on release)
 /// - Handling toggle mode (toggle state on press only)
 ///
@@ -50,6 +51,15 @@ pub fn handle_shortcut_event(
         return;
     }
 
+    // Undo binding: fires on release, so the undo keystrokes it sends aren't
+    // combined with the shortcut's keys, and ignores push-to-talk/toggle mode
+    if binding_id == "undo_last_dictation" {
+        if !is_pressed {
+            action.start(app, binding_id, hotkey_string);
+        }
+        return;
+    }
+
     // Push-to-talk mode: start on press, stop on release
     if settings.push_to_talk {
         if is_pressed {
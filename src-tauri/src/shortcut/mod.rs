@@ -74,6 +74,9 @@ pub fn unregister_cancel_shortcut(app: &AppHandle) {
 
 /// Register a shortcut using the appropriate implementation
 pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    if binding.is_unbound() {
+        return Ok(());
+    }
     let settings = get_settings(app);
     match settings.keyboard_implementation {
         KeyboardImplementation::Tauri => tauri_impl::register_shortcut(app, binding),
@@ -83,6 +86,9 @@ pub fn register_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<()
 
 /// Unregister a shortcut using the appropriate implementation
 pub fn unregister_shortcut(app: &AppHandle, binding: ShortcutBinding) -> Result<(), String> {
+    if binding.is_unbound() {
+        return Ok(());
+    }
     let settings = get_settings(app);
     match settings.keyboard_implementation {
         KeyboardImplementation::Tauri => tauri_impl::unregister_shortcut(app, binding),
@@ -203,6 +209,22 @@ pub fn change_binding(
 #[specta::specta]
 pub fn reset_binding(app: AppHandle, id: String) -> Result<BindingResponse, String> {
     let binding = settings::get_stored_binding(&app, &id);
+    if binding.default_binding.trim().is_empty() {
+        // Shortcuts that ship unbound are reset by unbinding them
+        if let Err(e) = unregister_shortcut(&app, binding.clone()) {
+            error!("reset_binding error for id '{}': {}", id, e);
+        }
+        let mut settings = settings::get_settings(&app);
+        let mut updated_binding = binding;
+        updated_binding.current_binding = String::new();
+        settings.bindings.insert(id, updated_binding.clone());
+        settings::write_settings(&app, settings);
+        return Ok(BindingResponse {
+            success: true,
+            binding: Some(updated_binding),
+            error: None,
+        });
+    }
     change_binding(app, id, binding.default_binding)
 }
 
@@ -358,7 +380,7 @@ fn unregister_all_shortcuts(app: &AppHandle, implementation: KeyboardImplementat
 
     for (id, binding) in bindings {
         // Skip cancel shortcut as it's dynamically registered
-        if id == "cancel" {
+        if id == "cancel" || binding.is_unbound() {
             continue;
         }
 
@@ -401,6 +423,9 @@ fn register_all_shortcuts_for_implementation(
             .get(id)
             .cloned()
             .unwrap_or_else(|| default_binding.clone());
+        if binding.is_unbound() {
+            continue;
+        }
 
         // Validate the shortcut for the target implementation
         if let Err(e) =
@@ -531,6 +556,36 @@ pub fn change_selected_language_setting(app: AppHandle, language: String) -> Res
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_auto_detect_languages_setting(
+    app: AppHandle,
+    languages: Vec<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.auto_detect_languages = languages;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_auto_chinese_variant_setting(
+    app: AppHandle,
+    variant: Option<String>,
+) -> Result<(), String> {
+    if let Some(variant) = &variant {
+        if variant != "zh-Hans" && variant != "zh-Hant" {
+            return Err(format!("Invalid Chinese variant: {}", variant));
+        }
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.auto_chinese_variant = variant;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_overlay_position_setting(app: AppHandle, position: String) -> Result<(), String> {
@@ -877,6 +932,10 @@ pub fn change_post_process_api_key_setting(
     provider_id: String,
     api_key: String,
 ) -> Result<(), String> {
+    // The UI only ever sees redacted keys; sending one back changes nothing
+    if api_key == settings::REDACTED_API_KEY {
+        return Ok(());
+    }
     let mut settings = settings::get_settings(&app);
     validate_provider_exists(&settings, &provider_id)?;
     settings.post_process_api_keys.insert(provider_id, api_key);
@@ -980,6 +1039,11 @@ pub fn delete_post_process_prompt(app: AppHandle, id: String) -> Result<(), Stri
             settings.post_process_prompts.first().map(|p| p.id.clone());
     }
 
+    // Languages routed to the deleted prompt fall back to the selected one
+    settings
+        .post_process_language_prompts
+        .retain(|_, prompt_id| prompt_id != &id);
+
     settings::write_settings(&app, settings);
     Ok(())
 }
@@ -1026,7 +1090,7 @@ pub async fn fetch_post_process_models(
         ));
     }
 
-    crate::llm_client::fetch_models(provider, api_key).await
+    crate::llm_client::fetch_models(provider, api_key, &settings.network).await
 }
 
 #[tauri::command]
@@ -1044,6 +1108,33 @@ pub fn set_post_process_selected_prompt(app: AppHandle, id: String) -> Result<()
     Ok(())
 }
 
+/// Routes transcriptions detected as `language` to a prompt, or removes the
+/// route when `id` is `None`.
+#[tauri::command]
+#[specta::specta]
+pub fn set_post_process_language_prompt(
+    app: AppHandle,
+    language: String,
+    id: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+
+    match id {
+        Some(id) => {
+            if !settings.post_process_prompts.iter().any(|p| p.id == id) {
+                return Err(format!("Prompt with id '{}' not found", id));
+            }
+            settings.post_process_language_prompts.insert(language, id);
+        }
+        None => {
+            settings.post_process_language_prompts.remove(&language);
+        }
+    }
+
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -1053,6 +1144,15 @@ pub fn change_mute_while_recording_setting(app: AppHandle, enabled: bool) -> Res
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub fn change_undo_reopens_recording_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.undo_reopens_recording = enabled;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub fn change_append_trailing_space_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
@@ -1151,3 +1251,88 @@ pub fn change_tts_speed_setting(app: AppHandle, speed: f64) -> Result<(), String
     settings::write_settings(&app, settings);
     Ok(())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_setting(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.hands_free_enabled = enabled;
+    settings::write_settings(&app, settings);
+
+    if enabled {
+        crate::hands_free::arm(&app);
+    } else {
+        crate::hands_free::disarm(&app);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_silence_timeout_setting(
+    app: AppHandle,
+    timeout_ms: u64,
+) -> Result<(), String> {
+    if timeout_ms < 300 {
+        return Err("Silence timeout must be at least 300 ms".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.hands_free_silence_timeout_ms = timeout_ms;
+    let enabled = settings.hands_free_enabled;
+    settings::write_settings(&app, settings);
+
+    // Re-arm so the recorder picks up the new timeout
+    if enabled {
+        crate::hands_free::rearm_if_enabled(&app);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_hands_free_wake_phrase_setting(
+    app: AppHandle,
+    phrase: Option<String>,
+) -> Result<(), String> {
+    let mut settings = settings::get_settings(&app);
+    settings.hands_free_wake_phrase = phrase
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty());
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_auto_stop_silence_setting(
+    app: AppHandle,
+    timeout_ms: Option<u64>,
+) -> Result<(), String> {
+    if timeout_ms.is_some_and(|ms| ms < 300) {
+        return Err("Silence timeout must be at least 300 ms".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.auto_stop_silence_ms = timeout_ms;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn change_max_recording_duration_setting(
+    app: AppHandle,
+    seconds: Option<u64>,
+) -> Result<(), String> {
+    if seconds == Some(0) {
+        return Err("Maximum recording duration must be greater than zero".to_string());
+    }
+
+    let mut settings = settings::get_settings(&app);
+    settings.max_recording_secs = seconds;
+    settings::write_settings(&app, settings);
+    Ok(())
+}
@@ -171,10 +171,7 @@ pub fn update_tray_menu(app: &AppHandle, state: &TrayIconState, locale: Option<&
 }
 
 fn last_transcript_text(entry: &HistoryEntry) -> &str {
-    entry
-        .post_processed_text
-        .as_deref()
-        .unwrap_or(&entry.transcription_text)
+    entry.final_text()
 }
 
 pub fn set_tray_visibility(app: &AppHandle, visible: bool) {
@@ -223,6 +220,12 @@ mod tests {
             transcription_text: transcription.to_string(),
             post_processed_text: post_processed.map(|text| text.to_string()),
             post_process_prompt: None,
+            detected_language: None,
+            language_probability: None,
+            edited_text: None,
+            tags: Vec::new(),
+            notes: None,
+            audio_deleted: false,
         }
     }
 
@@ -232,6 +235,13 @@ mod tests {
         assert_eq!(last_transcript_text(&entry), "processed");
     }
 
+    #[test]
+    fn uses_user_correction_when_available() {
+        let mut entry = build_entry("raw", Some("processed"));
+        entry.edited_text = Some("corrected".to_string());
+        assert_eq!(last_transcript_text(&entry), "corrected");
+    }
+
     #[test]
     fn falls_back_to_raw_transcription() {
         let entry = build_entry("raw", None);
@@ -29,6 +29,8 @@ pub fn cancel_current_operation(app: &AppHandle) {
         warn!("Failed to lock toggle state manager during cancellation");
     }
 
+    crate::auto_stop::end(app);
+
     // Cancel any ongoing recording
     let audio_manager = app.state::<Arc<AudioRecordingManager>>();
     audio_manager.cancel_recording();
@@ -41,6 +43,9 @@ pub fn cancel_current_operation(app: &AppHandle) {
     let tm = app.state::<Arc<TranscriptionManager>>();
     tm.maybe_unload_immediately("cancellation");
 
+    // Go back to waiting for speech if hands-free mode is armed
+    crate::hands_free::rearm_if_enabled(app);
+
     info!("Operation cancellation completed - returned to idle state");
 }
 
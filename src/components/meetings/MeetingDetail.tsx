@@ -2,7 +2,7 @@ import React, { useEffect, useState } from "react";
 import { ArrowLeft, Calendar, Clock, Download, FileAudio, Volume2 } from "lucide-react";
 import { commands } from "@/bindings";
 import { MeetingDetails } from "@/types/meetingTypes";
-import { convertFileSrc } from "@tauri-apps/api/core";
+import { getRecordingUrl } from "@/lib/utils/recording";
 import { toast } from "sonner";
 
 interface MeetingDetailProps {
@@ -33,8 +33,8 @@ export const MeetingDetail: React.FC<MeetingDetailProps> = ({ meetingId, onBack
                 const res = await (commands as any).getMeetingDetails(meetingId);
                 if (res.status === "ok") {
                     setDetails(res.data);
-                    if (res.data.audio_path) {
-                        setAudioSrc(convertFileSrc(res.data.audio_path));
+                    if (res.data.meeting.file_name) {
+                        setAudioSrc(await getRecordingUrl(res.data.meeting.file_name));
                     }
                 } else {
                     toast.error("Failed to load meeting details");
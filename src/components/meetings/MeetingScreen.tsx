@@ -26,7 +26,7 @@ export const MeetingScreen: React.FC = () => {
 
     const fetchMeetings = async () => {
         try {
-            const res = await commands.getMeetings();
+            const res = await commands.getMeetings(null);
             if (res.status === "ok") {
                 setMeetings(res.data as Meeting[]);
             }
@@ -61,9 +61,15 @@ export const MeetingScreen: React.FC = () => {
             fetchMeetings(); // Refresh list
         });
 
+        // Titles, tags and notes edited elsewhere
+        const unlistenUpdated = listen("meetings-updated", () => {
+            fetchMeetings();
+        });
+
         return () => {
             unlistenSegment.then(u => u());
             unlistenStopped.then(u => u());
+            unlistenUpdated.then(u => u());
         };
     }, []);
 
@@ -1,7 +1,8 @@
 import React, { useState, useRef, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { listen } from "@tauri-apps/api/event";
-import { commands } from "@/bindings";
+import { toast } from "sonner";
+import { commands, type ModelBenchmark } from "@/bindings";
 import { getTranslatedModelName } from "../../lib/utils/modelTranslation";
 import { useModelStore } from "../../stores/modelStore";
 import ModelStatusButton from "./ModelStatusButton";
@@ -124,6 +125,22 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
       },
     );
 
+    // Warn when the model just selected can't keep up with real time here
+    const tooSlowUnlisten = listen<ModelBenchmark>(
+      "model-too-slow",
+      (event) => {
+        const { model_id, real_time_factor } = event.payload;
+        const { models } = useModelStore.getState();
+        const model = models.find((m) => m.id === model_id);
+        toast.warning(
+          t("modelSelector.tooSlow", {
+            modelName: model ? getTranslatedModelName(model, t) : model_id,
+            factor: real_time_factor.toFixed(1),
+          }),
+        );
+      },
+    );
+
     // Click outside to close dropdown
     const handleClickOutside = (event: MouseEvent) => {
       if (
@@ -140,8 +157,9 @@ const ModelSelector: React.FC<ModelSelectorProps> = ({ onError }) => {
       document.removeEventListener("mousedown", handleClickOutside);
       modelStateUnlisten.then((fn) => fn());
       downloadCompleteUnlisten.then((fn) => fn());
+      tooSlowUnlisten.then((fn) => fn());
     };
-  }, [selectModel]);
+  }, [selectModel, t]);
 
   const handleModelSelect = async (modelId: string) => {
     setPendingModelId(modelId);
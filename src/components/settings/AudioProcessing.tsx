@@ -0,0 +1,46 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface AudioProcessingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type ProcessingSetting =
+  | "noise_suppression"
+  | "auto_gain_control"
+  | "high_pass_filter";
+
+const STAGES: [ProcessingSetting, string][] = [
+  ["noise_suppression", "noiseSuppression"],
+  ["auto_gain_control", "autoGainControl"],
+  ["high_pass_filter", "highPassFilter"],
+];
+
+export const AudioProcessing: React.FC<AudioProcessingProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    return (
+      <>
+        {STAGES.map(([setting, key]) => (
+          <ToggleSwitch
+            key={setting}
+            checked={getSetting(setting) || false}
+            onChange={(enabled) => updateSetting(setting, enabled)}
+            isUpdating={isUpdating(setting)}
+            label={t(`settings.advanced.audioProcessing.${key}.label`)}
+            description={t(
+              `settings.advanced.audioProcessing.${key}.description`,
+            )}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          />
+        ))}
+      </>
+    );
+  },
+);
@@ -0,0 +1,110 @@
+import React, { useMemo } from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { DETECTED_LANGUAGES } from "../../lib/constants/languages";
+import { Button } from "../ui/Button";
+import { Select } from "../ui/Select";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface AutoDetectLanguagesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+  supportedLanguages?: string[];
+}
+
+export const AutoDetectLanguages: React.FC<AutoDetectLanguagesProps> =
+  React.memo(
+    ({ descriptionMode = "tooltip", grouped = false, supportedLanguages }) => {
+      const { t } = useTranslation();
+      const { getSetting, updateSetting, isUpdating } = useSettings();
+      const languages = getSetting("auto_detect_languages") || [];
+      const updating = isUpdating("auto_detect_languages");
+
+      const labelFor = (code: string) =>
+        DETECTED_LANGUAGES.find((lang) => lang.value === code)?.label ?? code;
+
+      const options = useMemo(
+        () =>
+          DETECTED_LANGUAGES.filter(
+            (lang) =>
+              !languages.includes(lang.value) &&
+              (!supportedLanguages?.length ||
+                supportedLanguages.some(
+                  (code) => code.split("-")[0] === lang.value,
+                )),
+          ),
+        [languages, supportedLanguages],
+      );
+
+      const handleAdd = (code: string | null) => {
+        if (code) {
+          updateSetting("auto_detect_languages", [...languages, code]);
+        }
+      };
+
+      const handleRemove = (code: string) => {
+        updateSetting(
+          "auto_detect_languages",
+          languages.filter((language) => language !== code),
+        );
+      };
+
+      return (
+        <>
+          <SettingContainer
+            title={t("settings.general.autoDetectLanguages.title")}
+            description={t("settings.general.autoDetectLanguages.description")}
+            descriptionMode={descriptionMode}
+            grouped={grouped}
+          >
+            <Select
+              options={options}
+              value={null}
+              onChange={handleAdd}
+              disabled={updating}
+              placeholder={
+                languages.length === 0
+                  ? t("settings.general.autoDetectLanguages.allLanguages")
+                  : t("settings.general.autoDetectLanguages.add")
+              }
+              className="min-w-[200px]"
+            />
+          </SettingContainer>
+          {languages.length > 0 && (
+            <div
+              className={`px-4 p-2 ${grouped ? "" : "rounded-lg border border-mid-gray/20"} flex flex-wrap gap-1`}
+            >
+              {languages.map((code) => (
+                <Button
+                  key={code}
+                  onClick={() => handleRemove(code)}
+                  disabled={updating}
+                  variant="secondary"
+                  size="sm"
+                  className="inline-flex items-center gap-1 cursor-pointer"
+                  aria-label={t("settings.general.autoDetectLanguages.remove", {
+                    language: labelFor(code),
+                  })}
+                >
+                  <span>{labelFor(code)}</span>
+                  <svg
+                    className="w-3 h-3"
+                    fill="none"
+                    stroke="currentColor"
+                    viewBox="0 0 24 24"
+                  >
+                    <path
+                      strokeLinecap="round"
+                      strokeLinejoin="round"
+                      strokeWidth={2}
+                      d="M6 18L18 6M6 6l12 12"
+                    />
+                  </svg>
+                </Button>
+              ))}
+            </div>
+          )}
+        </>
+      );
+    },
+  );
@@ -0,0 +1,84 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Select } from "../ui/Select";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface AutoStopProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const OFF = "off";
+
+const SILENCE_OPTIONS_MS: [string, number][] = [
+  ["sec1", 1000],
+  ["sec2", 2000],
+  ["sec3", 3000],
+  ["sec5", 5000],
+];
+
+const DURATION_OPTIONS_SECS: [string, number][] = [
+  ["min1", 60],
+  ["min5", 300],
+  ["min10", 600],
+  ["min30", 1800],
+];
+
+export const AutoStop: React.FC<AutoStopProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const silenceMs = getSetting("auto_stop_silence_ms") ?? null;
+    const maxSecs = getSetting("max_recording_secs") ?? null;
+
+    const toOptions = (values: [string, number][]) => [
+      { value: OFF, label: t("settings.advanced.autoStop.options.off") },
+      ...values.map(([key, value]) => ({
+        value: String(value),
+        label: t(`settings.advanced.autoStop.options.${key}`),
+      })),
+    ];
+
+    const parse = (value: string | null) =>
+      !value || value === OFF ? null : Number(value);
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.autoStop.silence.title")}
+          description={t("settings.advanced.autoStop.silence.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Select
+            options={toOptions(SILENCE_OPTIONS_MS)}
+            value={silenceMs === null ? OFF : String(silenceMs)}
+            onChange={(value) =>
+              updateSetting("auto_stop_silence_ms", parse(value))
+            }
+            disabled={isUpdating("auto_stop_silence_ms")}
+            className="min-w-[160px]"
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.autoStop.maxDuration.title")}
+          description={t("settings.advanced.autoStop.maxDuration.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Select
+            options={toOptions(DURATION_OPTIONS_SECS)}
+            value={maxSecs === null ? OFF : String(maxSecs)}
+            onChange={(value) =>
+              updateSetting("max_recording_secs", parse(value))
+            }
+            disabled={isUpdating("max_recording_secs")}
+            className="min-w-[160px]"
+          />
+        </SettingContainer>
+      </>
+    );
+  },
+);
@@ -0,0 +1,61 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { useSettings } from "../../hooks/useSettings";
+import { Select } from "../ui/Select";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface ChineseVariantProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+// Select needs a non-empty value for "leave the script as transcribed"
+const NO_CONVERSION = "none";
+
+export const ChineseVariant: React.FC<ChineseVariantProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const variant = getSetting("auto_chinese_variant") ?? NO_CONVERSION;
+
+    const options = [
+      {
+        value: NO_CONVERSION,
+        label: t("settings.general.chineseVariant.none"),
+      },
+      {
+        value: "zh-Hans",
+        label: t("settings.general.chineseVariant.simplified"),
+      },
+      {
+        value: "zh-Hant",
+        label: t("settings.general.chineseVariant.traditional"),
+      },
+    ];
+
+    const handleChange = (value: string | null) => {
+      if (!value) return;
+      updateSetting(
+        "auto_chinese_variant",
+        value === NO_CONVERSION ? null : value,
+      );
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.general.chineseVariant.title")}
+        description={t("settings.general.chineseVariant.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <Select
+          options={options}
+          value={variant}
+          onChange={handleChange}
+          disabled={isUpdating("auto_chinese_variant")}
+          className="min-w-[200px]"
+        />
+      </SettingContainer>
+    );
+  },
+);
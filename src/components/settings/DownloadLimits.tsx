@@ -0,0 +1,87 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { Select } from "../ui/Select";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface DownloadLimitsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const UNLIMITED = "unlimited";
+
+const CONCURRENT_OPTIONS = [1, 2, 3, 4];
+
+const BANDWIDTH_OPTIONS_KIB: [string, number][] = [
+  ["kib512", 512],
+  ["mib1", 1024],
+  ["mib5", 5 * 1024],
+  ["mib10", 10 * 1024],
+];
+
+export const DownloadLimits: React.FC<DownloadLimitsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const maxConcurrent = getSetting("max_concurrent_downloads") ?? 2;
+    const bandwidthKib = getSetting("download_bandwidth_limit_kib") ?? null;
+
+    const concurrentOptions = CONCURRENT_OPTIONS.map((value) => ({
+      value: String(value),
+      label: String(value),
+    }));
+
+    const bandwidthOptions = [
+      {
+        value: UNLIMITED,
+        label: t("settings.advanced.downloads.bandwidth.options.unlimited"),
+      },
+      ...BANDWIDTH_OPTIONS_KIB.map(([key, value]) => ({
+        value: String(value),
+        label: t(`settings.advanced.downloads.bandwidth.options.${key}`),
+      })),
+    ];
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.downloads.concurrent.title")}
+          description={t("settings.advanced.downloads.concurrent.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Select
+            options={concurrentOptions}
+            value={String(maxConcurrent)}
+            onChange={(value) =>
+              value && updateSetting("max_concurrent_downloads", Number(value))
+            }
+            disabled={isUpdating("max_concurrent_downloads")}
+            className="min-w-[160px]"
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.downloads.bandwidth.title")}
+          description={t("settings.advanced.downloads.bandwidth.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Select
+            options={bandwidthOptions}
+            value={bandwidthKib === null ? UNLIMITED : String(bandwidthKib)}
+            onChange={(value) =>
+              updateSetting(
+                "download_bandwidth_limit_kib",
+                !value || value === UNLIMITED ? null : Number(value),
+              )
+            }
+            disabled={isUpdating("download_bandwidth_limit_kib")}
+            className="min-w-[160px]"
+          />
+        </SettingContainer>
+      </>
+    );
+  },
+);
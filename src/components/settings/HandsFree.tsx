@@ -0,0 +1,90 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { Slider } from "../ui/Slider";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface HandsFreeProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const HandsFree: React.FC<HandsFreeProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("hands_free_enabled") || false;
+    const silenceTimeout = getSetting("hands_free_silence_timeout_ms") ?? 1500;
+    const wakePhrase = getSetting("hands_free_wake_phrase") ?? "";
+    const [phraseDraft, setPhraseDraft] = useState(wakePhrase);
+
+    useEffect(() => {
+      setPhraseDraft(wakePhrase);
+    }, [wakePhrase]);
+
+    // Saved on blur so every keystroke doesn't rewrite the settings
+    const handlePhraseCommit = () => {
+      const phrase = phraseDraft.trim();
+      if (phrase !== wakePhrase) {
+        updateSetting("hands_free_wake_phrase", phrase || null);
+      }
+    };
+
+    return (
+      <>
+        <ToggleSwitch
+          checked={enabled}
+          onChange={(value) => updateSetting("hands_free_enabled", value)}
+          isUpdating={isUpdating("hands_free_enabled")}
+          label={t("settings.advanced.handsFree.label")}
+          description={t("settings.advanced.handsFree.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        {enabled && (
+          <>
+            <Slider
+              value={silenceTimeout}
+              onChange={(value) =>
+                updateSetting("hands_free_silence_timeout_ms", value)
+              }
+              min={300}
+              max={5000}
+              step={100}
+              label={t("settings.advanced.handsFree.silenceTimeout.title")}
+              description={t(
+                "settings.advanced.handsFree.silenceTimeout.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+              formatValue={(v) => `${(v / 1000).toFixed(1)}s`}
+            />
+            <SettingContainer
+              title={t("settings.advanced.handsFree.wakePhrase.title")}
+              description={t(
+                "settings.advanced.handsFree.wakePhrase.description",
+              )}
+              descriptionMode={descriptionMode}
+              grouped={grouped}
+            >
+              <Input
+                type="text"
+                value={phraseDraft}
+                placeholder={t(
+                  "settings.advanced.handsFree.wakePhrase.placeholder",
+                )}
+                onChange={(event) => setPhraseDraft(event.target.value)}
+                onBlur={handlePhraseCommit}
+                disabled={isUpdating("hands_free_wake_phrase")}
+                className="w-48"
+              />
+            </SettingContainer>
+          </>
+        )}
+      </>
+    );
+  },
+);
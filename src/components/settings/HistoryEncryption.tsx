@@ -0,0 +1,64 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+
+interface HistoryEncryptionProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const HistoryEncryption: React.FC<HistoryEncryptionProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const [enabled, setEnabled] = useState(false);
+    const [isUpdating, setIsUpdating] = useState(false);
+
+    useEffect(() => {
+      commands.getHistoryEncryption().then((result) => {
+        if (result.status === "ok") {
+          setEnabled(result.data);
+        }
+      });
+    }, []);
+
+    const handleChange = async (enable: boolean) => {
+      setIsUpdating(true);
+      try {
+        // Converts every recording, which can take a while
+        const result = await commands.setHistoryEncryption(enable);
+        if (result.status === "ok") {
+          setEnabled(enable);
+          if (result.data > 0) {
+            toast.warning(
+              t("settings.advanced.historyEncryption.partialFailure", {
+                count: result.data,
+              }),
+            );
+          }
+        } else {
+          toast.error(
+            t("settings.advanced.historyEncryption.error", {
+              error: result.error,
+            }),
+          );
+        }
+      } finally {
+        setIsUpdating(false);
+      }
+    };
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={handleChange}
+        isUpdating={isUpdating}
+        label={t("settings.advanced.historyEncryption.label")}
+        description={t("settings.advanced.historyEncryption.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  },
+);
@@ -0,0 +1,102 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, MicTestReport } from "@/bindings";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+
+interface MicrophoneTestProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const MicrophoneTest: React.FC<MicrophoneTestProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const [playBack, setPlayBack] = useState(false);
+    const [isTesting, setIsTesting] = useState(false);
+    const [report, setReport] = useState<MicTestReport | null>(null);
+
+    const handleTest = async () => {
+      setIsTesting(true);
+      setReport(null);
+      try {
+        const result = await commands.testMicrophone(null, playBack);
+        if (result.status === "ok") {
+          setReport(result.data);
+        } else {
+          toast.error(
+            t("settings.advanced.microphoneTest.error", {
+              error: result.error,
+            }),
+          );
+        }
+      } finally {
+        setIsTesting(false);
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.microphoneTest.title")}
+        description={t("settings.advanced.microphoneTest.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          <div className="flex items-center gap-3">
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={handleTest}
+              disabled={isTesting}
+            >
+              {isTesting
+                ? t("settings.advanced.microphoneTest.testing")
+                : t("settings.advanced.microphoneTest.start")}
+            </Button>
+            <label className="flex items-center gap-1 text-xs text-text">
+              <input
+                type="checkbox"
+                checked={playBack}
+                onChange={(event) => setPlayBack(event.target.checked)}
+                disabled={isTesting}
+              />
+              {t("settings.advanced.microphoneTest.playBack")}
+            </label>
+          </div>
+
+          {report && (
+            <div className="text-xs text-text space-y-1">
+              <p>
+                {t("settings.advanced.microphoneTest.device", {
+                  name: report.device_name,
+                })}
+              </p>
+              <p>
+                {t("settings.advanced.microphoneTest.levels", {
+                  rms: report.rms_db.toFixed(0),
+                  peak: report.peak_db.toFixed(0),
+                  floor: report.noise_floor_db.toFixed(0),
+                  speech: Math.round(report.speech_ratio * 100),
+                })}
+              </p>
+              {report.warnings.length === 0 ? (
+                <p className="text-mid-gray">
+                  {t("settings.advanced.microphoneTest.noProblems")}
+                </p>
+              ) : (
+                report.warnings.map((warning) => (
+                  <p key={warning} className="text-red-400">
+                    {warning}
+                  </p>
+                ))
+              )}
+            </div>
+          )}
+        </div>
+      </SettingContainer>
+    );
+  },
+);
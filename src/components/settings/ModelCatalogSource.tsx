@@ -0,0 +1,141 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands } from "@/bindings";
+import { Select } from "../ui/Select";
+import { Input } from "../ui/Input";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface ModelCatalogSourceProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+const BUNDLED = "bundled";
+const PUBLISHED = "published";
+const CUSTOM = "custom";
+
+export const ModelCatalogSource: React.FC<ModelCatalogSourceProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const source = getSetting("model_catalog_source") ?? null;
+    const mode =
+      source === null || source.toLowerCase() === BUNDLED
+        ? BUNDLED
+        : source.toLowerCase() === PUBLISHED
+          ? PUBLISHED
+          : CUSTOM;
+    const [selected, setSelected] = useState(mode);
+    const [customDraft, setCustomDraft] = useState(
+      mode === CUSTOM ? (source ?? "") : "",
+    );
+    const [isBusy, setIsBusy] = useState(false);
+
+    useEffect(() => {
+      setSelected(mode);
+      if (mode === CUSTOM) {
+        setCustomDraft(source ?? "");
+      }
+    }, [mode, source]);
+
+    const applySource = async (value: string | null) => {
+      setIsBusy(true);
+      try {
+        // Kept only if the new catalog loads, so errors leave the old one
+        const result = await commands.setModelCatalogSource(value);
+        if (result.status === "ok") {
+          toast.success(t("settings.advanced.modelCatalog.loaded"));
+        } else {
+          toast.error(
+            t("settings.advanced.modelCatalog.error", { error: result.error }),
+          );
+          setSelected(mode);
+        }
+        await refreshSettings();
+      } finally {
+        setIsBusy(false);
+      }
+    };
+
+    const handleSelect = (value: string | null) => {
+      if (!value) return;
+      setSelected(value);
+      if (value === BUNDLED) {
+        applySource(null);
+      } else if (value === PUBLISHED) {
+        applySource(PUBLISHED);
+      }
+    };
+
+    const handleCustomCommit = () => {
+      const value = customDraft.trim();
+      if (value && value !== source) {
+        applySource(value);
+      }
+    };
+
+    const handleRefresh = async () => {
+      setIsBusy(true);
+      try {
+        const result = await commands.refreshModelCatalog();
+        if (result.status === "ok") {
+          toast.success(t("settings.advanced.modelCatalog.loaded"));
+        } else {
+          toast.error(
+            t("settings.advanced.modelCatalog.error", { error: result.error }),
+          );
+        }
+      } finally {
+        setIsBusy(false);
+      }
+    };
+
+    const options = [BUNDLED, PUBLISHED, CUSTOM].map((value) => ({
+      value,
+      label: t(`settings.advanced.modelCatalog.sources.${value}`),
+    }));
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.modelCatalog.title")}
+        description={t("settings.advanced.modelCatalog.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="flex items-center gap-2">
+          <Select
+            options={options}
+            value={selected}
+            onChange={handleSelect}
+            disabled={isBusy}
+            className="min-w-[160px]"
+          />
+          {selected === CUSTOM && (
+            <Input
+              type="text"
+              value={customDraft}
+              placeholder={t("settings.advanced.modelCatalog.placeholder")}
+              onChange={(event) => setCustomDraft(event.target.value)}
+              onBlur={handleCustomCommit}
+              disabled={isBusy}
+              className="flex-1"
+            />
+          )}
+          {selected !== BUNDLED && (
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={handleRefresh}
+              disabled={isBusy}
+            >
+              {t("settings.advanced.modelCatalog.refresh")}
+            </Button>
+          )}
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,118 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, NetworkSettings } from "@/bindings";
+import { Input } from "../ui/Input";
+import { Textarea } from "../ui/Textarea";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface NetworkSettingsEditorProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const NetworkSettingsEditor: React.FC<NetworkSettingsEditorProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const network = getSetting("network");
+    const [proxy, setProxy] = useState("");
+    const [caBundle, setCaBundle] = useState("");
+    const [mirrors, setMirrors] = useState("");
+    const [isSaving, setIsSaving] = useState(false);
+
+    useEffect(() => {
+      setProxy(network?.proxy_url ?? "");
+      setCaBundle(network?.ca_bundle_path ?? "");
+      setMirrors((network?.model_mirrors ?? []).join("\n"));
+    }, [network]);
+
+    const handleSave = async () => {
+      const updated: NetworkSettings = {
+        proxy_url: proxy.trim() || null,
+        ca_bundle_path: caBundle.trim() || null,
+        // One mirror per line, tried in order
+        model_mirrors: mirrors
+          .split("\n")
+          .map((mirror) => mirror.trim())
+          .filter(Boolean),
+      };
+
+      setIsSaving(true);
+      try {
+        const result = await commands.setNetworkSettings(updated);
+        if (result.status === "ok") {
+          await refreshSettings();
+          toast.success(t("settings.advanced.network.saved"));
+        } else {
+          toast.error(
+            t("settings.advanced.network.error", { error: result.error }),
+          );
+        }
+      } finally {
+        setIsSaving(false);
+      }
+    };
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.network.title")}
+        description={t("settings.advanced.network.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-2">
+          <label className="block text-xs text-mid-gray space-y-1">
+            <span className="block">
+              {t("settings.advanced.network.proxy")}
+            </span>
+            <Input
+              type="text"
+              value={proxy}
+              placeholder="http://proxy:8080"
+              onChange={(event) => setProxy(event.target.value)}
+              disabled={isSaving}
+              className="w-full"
+            />
+          </label>
+          <label className="block text-xs text-mid-gray space-y-1">
+            <span className="block">
+              {t("settings.advanced.network.caBundle")}
+            </span>
+            <Input
+              type="text"
+              value={caBundle}
+              placeholder={t("settings.advanced.network.caBundlePlaceholder")}
+              onChange={(event) => setCaBundle(event.target.value)}
+              disabled={isSaving}
+              className="w-full"
+            />
+          </label>
+          <label className="block text-xs text-mid-gray space-y-1">
+            <span className="block">
+              {t("settings.advanced.network.mirrors")}
+            </span>
+            <Textarea
+              value={mirrors}
+              placeholder="https://mirror.example.com/models"
+              onChange={(event) => setMirrors(event.target.value)}
+              disabled={isSaving}
+              variant="compact"
+              className="w-full"
+            />
+          </label>
+          <Button
+            variant="primary"
+            size="sm"
+            onClick={handleSave}
+            disabled={isSaving}
+          >
+            {t("settings.advanced.network.save")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  });
@@ -0,0 +1,135 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
+import { commands, RecordingFormat } from "@/bindings";
+import { Select } from "../ui/Select";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface RecordingFormatProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+interface ReencodeProgress {
+  done: number;
+  total: number;
+}
+
+interface ReencodeResult {
+  converted: number;
+  failed: number;
+  bytes_saved: number;
+}
+
+export const RecordingFormatSelector: React.FC<RecordingFormatProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+    const [isReencoding, setIsReencoding] = useState(false);
+    const [progress, setProgress] = useState<ReencodeProgress | null>(null);
+
+    const selectedFormat = getSetting("recording_format") || "wav";
+
+    useEffect(() => {
+      const unlistenProgress = listen<ReencodeProgress>(
+        "recording-reencode-progress",
+        (event) => {
+          setIsReencoding(true);
+          setProgress(event.payload);
+        },
+      );
+      const unlistenCompleted = listen<ReencodeResult>(
+        "recording-reencode-completed",
+        (event) => {
+          setIsReencoding(false);
+          setProgress(null);
+          toast.success(
+            t("settings.advanced.recordingFormat.completed", {
+              converted: event.payload.converted,
+              failed: event.payload.failed,
+              size: (event.payload.bytes_saved / 1024 / 1024).toFixed(1),
+            }),
+          );
+        },
+      );
+      const unlistenFailed = listen<string>(
+        "recording-reencode-failed",
+        (event) => {
+          setIsReencoding(false);
+          setProgress(null);
+          toast.error(
+            t("settings.advanced.recordingFormat.error", {
+              error: event.payload,
+            }),
+          );
+        },
+      );
+
+      return () => {
+        unlistenProgress.then((unlisten) => unlisten());
+        unlistenCompleted.then((unlisten) => unlisten());
+        unlistenFailed.then((unlisten) => unlisten());
+      };
+    }, [t]);
+
+    const handleFormatSelect = async (format: string | null) => {
+      if (!format) return;
+      await updateSetting("recording_format", format as RecordingFormat);
+    };
+
+    const handleReencode = async () => {
+      setIsReencoding(true);
+      const result = await commands.reencodeRecordings();
+      if (result.status === "error") {
+        setIsReencoding(false);
+        toast.error(
+          t("settings.advanced.recordingFormat.error", {
+            error: result.error,
+          }),
+        );
+      }
+    };
+
+    const formatOptions = [
+      { value: "wav", label: t("settings.advanced.recordingFormat.wav") },
+      { value: "flac", label: t("settings.advanced.recordingFormat.flac") },
+      { value: "opus", label: t("settings.advanced.recordingFormat.opus") },
+    ];
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.recordingFormat.title")}
+        description={t("settings.advanced.recordingFormat.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      >
+        <div className="flex items-center gap-2">
+          <Select
+            options={formatOptions}
+            value={selectedFormat}
+            onChange={handleFormatSelect}
+            disabled={isUpdating("recording_format") || isReencoding}
+            className="min-w-[160px]"
+          />
+          <Button
+            variant="secondary"
+            size="md"
+            onClick={handleReencode}
+            disabled={isReencoding}
+          >
+            {isReencoding
+              ? t("settings.advanced.recordingFormat.reencoding", {
+                  done: progress?.done ?? 0,
+                  total: progress?.total ?? 0,
+                })
+              : t("settings.advanced.recordingFormat.reencode")}
+          </Button>
+        </div>
+      </SettingContainer>
+    );
+  });
+
+RecordingFormatSelector.displayName = "RecordingFormatSelector";
@@ -0,0 +1,224 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  CleanupPreview,
+  RetentionPolicies,
+  RetentionPolicy,
+} from "@/bindings";
+import { Button } from "../ui/Button";
+import { Input } from "../ui/Input";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface RetentionPoliciesProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type PolicyKind = keyof RetentionPolicies;
+type LimitField = "max_age_days" | "max_entries" | "audio_max_age_days";
+
+const KINDS: PolicyKind[] = ["dictations", "meetings", "tts"];
+const LIMIT_FIELDS: LimitField[] = [
+  "max_age_days",
+  "max_entries",
+  "audio_max_age_days",
+];
+
+// How many of the affected entries the preview lists by name
+const PREVIEW_LIMIT = 5;
+
+export const RetentionPoliciesEditor: React.FC<RetentionPoliciesProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { refreshSettings } = useSettings();
+    const [policies, setPolicies] = useState<RetentionPolicies | null>(null);
+    const [preview, setPreview] = useState<CleanupPreview | null>(null);
+    const [isBusy, setIsBusy] = useState(false);
+
+    useEffect(() => {
+      commands.getRetentionPolicies().then(setPolicies);
+    }, []);
+
+    if (!policies) {
+      return null;
+    }
+
+    const updatePolicy = (
+      kind: PolicyKind,
+      change: Partial<RetentionPolicy>,
+    ) => {
+      setPolicies({ ...policies, [kind]: { ...policies[kind], ...change } });
+      // A preview of other policies would be misleading
+      setPreview(null);
+    };
+
+    const handleLimitChange = (
+      kind: PolicyKind,
+      field: LimitField,
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const value = parseInt(event.target.value, 10);
+      if (event.target.value === "") {
+        updatePolicy(kind, { [field]: null });
+      } else if (!isNaN(value) && value >= 0) {
+        updatePolicy(kind, { [field]: value });
+      }
+    };
+
+    const handlePreview = async () => {
+      setIsBusy(true);
+      try {
+        const result = await commands.previewRetentionCleanup(policies);
+        if (result.status === "ok") {
+          setPreview(result.data);
+        } else {
+          toast.error(
+            t("settings.advanced.retentionPolicies.error", {
+              error: result.error,
+            }),
+          );
+        }
+      } finally {
+        setIsBusy(false);
+      }
+    };
+
+    const handleApply = async () => {
+      setIsBusy(true);
+      try {
+        const result = await commands.updateRetentionPolicies(policies);
+        if (result.status === "ok") {
+          setPreview(null);
+          await refreshSettings();
+          toast.success(t("settings.advanced.retentionPolicies.applied"));
+        } else {
+          toast.error(
+            t("settings.advanced.retentionPolicies.error", {
+              error: result.error,
+            }),
+          );
+        }
+      } finally {
+        setIsBusy(false);
+      }
+    };
+
+    const deletedEntries =
+      preview?.candidates.filter((c) => c.action === "delete_entry").length ??
+      0;
+    const strippedEntries =
+      preview?.candidates.filter((c) => c.action === "delete_audio").length ??
+      0;
+
+    return (
+      <SettingContainer
+        title={t("settings.advanced.retentionPolicies.title")}
+        description={t("settings.advanced.retentionPolicies.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+        layout="stacked"
+      >
+        <div className="space-y-3">
+          <div className="grid grid-cols-5 gap-2 items-center text-xs text-mid-gray">
+            <span />
+            {LIMIT_FIELDS.map((field) => (
+              <span key={field}>
+                {t(`settings.advanced.retentionPolicies.fields.${field}`)}
+              </span>
+            ))}
+            <span>
+              {t("settings.advanced.retentionPolicies.fields.keep_saved")}
+            </span>
+          </div>
+          {KINDS.map((kind) => {
+            const policy: RetentionPolicy = policies[kind] ?? {};
+            return (
+              <div key={kind} className="grid grid-cols-5 gap-2 items-center">
+                <span className="text-sm text-text">
+                  {t(`settings.advanced.retentionPolicies.kinds.${kind}`)}
+                </span>
+                {LIMIT_FIELDS.map((field) => (
+                  <Input
+                    key={field}
+                    type="number"
+                    min="0"
+                    variant="compact"
+                    value={policy[field] ?? ""}
+                    placeholder={t(
+                      "settings.advanced.retentionPolicies.noLimit",
+                    )}
+                    onChange={(event) => handleLimitChange(kind, field, event)}
+                    disabled={isBusy}
+                    className="w-24"
+                  />
+                ))}
+                <input
+                  type="checkbox"
+                  checked={policy.keep_saved ?? true}
+                  onChange={(event) =>
+                    updatePolicy(kind, { keep_saved: event.target.checked })
+                  }
+                  disabled={isBusy}
+                />
+              </div>
+            );
+          })}
+
+          <div className="flex items-center gap-2">
+            <Button
+              variant="secondary"
+              size="sm"
+              onClick={handlePreview}
+              disabled={isBusy}
+            >
+              {t("settings.advanced.retentionPolicies.preview")}
+            </Button>
+            <Button
+              variant="primary"
+              size="sm"
+              onClick={handleApply}
+              disabled={isBusy}
+            >
+              {t("settings.advanced.retentionPolicies.apply")}
+            </Button>
+          </div>
+
+          {preview && (
+            <div className="text-xs text-text space-y-1">
+              <p>
+                {preview.candidates.length === 0
+                  ? t("settings.advanced.retentionPolicies.previewEmpty")
+                  : t("settings.advanced.retentionPolicies.previewSummary", {
+                      deleted: deletedEntries,
+                      stripped: strippedEntries,
+                      size: (preview.audio_bytes / 1024 / 1024).toFixed(1),
+                    })}
+              </p>
+              {preview.candidates.slice(0, PREVIEW_LIMIT).map((candidate) => (
+                <p
+                  key={`${candidate.kind}-${candidate.id}`}
+                  className="text-mid-gray truncate"
+                >
+                  {new Date(candidate.timestamp * 1000).toLocaleDateString()}{" "}
+                  {candidate.title ||
+                    t("settings.advanced.retentionPolicies.untitled")}
+                </p>
+              ))}
+              {preview.candidates.length > PREVIEW_LIMIT && (
+                <p className="text-mid-gray">
+                  {t("settings.advanced.retentionPolicies.previewMore", {
+                    count: preview.candidates.length - PREVIEW_LIMIT,
+                  })}
+                </p>
+              )}
+            </div>
+          )}
+        </div>
+      </SettingContainer>
+    );
+  });
+
+RetentionPoliciesEditor.displayName = "RetentionPoliciesEditor";
@@ -287,7 +287,11 @@ export const TypeZeroKeysShortcutInput: React.FC<TypeZeroKeysShortcutInputProps>
             className="px-2 py-1 text-sm font-semibold bg-mid-gray/10 border border-mid-gray/80 hover:bg-logo-primary/10 rounded-md cursor-pointer hover:border-logo-primary"
             onClick={startRecording}
           >
-            {formatKeyCombination(binding.current_binding, osType)}
+            {binding.current_binding ? (
+              formatKeyCombination(binding.current_binding, osType)
+            ) : (
+              <span className="text-text-muted italic">Click to set</span>
+            )}
           </div>
         )}
         <ResetButton
@@ -0,0 +1,29 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface UndoReopensRecordingProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+export const UndoReopensRecording: React.FC<UndoReopensRecordingProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating } = useSettings();
+
+    const enabled = getSetting("undo_reopens_recording") ?? false;
+
+    return (
+      <ToggleSwitch
+        checked={enabled}
+        onChange={(enabled) => updateSetting("undo_reopens_recording", enabled)}
+        isUpdating={isUpdating("undo_reopens_recording")}
+        label={t("settings.advanced.undoReopensRecording.label")}
+        description={t("settings.advanced.undoReopensRecording.description")}
+        descriptionMode={descriptionMode}
+        grouped={grouped}
+      />
+    );
+  });
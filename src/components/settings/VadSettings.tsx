@@ -0,0 +1,179 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import {
+  commands,
+  VadBackend,
+  VadCalibrationResult,
+  VadTuning,
+} from "@/bindings";
+import { Select } from "../ui/Select";
+import { Input } from "../ui/Input";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+import { useSettings } from "../../hooks/useSettings";
+
+interface VadSettingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type TuningField = keyof VadTuning;
+
+const FRAME_FIELDS: TuningField[] = [
+  "onset_frames",
+  "prefill_frames",
+  "hangover_frames",
+];
+
+export const VadSettings: React.FC<VadSettingsProps> = React.memo(
+  ({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, updateSetting, isUpdating, refreshSettings } =
+      useSettings();
+    const backend = getSetting("vad_backend") ?? "silero";
+    const tuning = getSetting("vad_tuning");
+    const [draft, setDraft] = useState<VadTuning>(tuning ?? {});
+    const [calibration, setCalibration] =
+      useState<VadCalibrationResult | null>(null);
+    const [isBusy, setIsBusy] = useState(false);
+
+    useEffect(() => {
+      if (tuning) {
+        setDraft(tuning);
+      }
+    }, [tuning]);
+
+    const backendOptions = [
+      { value: "silero", label: t("settings.advanced.vad.backends.silero") },
+      { value: "energy", label: t("settings.advanced.vad.backends.energy") },
+    ];
+
+    // The threshold only applies to Silero and the margin only to the energy VAD
+    const fields: TuningField[] = [
+      backend === "silero" ? "threshold" : "energy_margin_db",
+      ...FRAME_FIELDS,
+    ];
+
+    const handleFieldChange = (
+      field: TuningField,
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const value = parseFloat(event.target.value);
+      if (!isNaN(value)) {
+        setDraft({ ...draft, [field]: value });
+      }
+    };
+
+    const handleApply = async () => {
+      setIsBusy(true);
+      try {
+        const result = await commands.setVadTuning(draft);
+        if (result.status === "ok") {
+          await refreshSettings();
+          toast.success(t("settings.advanced.vad.applied"));
+        } else {
+          toast.error(
+            t("settings.advanced.vad.error", { error: result.error }),
+          );
+        }
+      } finally {
+        setIsBusy(false);
+      }
+    };
+
+    const handleCalibrate = async () => {
+      setIsBusy(true);
+      try {
+        // Records a few seconds of the room, so nobody should be talking
+        const result = await commands.calibrateVad(null);
+        if (result.status === "ok") {
+          setCalibration(result.data);
+          setDraft(result.data.suggested);
+        } else {
+          toast.error(
+            t("settings.advanced.vad.error", { error: result.error }),
+          );
+        }
+      } finally {
+        setIsBusy(false);
+      }
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.vad.backend.title")}
+          description={t("settings.advanced.vad.backend.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        >
+          <Select
+            options={backendOptions}
+            value={backend}
+            onChange={(value) =>
+              value && updateSetting("vad_backend", value as VadBackend)
+            }
+            disabled={isUpdating("vad_backend")}
+            className="min-w-[160px]"
+          />
+        </SettingContainer>
+        <SettingContainer
+          title={t("settings.advanced.vad.tuning.title")}
+          description={t("settings.advanced.vad.tuning.description")}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <div className="space-y-3">
+            <div className="grid grid-cols-4 gap-2 items-center">
+              {fields.map((field) => (
+                <label key={field} className="text-xs text-mid-gray space-y-1">
+                  <span className="block">
+                    {t(`settings.advanced.vad.fields.${field}`)}
+                  </span>
+                  <Input
+                    type="number"
+                    min="0"
+                    step={field === "threshold" ? "0.05" : "1"}
+                    variant="compact"
+                    value={draft[field] ?? ""}
+                    onChange={(event) => handleFieldChange(field, event)}
+                    disabled={isBusy}
+                    className="w-24"
+                  />
+                </label>
+              ))}
+            </div>
+            <div className="flex items-center gap-2">
+              <Button
+                variant="secondary"
+                size="sm"
+                onClick={handleCalibrate}
+                disabled={isBusy}
+              >
+                {t("settings.advanced.vad.calibrate")}
+              </Button>
+              <Button
+                variant="primary"
+                size="sm"
+                onClick={handleApply}
+                disabled={isBusy}
+              >
+                {t("settings.advanced.vad.apply")}
+              </Button>
+            </div>
+            {calibration && (
+              <p className="text-xs text-mid-gray">
+                {t("settings.advanced.vad.calibrated", {
+                  floor: calibration.noise_floor_db.toFixed(0),
+                  peak: calibration.noise_peak_db.toFixed(0),
+                })}
+              </p>
+            )}
+          </div>
+        </SettingContainer>
+      </>
+    );
+  },
+);
@@ -0,0 +1,165 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { commands, WhisperDecoding } from "@/bindings";
+import { Input } from "../ui/Input";
+import { Textarea } from "../ui/Textarea";
+import { Button } from "../ui/Button";
+import { SettingContainer } from "../ui/SettingContainer";
+import { ToggleSwitch } from "../ui/ToggleSwitch";
+import { useSettings } from "../../hooks/useSettings";
+
+interface WhisperDecodingSettingsProps {
+  descriptionMode?: "inline" | "tooltip";
+  grouped?: boolean;
+}
+
+type NumberField =
+  | "beam_size"
+  | "temperature"
+  | "temperature_increment"
+  | "no_speech_threshold"
+  | "threads";
+
+// Input attributes matching the ranges set_whisper_decoding accepts
+const NUMBER_FIELDS: { field: NumberField; min: string; step: string }[] = [
+  { field: "beam_size", min: "1", step: "1" },
+  { field: "temperature", min: "0", step: "0.05" },
+  { field: "temperature_increment", min: "0", step: "0.1" },
+  { field: "no_speech_threshold", min: "0", step: "0.05" },
+  { field: "threads", min: "1", step: "1" },
+];
+
+export const WhisperDecodingSettings: React.FC<WhisperDecodingSettingsProps> =
+  React.memo(({ descriptionMode = "tooltip", grouped = false }) => {
+    const { t } = useTranslation();
+    const { getSetting, refreshSettings } = useSettings();
+    const decoding = getSetting("whisper_decoding");
+    const [draft, setDraft] = useState<WhisperDecoding>(decoding ?? {});
+    const [isSaving, setIsSaving] = useState(false);
+
+    useEffect(() => {
+      if (decoding) {
+        setDraft(decoding);
+      }
+    }, [decoding]);
+
+    const handleNumberChange = (
+      field: NumberField,
+      event: React.ChangeEvent<HTMLInputElement>,
+    ) => {
+      const value = parseFloat(event.target.value);
+      if (field === "threads" && event.target.value === "") {
+        // Empty means one thread per core
+        setDraft({ ...draft, threads: null });
+      } else if (!isNaN(value)) {
+        setDraft({ ...draft, [field]: value });
+      }
+    };
+
+    const handleApply = async () => {
+      setIsSaving(true);
+      try {
+        const result = await commands.setWhisperDecoding({
+          ...draft,
+          initial_prompt: draft.initial_prompt?.trim() || null,
+        });
+        if (result.status === "ok") {
+          await refreshSettings();
+          toast.success(t("settings.advanced.whisperDecoding.applied"));
+        } else {
+          toast.error(
+            t("settings.advanced.whisperDecoding.error", {
+              error: result.error,
+            }),
+          );
+        }
+      } finally {
+        setIsSaving(false);
+      }
+    };
+
+    return (
+      <>
+        <SettingContainer
+          title={t("settings.advanced.whisperDecoding.prompt.title")}
+          description={t(
+            "settings.advanced.whisperDecoding.prompt.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <Textarea
+            value={draft.initial_prompt ?? ""}
+            onChange={(event) =>
+              setDraft({ ...draft, initial_prompt: event.target.value })
+            }
+            placeholder={t(
+              "settings.advanced.whisperDecoding.prompt.placeholder",
+            )}
+            variant="compact"
+            disabled={isSaving}
+            className="w-full"
+          />
+        </SettingContainer>
+        <ToggleSwitch
+          checked={draft.prompt_custom_words ?? false}
+          onChange={(enabled) =>
+            setDraft({ ...draft, prompt_custom_words: enabled })
+          }
+          disabled={isSaving}
+          label={t("settings.advanced.whisperDecoding.promptCustomWords.label")}
+          description={t(
+            "settings.advanced.whisperDecoding.promptCustomWords.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+        />
+        <SettingContainer
+          title={t("settings.advanced.whisperDecoding.tuning.title")}
+          description={t(
+            "settings.advanced.whisperDecoding.tuning.description",
+          )}
+          descriptionMode={descriptionMode}
+          grouped={grouped}
+          layout="stacked"
+        >
+          <div className="space-y-3">
+            <div className="grid grid-cols-5 gap-2 items-center">
+              {NUMBER_FIELDS.map(({ field, min, step }) => (
+                <label key={field} className="text-xs text-mid-gray space-y-1">
+                  <span className="block">
+                    {t(`settings.advanced.whisperDecoding.fields.${field}`)}
+                  </span>
+                  <Input
+                    type="number"
+                    min={min}
+                    step={step}
+                    variant="compact"
+                    value={draft[field] ?? ""}
+                    placeholder={
+                      field === "threads"
+                        ? t("settings.advanced.whisperDecoding.autoThreads")
+                        : undefined
+                    }
+                    onChange={(event) => handleNumberChange(field, event)}
+                    disabled={isSaving}
+                    className="w-24"
+                  />
+                </label>
+              ))}
+            </div>
+            <Button
+              variant="primary"
+              size="sm"
+              onClick={handleApply}
+              disabled={isSaving}
+            >
+              {t("settings.advanced.whisperDecoding.apply")}
+            </Button>
+          </div>
+        </SettingContainer>
+      </>
+    );
+  });
@@ -13,19 +13,35 @@ import { ClipboardHandlingSetting } from "../ClipboardHandling";
 import { AutoSubmit } from "../AutoSubmit";
 import { PostProcessingToggle } from "../PostProcessingToggle";
 import { AppendTrailingSpace } from "../AppendTrailingSpace";
+import { UndoReopensRecording } from "../UndoReopensRecording";
 import { HistoryLimit } from "../HistoryLimit";
 import { RecordingRetentionPeriodSelector } from "../RecordingRetentionPeriod";
+import { HistoryEncryption } from "../HistoryEncryption";
+import { RecordingFormatSelector } from "../RecordingFormat";
+import { RetentionPoliciesEditor } from "../RetentionPolicies";
+import { HandsFree } from "../HandsFree";
+import { AutoStop } from "../AutoStop";
+import { AudioProcessing } from "../AudioProcessing";
+import { VadSettings } from "../VadSettings";
+import { WhisperDecodingSettings } from "../WhisperDecodingSettings";
+import { MicrophoneTest } from "../MicrophoneTest";
+import { ModelCatalogSource } from "../ModelCatalogSource";
+import { NetworkSettingsEditor } from "../NetworkSettings";
+import { DownloadLimits } from "../DownloadLimits";
 import { ExperimentalToggle } from "../ExperimentalToggle";
 import { useSettings } from "../../../hooks/useSettings";
 import { KeyboardImplementationSelector } from "../debug/KeyboardImplementationSelector";
 import { BetaChannelToggle } from "../BetaChannelToggle";
 
 import { GlobalShortcutInput } from "../GlobalShortcutInput";
+import { ShortcutInput } from "../ShortcutInput";
 
 export const AdvancedSettings: React.FC = () => {
   const { t } = useTranslation();
   const { getSetting } = useSettings();
   const experimentalEnabled = getSetting("experimental_enabled") || false;
+  // The per-kind policies replace the history limit and retention period
+  const hasRetentionPolicies = getSetting("retention_policies") != null;
 
   return (
     <div className="w-full space-y-12">
@@ -45,24 +61,52 @@ export const AdvancedSettings: React.FC = () => {
           <BetaChannelToggle descriptionMode="tooltip" grouped={true} />
         </SettingsGroup>
 
+        <SettingsGroup title={t("settings.advanced.groups.recording")}>
+          <HandsFree descriptionMode="tooltip" grouped={true} />
+          <AutoStop descriptionMode="tooltip" grouped={true} />
+          <AudioProcessing descriptionMode="tooltip" grouped={true} />
+          <VadSettings descriptionMode="tooltip" grouped={true} />
+          <MicrophoneTest descriptionMode="tooltip" grouped={true} />
+        </SettingsGroup>
+
         <SettingsGroup title={t("settings.advanced.groups.output")}>
           <PasteMethodSetting descriptionMode="tooltip" grouped={true} />
           <TypingToolSetting descriptionMode="tooltip" grouped={true} />
           <ClipboardHandlingSetting descriptionMode="tooltip" grouped={true} />
           <AutoSubmit descriptionMode="tooltip" grouped={true} />
+          <ShortcutInput
+            shortcutId="undo_last_dictation"
+            descriptionMode="tooltip"
+            grouped={true}
+          />
+          <UndoReopensRecording descriptionMode="tooltip" grouped={true} />
         </SettingsGroup>
 
         <SettingsGroup title={t("settings.advanced.groups.transcription")}>
           <CustomWords descriptionMode="tooltip" grouped />
+          <WhisperDecodingSettings descriptionMode="tooltip" grouped={true} />
           <AppendTrailingSpace descriptionMode="tooltip" grouped={true} />
         </SettingsGroup>
 
+        <SettingsGroup title={t("settings.advanced.groups.models")}>
+          <ModelCatalogSource descriptionMode="tooltip" grouped={true} />
+          <NetworkSettingsEditor descriptionMode="tooltip" grouped={true} />
+          <DownloadLimits descriptionMode="tooltip" grouped={true} />
+        </SettingsGroup>
+
         <SettingsGroup title={t("settings.advanced.groups.history")}>
-          <HistoryLimit descriptionMode="tooltip" grouped={true} />
-          <RecordingRetentionPeriodSelector
-            descriptionMode="tooltip"
-            grouped={true}
-          />
+          {!hasRetentionPolicies && (
+            <>
+              <HistoryLimit descriptionMode="tooltip" grouped={true} />
+              <RecordingRetentionPeriodSelector
+                descriptionMode="tooltip"
+                grouped={true}
+              />
+            </>
+          )}
+          <RetentionPoliciesEditor descriptionMode="tooltip" grouped={true} />
+          <HistoryEncryption descriptionMode="tooltip" grouped={true} />
+          <RecordingFormatSelector descriptionMode="tooltip" grouped={true} />
         </SettingsGroup>
 
         <SettingsGroup title="Experimental">
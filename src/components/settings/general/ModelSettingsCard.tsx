@@ -2,19 +2,27 @@ import React from "react";
 import { useTranslation } from "react-i18next";
 import { SettingsGroup } from "../../ui/SettingsGroup";
 import { LanguageSelector } from "../LanguageSelector";
+import { AutoDetectLanguages } from "../AutoDetectLanguages";
+import { ChineseVariant } from "../ChineseVariant";
 import { TranslateToEnglish } from "../TranslateToEnglish";
 import { useModelStore } from "../../../stores/modelStore";
+import { useSettings } from "../../../hooks/useSettings";
 import type { ModelInfo } from "@/bindings";
 
 export const ModelSettingsCard: React.FC = () => {
   const { t } = useTranslation();
   const { currentModel, models } = useModelStore();
+  const { getSetting } = useSettings();
 
   const currentModelInfo = models.find((m: ModelInfo) => m.id === currentModel);
 
   const supportsLanguageSelection =
     currentModelInfo?.engine_type === "Whisper" ||
     currentModelInfo?.engine_type === "SenseVoice";
+  // Only Whisper reports the language it detected
+  const supportsAutoDetectOptions =
+    currentModelInfo?.engine_type === "Whisper" &&
+    (getSetting("selected_language") || "auto") === "auto";
   const supportsTranslation = currentModelInfo?.supports_translation ?? false;
   const hasAnySettings = supportsLanguageSelection || supportsTranslation;
 
@@ -36,6 +44,16 @@ export const ModelSettingsCard: React.FC = () => {
           supportedLanguages={currentModelInfo.supported_languages}
         />
       )}
+      {supportsAutoDetectOptions && (
+        <>
+          <AutoDetectLanguages
+            descriptionMode="tooltip"
+            grouped={true}
+            supportedLanguages={currentModelInfo.supported_languages}
+          />
+          <ChineseVariant descriptionMode="tooltip" grouped={true} />
+        </>
+      )}
       {supportsTranslation && (
         <TranslateToEnglish descriptionMode="tooltip" grouped={true} />
       )}
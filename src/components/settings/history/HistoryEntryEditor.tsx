@@ -0,0 +1,140 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { Plus } from "lucide-react";
+import {
+  commands,
+  type HistoryEntry,
+  type TranscriptionRevision,
+} from "@/bindings";
+import { formatDateTime } from "@/utils/dateFormat";
+import { useSettings } from "../../../hooks/useSettings";
+import { Button } from "../../ui/Button";
+import { Textarea } from "../../ui/Textarea";
+
+interface HistoryEntryEditorProps {
+  entry: HistoryEntry;
+  initialText: string;
+  onClose: () => void;
+}
+
+// Corrects a history entry's text. Words the correction introduced can be
+// added to the custom words so the next transcription gets them right.
+export const HistoryEntryEditor: React.FC<HistoryEntryEditorProps> = ({
+  entry,
+  initialText,
+  onClose,
+}) => {
+  const { t, i18n } = useTranslation();
+  const { getSetting, updateSetting } = useSettings();
+  const [draft, setDraft] = useState(initialText);
+  const [isSaving, setIsSaving] = useState(false);
+  const [suggestedWords, setSuggestedWords] = useState<string[]>([]);
+  const [revisions, setRevisions] = useState<TranscriptionRevision[]>([]);
+  const customWords = getSetting("custom_words") || [];
+
+  useEffect(() => {
+    commands.getHistoryEntryRevisions(entry.id).then((result) => {
+      if (result.status === "ok") {
+        setRevisions(result.data);
+      }
+    });
+  }, [entry.id]);
+
+  const handleSave = async () => {
+    setIsSaving(true);
+    try {
+      const result = await commands.editHistoryEntry(entry.id, draft.trim());
+      if (result.status === "ok") {
+        setRevisions([...revisions, result.data.revision]);
+        setSuggestedWords(result.data.suggested_words);
+        if (result.data.suggested_words.length === 0) {
+          onClose();
+        }
+      } else {
+        toast.error(t("settings.history.edit.error", { error: result.error }));
+      }
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleAddWord = (word: string) => {
+    if (!customWords.includes(word)) {
+      updateSetting("custom_words", [...customWords, word]);
+    }
+    const remaining = suggestedWords.filter((w) => w !== word);
+    setSuggestedWords(remaining);
+    if (remaining.length === 0) {
+      onClose();
+    }
+  };
+
+  if (suggestedWords.length > 0) {
+    return (
+      <div className="bg-white/5 rounded-2xl p-5 border border-white/5 space-y-3">
+        <p className="text-sm">{t("settings.history.edit.suggestions")}</p>
+        <div className="flex flex-wrap gap-1">
+          {suggestedWords.map((word) => (
+            <Button
+              key={word}
+              onClick={() => handleAddWord(word)}
+              variant="secondary"
+              size="sm"
+              className="inline-flex items-center gap-1"
+              aria-label={t("settings.history.edit.addWord", { word })}
+            >
+              <Plus size={12} />
+              <span>{word}</span>
+            </Button>
+          ))}
+        </div>
+        <Button onClick={onClose} variant="secondary" size="sm">
+          {t("settings.history.edit.done")}
+        </Button>
+      </div>
+    );
+  }
+
+  return (
+    <div className="bg-white/5 rounded-2xl p-5 border border-white/5 space-y-3">
+      <Textarea
+        value={draft}
+        onChange={(event) => setDraft(event.target.value)}
+        disabled={isSaving}
+        className="w-full"
+      />
+      <div className="flex gap-2">
+        <Button
+          onClick={handleSave}
+          variant="primary"
+          size="sm"
+          disabled={isSaving || !draft.trim() || draft.trim() === initialText}
+        >
+          {t("settings.history.edit.save")}
+        </Button>
+        <Button onClick={onClose} variant="secondary" size="sm">
+          {t("settings.history.edit.cancel")}
+        </Button>
+      </div>
+      {revisions.length > 0 && (
+        <div className="space-y-2 pt-2">
+          <p className="text-xs font-bold uppercase tracking-wider text-text/40">
+            {t("settings.history.edit.history")}
+          </p>
+          <p className="text-xs text-text-muted">
+            {t("settings.history.edit.original", {
+              text: entry.post_processed_text ?? entry.transcription_text,
+            })}
+          </p>
+          {revisions.map((revision) => (
+            <p key={revision.id} className="text-xs text-text-muted">
+              {formatDateTime(String(revision.timestamp), i18n.language)}:{" "}
+              {revision.text}
+            </p>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,107 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { ClipboardPaste, Copy, Loader2 } from "lucide-react";
+import { commands, type TranscriptionVersion } from "@/bindings";
+import { formatDateTime } from "@/utils/dateFormat";
+
+interface HistoryEntryVersionsProps {
+  entryId: number;
+}
+
+// Re-transcriptions of one history entry, newest first, each of which can be
+// copied or pasted instead of the entry's own text
+export const HistoryEntryVersions: React.FC<HistoryEntryVersionsProps> = ({
+  entryId,
+}) => {
+  const { t, i18n } = useTranslation();
+  const [versions, setVersions] = useState<TranscriptionVersion[] | null>(
+    null,
+  );
+
+  useEffect(() => {
+    commands.getHistoryEntryVersions(entryId).then((result) => {
+      if (result.status === "ok") {
+        setVersions([...result.data].reverse());
+      } else {
+        setVersions([]);
+        console.error("Failed to load versions:", result.error);
+      }
+    });
+  }, [entryId]);
+
+  const handleCopy = async (versionId: number) => {
+    const result = await commands.copyHistoryText(entryId, versionId);
+    if (result.status === "ok") {
+      toast.success(t("settings.history.versions.copied"));
+    } else {
+      toast.error(result.error);
+    }
+  };
+
+  const handlePaste = async (versionId: number) => {
+    const result = await commands.pasteHistoryText(entryId, versionId);
+    if (result.status === "error") {
+      toast.error(result.error);
+    }
+  };
+
+  if (versions === null) {
+    return (
+      <div className="flex justify-center py-4">
+        <Loader2 className="w-5 h-5 animate-spin text-accent" />
+      </div>
+    );
+  }
+
+  if (versions.length === 0) {
+    return (
+      <p className="mac-muted text-sm px-1">
+        {t("settings.history.versions.empty")}
+      </p>
+    );
+  }
+
+  return (
+    <div className="space-y-3">
+      {versions.map((version) => (
+        <div
+          key={version.id}
+          className="bg-white/5 rounded-2xl p-4 border border-white/5 space-y-2"
+        >
+          <div className="flex items-center justify-between gap-2">
+            <span className="text-xs text-text-muted">
+              {t("settings.history.versions.details", {
+                date: formatDateTime(String(version.timestamp), i18n.language),
+                model: version.model_id,
+                language:
+                  version.detected_language ??
+                  version.language ??
+                  t("settings.history.versions.autoLanguage"),
+              })}
+            </span>
+            <div className="flex items-center gap-1">
+              <button
+                onClick={() => handleCopy(version.id)}
+                className="p-2 rounded-xl hover:bg-accent/10 hover:text-accent transition-all"
+                title={t("settings.history.versions.copy")}
+              >
+                <Copy size={16} />
+              </button>
+              <button
+                onClick={() => handlePaste(version.id)}
+                className="p-2 rounded-xl hover:bg-accent/10 hover:text-accent transition-all"
+                title={t("settings.history.versions.paste")}
+              >
+                <ClipboardPaste size={16} />
+              </button>
+            </div>
+          </div>
+          <p className="text-[14px] leading-relaxed text-text/90 select-text">
+            {version.post_processed_text ?? version.transcription_text}
+          </p>
+        </div>
+      ))}
+    </div>
+  );
+};
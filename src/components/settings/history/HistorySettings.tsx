@@ -12,27 +12,41 @@ import {
   Clock,
   ExternalLink,
   History,
-  Volume2
+  Volume2,
+  ClipboardPaste,
+  Layers,
+  Pencil,
 } from "lucide-react";
-import { convertFileSrc } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
-import { readFile } from "@tauri-apps/plugin-fs";
 import { commands, type HistoryEntry } from "@/bindings";
 import { formatDateTime } from "@/utils/dateFormat";
-import { useOsType } from "@/hooks/useOsType";
+import { getRecordingUrl } from "@/lib/utils/recording";
+import { HistoryEntryVersions } from "./HistoryEntryVersions";
+import { HistoryEntryEditor } from "./HistoryEntryEditor";
+import { Alert } from "../../ui/Alert";
 
 export const HistorySettings: React.FC = () => {
   const { t } = useTranslation();
-  const osType = useOsType();
   const [historyEntries, setHistoryEntries] = useState<HistoryEntry[]>([]);
   const [loading, setLoading] = useState(true);
   const [searchQuery, setSearchQuery] = useState("");
 
   const [viewMode, setViewMode] = useState<"all" | "favorites">("all");
+  const [unavailableReason, setUnavailableReason] = useState<string | null>(
+    null,
+  );
+
+  useEffect(() => {
+    commands.getHistoryUnavailableReason().then((result) => {
+      if (result.status === "ok") {
+        setUnavailableReason(result.data);
+      }
+    });
+  }, []);
 
   const loadHistoryEntries = useCallback(async () => {
     try {
-      const result = await commands.getHistoryEntries();
+      const result = await commands.getHistoryEntries(null);
       if (result.status === "ok") {
         setHistoryEntries(result.data);
       }
@@ -78,34 +92,29 @@ export const HistorySettings: React.FC = () => {
     }
   };
 
-  const copyToClipboard = async (text: string) => {
-    try {
-      await navigator.clipboard.writeText(text);
-    } catch (error) {
-      console.error("Failed to copy to clipboard:", error);
+  // Both use the entry's final text: its latest edit or post-processed text
+  const copyEntryText = async (id: number) => {
+    const result = await commands.copyHistoryText(id, null);
+    if (result.status === "error") {
+      console.error("Failed to copy to clipboard:", result.error);
     }
   };
 
-  const getAudioUrl = useCallback(
-    async (fileName: string) => {
-      try {
-        const result = await commands.getAudioFilePath(fileName);
-        if (result.status === "ok") {
-          if (osType === "linux") {
-            const fileData = await readFile(result.data);
-            const blob = new Blob([fileData], { type: "audio/wav" });
-            return URL.createObjectURL(blob);
-          }
-          return convertFileSrc(result.data, "asset");
-        }
-        return null;
-      } catch (error) {
-        console.error("Failed to get audio file path:", error);
-        return null;
-      }
-    },
-    [osType],
-  );
+  const pasteEntryText = async (id: number) => {
+    const result = await commands.pasteHistoryText(id, null);
+    if (result.status === "error") {
+      console.error("Failed to paste history entry:", result.error);
+    }
+  };
+
+  const getAudioUrl = useCallback(async (fileName: string) => {
+    try {
+      return await getRecordingUrl(fileName);
+    } catch (error) {
+      console.error("Failed to load recording:", error);
+      return null;
+    }
+  }, []);
 
   const deleteAudioEntry = async (id: number) => {
     try {
@@ -156,6 +165,12 @@ export const HistorySettings: React.FC = () => {
         </div>
       </div>
 
+      {unavailableReason && (
+        <Alert variant="error">
+          {t("settings.history.unavailable", { reason: unavailableReason })}
+        </Alert>
+      )}
+
       <div className="relative group">
         <div className="absolute left-4 top-1/2 -translate-y-1/2 text-text-muted pointer-events-none transition-colors group-focus-within:text-accent">
           <Search size={18} />
@@ -190,7 +205,8 @@ export const HistorySettings: React.FC = () => {
                 key={entry.id}
                 entry={entry}
                 onToggleSaved={() => toggleSaved(entry.id)}
-                onCopyText={() => copyToClipboard(entry.transcription_text)}
+                onCopyText={() => copyEntryText(entry.id)}
+                onPasteText={() => pasteEntryText(entry.id)}
                 getAudioUrl={getAudioUrl}
                 deleteAudio={deleteAudioEntry}
               />
@@ -206,6 +222,7 @@ interface HistoryEntryProps {
   entry: HistoryEntry;
   onToggleSaved: () => void;
   onCopyText: () => void;
+  onPasteText: () => void;
   getAudioUrl: (fileName: string) => Promise<string | null>;
   deleteAudio: (id: number) => Promise<void>;
 }
@@ -214,11 +231,17 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
   entry,
   onToggleSaved,
   onCopyText,
+  onPasteText,
   getAudioUrl,
   deleteAudio,
 }) => {
-  const { i18n } = useTranslation();
+  const { t, i18n } = useTranslation();
   const [showCopied, setShowCopied] = useState(false);
+  const [showVersions, setShowVersions] = useState(false);
+  const [isEditing, setIsEditing] = useState(false);
+
+  const finalText =
+    entry.edited_text ?? entry.post_processed_text ?? entry.transcription_text;
 
   const formattedDate = formatDateTime(String(entry.timestamp), i18n.language);
   const [isSpeaking, setIsSpeaking] = useState(false);
@@ -276,6 +299,31 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
           >
             {showCopied ? <Check size={18} /> : <Copy size={18} />}
           </button>
+          <button
+            onClick={() => setIsEditing(!isEditing)}
+            className={`p-2.5 rounded-xl hover:bg-accent/10 hover:text-accent transition-all ${isEditing ? "text-accent bg-accent/10" : ""}`}
+            title={t("settings.history.edit.title")}
+          >
+            <Pencil size={18} />
+          </button>
+          <button
+            onClick={onPasteText}
+            className="p-2.5 rounded-xl hover:bg-accent/10 hover:text-accent transition-all"
+            title={t("settings.history.pasteToApp")}
+          >
+            <ClipboardPaste size={18} />
+          </button>
+          <button
+            onClick={() => setShowVersions(!showVersions)}
+            className={`p-2.5 rounded-xl hover:bg-accent/10 hover:text-accent transition-all ${showVersions ? "text-accent bg-accent/10" : ""}`}
+            title={
+              showVersions
+                ? t("settings.history.hideVersions")
+                : t("settings.history.showVersions")
+            }
+          >
+            <Layers size={18} />
+          </button>
           <button
             onClick={onToggleSaved}
             className={`p-2.5 rounded-xl transition-all ${entry.saved ? "text-amber-500 bg-amber-500/10" : "hover:bg-amber-500/10 hover:text-amber-500"}`}
@@ -293,11 +341,21 @@ const HistoryEntryComponent: React.FC<HistoryEntryProps> = ({
         </div>
       </div>
 
-      <div className="bg-white/5 rounded-2xl p-5 border border-white/5 group-hover:border-white/10 transition-all">
-        <p className="text-[15px] leading-relaxed text-text/90 select-text selection:bg-accent/30 tracking-tight">
-          {entry.transcription_text}
-        </p>
-      </div>
+      {isEditing ? (
+        <HistoryEntryEditor
+          entry={entry}
+          initialText={finalText}
+          onClose={() => setIsEditing(false)}
+        />
+      ) : (
+        <div className="bg-white/5 rounded-2xl p-5 border border-white/5 group-hover:border-white/10 transition-all">
+          <p className="text-[15px] leading-relaxed text-text/90 select-text selection:bg-accent/30 tracking-tight">
+            {finalText}
+          </p>
+        </div>
+      )}
+
+      {showVersions && <HistoryEntryVersions entryId={entry.id} />}
 
       <div className="w-full pt-2">
         <AudioPlayer
@@ -22,6 +22,8 @@ export { GlobalShortcutInput } from "./GlobalShortcutInput";
 export { TypeZeroKeysShortcutInput } from "./TypeZeroKeysShortcutInput";
 export { ShortcutInput } from "./ShortcutInput";
 export { TranslateToEnglish } from "./TranslateToEnglish";
+export { AutoDetectLanguages } from "./AutoDetectLanguages";
+export { ChineseVariant } from "./ChineseVariant";
 export { CustomWords } from "./CustomWords";
 export { PostProcessingToggle } from "./PostProcessingToggle";
 export { PostProcessingSettingsApi } from "./PostProcessingSettingsApi";
@@ -33,3 +35,15 @@ export { HistoryLimit } from "./HistoryLimit";
 export { RecordingRetentionPeriodSelector } from "./RecordingRetentionPeriod";
 export { AutostartToggle } from "./AutostartToggle";
 export { UpdateChecksToggle } from "./UpdateChecksToggle";
+export { HistoryEncryption } from "./HistoryEncryption";
+export { RecordingFormatSelector } from "./RecordingFormat";
+export { RetentionPoliciesEditor } from "./RetentionPolicies";
+export { HandsFree } from "./HandsFree";
+export { AutoStop } from "./AutoStop";
+export { AudioProcessing } from "./AudioProcessing";
+export { VadSettings } from "./VadSettings";
+export { WhisperDecodingSettings } from "./WhisperDecodingSettings";
+export { MicrophoneTest } from "./MicrophoneTest";
+export { ModelCatalogSource } from "./ModelCatalogSource";
+export { NetworkSettingsEditor } from "./NetworkSettings";
+export { DownloadLimits } from "./DownloadLimits";
@@ -0,0 +1,98 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { listen } from "@tauri-apps/api/event";
+import { toast } from "sonner";
+import { Pause, Play, X } from "lucide-react";
+import { commands, DownloadQueueState } from "@/bindings";
+import { useModelStore } from "@/stores/modelStore";
+import { Button } from "../../ui/Button";
+
+export const DownloadQueue: React.FC = () => {
+  const { t } = useTranslation();
+  const [queue, setQueue] = useState<DownloadQueueState | null>(null);
+  const { models, downloadProgress, downloadModel, cancelDownload } =
+    useModelStore();
+
+  useEffect(() => {
+    commands.getDownloadQueue().then((result) => {
+      if (result.status === "ok") {
+        setQueue(result.data);
+      }
+    });
+
+    const unlisten = listen<DownloadQueueState>(
+      "download-queue-changed",
+      (event) => setQueue(event.payload),
+    );
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const handlePause = async (modelId: string) => {
+    const result = await commands.pauseDownload(modelId);
+    if (result.status === "error") {
+      toast.error(
+        t("settings.models.queue.pauseError", { error: result.error }),
+      );
+    }
+  };
+
+  if (!queue || queue.downloads.length === 0) {
+    return null;
+  }
+
+  return (
+    <div className="mac-card p-4 space-y-2">
+      <h2 className="mac-section-header">{t("settings.models.queue.title")}</h2>
+      <ul className="space-y-1">
+        {queue.downloads.map(({ model_id, status }) => {
+          const name =
+            models.find((m) => m.id === model_id)?.name ?? model_id;
+          const progress = downloadProgress[model_id];
+          return (
+            <li
+              key={model_id}
+              className="flex items-center justify-between gap-2 text-sm"
+            >
+              <span className="truncate">{name}</span>
+              <span className="flex items-center gap-2 text-xs text-mid-gray">
+                {t(`settings.models.queue.status.${status}`)}
+                {status === "downloading" &&
+                  progress &&
+                  ` ${Math.round(progress.percentage)}%`}
+                {status === "paused" ? (
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    onClick={() => downloadModel(model_id)}
+                    title={t("settings.models.queue.resume")}
+                  >
+                    <Play size={14} />
+                  </Button>
+                ) : (
+                  <Button
+                    variant="ghost"
+                    size="sm"
+                    onClick={() => handlePause(model_id)}
+                    title={t("settings.models.queue.pause")}
+                  >
+                    <Pause size={14} />
+                  </Button>
+                )}
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => cancelDownload(model_id)}
+                  title={t("settings.models.queue.cancel")}
+                >
+                  <X size={14} />
+                </Button>
+              </span>
+            </li>
+          );
+        })}
+      </ul>
+    </div>
+  );
+};
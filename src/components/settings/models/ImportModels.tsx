@@ -0,0 +1,91 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { open } from "@tauri-apps/plugin-dialog";
+import { toast } from "sonner";
+import { FolderInput, Upload } from "lucide-react";
+import { commands, ModelImport } from "@/bindings";
+import { Button } from "../../ui/Button";
+
+const MODEL_EXTENSIONS = ["bin", "BIN", "gz", "GZ", "tgz", "TGZ"];
+
+export const ImportModels: React.FC = () => {
+  const { t } = useTranslation();
+  const [isImporting, setIsImporting] = useState(false);
+
+  const report = (imports: ModelImport[]) => {
+    for (const result of imports) {
+      const model = result.model_id ?? result.source;
+      if (result.status === "imported") {
+        toast.success(t("settings.models.import.imported", { model }));
+      } else if (result.status === "already_installed") {
+        toast.info(t("settings.models.import.alreadyInstalled", { model }));
+      } else {
+        toast.error(
+          t("settings.models.import.failed", {
+            model,
+            error: result.error ?? "",
+          }),
+        );
+      }
+    }
+  };
+
+  // Files go through import_model so they can be matched to the catalog;
+  // a folder is treated as an offline bundle of several models
+  const handleImport = async (bundle: boolean) => {
+    const path = await open(
+      bundle
+        ? { directory: true }
+        : {
+            filters: [
+              {
+                name: t("settings.models.import.filterName"),
+                extensions: MODEL_EXTENSIONS,
+              },
+            ],
+          },
+    );
+    if (typeof path !== "string") return;
+
+    setIsImporting(true);
+    try {
+      const result = bundle
+        ? await commands.importModelBundle(path)
+        : await commands.importModel(path, null);
+      if (result.status === "ok") {
+        report(Array.isArray(result.data) ? result.data : [result.data]);
+      } else {
+        toast.error(
+          t("settings.models.import.error", { error: result.error }),
+        );
+      }
+    } finally {
+      setIsImporting(false);
+    }
+  };
+
+  return (
+    <div className="flex items-center gap-2">
+      <Button
+        variant="secondary"
+        size="sm"
+        onClick={() => handleImport(false)}
+        disabled={isImporting}
+        className="flex items-center gap-1"
+      >
+        <Upload size={14} />
+        {t("settings.models.import.file")}
+      </Button>
+      <Button
+        variant="secondary"
+        size="sm"
+        onClick={() => handleImport(true)}
+        disabled={isImporting}
+        className="flex items-center gap-1"
+      >
+        <FolderInput size={14} />
+        {t("settings.models.import.bundle")}
+      </Button>
+    </div>
+  );
+};
@@ -6,6 +6,9 @@ import { ModelCard } from "@/components/onboarding/ModelCard";
 import { useModelStore } from "@/stores/modelStore";
 import { LANGUAGES } from "@/lib/constants/languages.ts";
 import type { ModelInfo } from "@/bindings";
+import { VerifyModels } from "./VerifyModels";
+import { ImportModels } from "./ImportModels";
+import { DownloadQueue } from "./DownloadQueue";
 
 export const ModelsSettings: React.FC = () => {
   const { t } = useTranslation();
@@ -88,19 +91,22 @@ export const ModelsSettings: React.FC = () => {
         </p>
       </div>
 
-      <div className="flex gap-2 p-1 bg-white/5 border border-white/10 rounded-2xl w-fit">
-        <button
-          onClick={() => setActiveTab("transcription")}
-          className={`px-6 py-2.5 rounded-xl text-sm font-bold transition-all ${activeTab === "transcription" ? "bg-accent text-white shadow-lg shadow-accent/20" : "text-text-muted hover:text-text hover:bg-white/5"}`}
-        >
-          Transcription
-        </button>
-        <button
-          onClick={() => setActiveTab("speech")}
-          className={`px-6 py-2.5 rounded-xl text-sm font-bold transition-all ${activeTab === "speech" ? "bg-accent text-white shadow-lg shadow-accent/20" : "text-text-muted hover:text-text hover:bg-white/5"}`}
-        >
-          Speech (TTS)
-        </button>
+      <div className="flex items-center justify-between gap-4">
+        <div className="flex gap-2 p-1 bg-white/5 border border-white/10 rounded-2xl w-fit">
+          <button
+            onClick={() => setActiveTab("transcription")}
+            className={`px-6 py-2.5 rounded-xl text-sm font-bold transition-all ${activeTab === "transcription" ? "bg-accent text-white shadow-lg shadow-accent/20" : "text-text-muted hover:text-text hover:bg-white/5"}`}
+          >
+            Transcription
+          </button>
+          <button
+            onClick={() => setActiveTab("speech")}
+            className={`px-6 py-2.5 rounded-xl text-sm font-bold transition-all ${activeTab === "speech" ? "bg-accent text-white shadow-lg shadow-accent/20" : "text-text-muted hover:text-text hover:bg-white/5"}`}
+          >
+            Speech (TTS)
+          </button>
+        </div>
+        <ImportModels />
       </div>
 
       <div className="flex items-center gap-4 border-b border-white/5 pb-8">
@@ -129,12 +135,15 @@ export const ModelsSettings: React.FC = () => {
       </div>
 
       <div className="space-y-12">
+        <DownloadQueue />
+
         {downloadedModels.length > 0 && (
           <div className="space-y-6">
             <h2 className="mac-section-header flex items-center gap-2">
               <Brain size={16} />
               Installed Models
             </h2>
+            <VerifyModels />
             <div className="grid grid-cols-1 md:grid-cols-2 gap-6">
               {downloadedModels.map((model) => {
                 const isTTS = model.engine_type === "Piper" || model.engine_type === "XTTS";
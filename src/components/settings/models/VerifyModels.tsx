@@ -0,0 +1,72 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { toast } from "sonner";
+import { ShieldCheck } from "lucide-react";
+import { commands, ModelVerification } from "@/bindings";
+import { Button } from "../../ui/Button";
+
+const STATUS_CLASSES: Record<ModelVerification["status"], string> = {
+  verified: "text-green-400",
+  unverified: "text-text-muted",
+  corrupt: "text-red-400",
+};
+
+export const VerifyModels: React.FC = () => {
+  const { t } = useTranslation();
+  const [isVerifying, setIsVerifying] = useState(false);
+  const [results, setResults] = useState<ModelVerification[] | null>(null);
+
+  const handleVerify = async () => {
+    setIsVerifying(true);
+    try {
+      const result = await commands.verifyInstalledModels();
+      if (result.status === "ok") {
+        setResults(result.data);
+      } else {
+        toast.error(
+          t("settings.models.verify.error", { error: result.error }),
+        );
+      }
+    } finally {
+      setIsVerifying(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <Button
+        variant="secondary"
+        size="sm"
+        onClick={handleVerify}
+        disabled={isVerifying}
+        className="flex items-center gap-1"
+      >
+        <ShieldCheck size={14} />
+        {isVerifying
+          ? t("settings.models.verify.verifying")
+          : t("settings.models.verify.start")}
+      </Button>
+      {results && (
+        <ul className="text-xs space-y-1">
+          {results.length === 0 && (
+            <li className="text-text-muted">
+              {t("settings.models.verify.none")}
+            </li>
+          )}
+          {results.map((result) => (
+            <li
+              key={result.model_id}
+              className={STATUS_CLASSES[result.status]}
+            >
+              {t(`settings.models.verify.status.${result.status}`, {
+                model: result.model_id,
+              })}
+              {result.redownloading &&
+                ` ${t("settings.models.verify.redownloading")}`}
+            </li>
+          ))}
+        </ul>
+      )}
+    </div>
+  );
+};
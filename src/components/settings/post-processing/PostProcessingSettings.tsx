@@ -145,6 +145,7 @@ const PostProcessingSettingsApiComponent: React.FC = () => {
 
 import { ShortcutInput as PostProcessShortcutInput } from "../ShortcutInput";
 import { useSettings } from "../../../hooks/useSettings";
+import { DETECTED_LANGUAGES } from "../../../lib/constants/languages";
 import { commands } from "@/bindings";
 import { useEffect, useState } from "react";
 
@@ -416,6 +417,111 @@ const PostProcessingSettingsPromptsComponent: React.FC = () => {
   );
 };
 
+const PostProcessingSettingsLanguagePromptsComponent: React.FC = () => {
+  const { t } = useTranslation();
+  const { getSetting, refreshSettings } = useSettings();
+  const [draftLanguage, setDraftLanguage] = useState<string | null>(null);
+  const [isSaving, setIsSaving] = useState(false);
+
+  const prompts = getSetting("post_process_prompts") || [];
+  const languagePrompts = getSetting("post_process_language_prompts") || {};
+  const routes = Object.entries(languagePrompts).filter(
+    (route): route is [string, string] => route[1] !== undefined,
+  );
+
+  const promptOptions = prompts.map((p) => ({ value: p.id, label: p.name }));
+  const languageOptions = DETECTED_LANGUAGES.filter(
+    (lang) => !(lang.value in languagePrompts),
+  );
+  const labelFor = (code: string) =>
+    DETECTED_LANGUAGES.find((lang) => lang.value === code)?.label ?? code;
+
+  const setRoute = async (language: string, promptId: string | null) => {
+    setIsSaving(true);
+    try {
+      const result = await commands.setPostProcessLanguagePrompt(
+        language,
+        promptId,
+      );
+      if (result.status === "ok") {
+        await refreshSettings();
+      } else {
+        console.error("Failed to route language prompt:", result.error);
+      }
+    } finally {
+      setIsSaving(false);
+    }
+  };
+
+  const handleAdd = async (promptId: string | null) => {
+    if (!draftLanguage || !promptId) return;
+    await setRoute(draftLanguage, promptId);
+    setDraftLanguage(null);
+  };
+
+  return (
+    <SettingContainer
+      title={t("settings.postProcessing.prompts.languagePrompts.title")}
+      description={t(
+        "settings.postProcessing.prompts.languagePrompts.description",
+      )}
+      descriptionMode="tooltip"
+      layout="stacked"
+      grouped={true}
+    >
+      <div className="space-y-3">
+        {routes.map(([language, promptId]) => (
+          <div key={language} className="flex items-center gap-3">
+            <span className="w-40 shrink-0 text-sm">{labelFor(language)}</span>
+            <div className="flex-1">
+              <Select
+                value={promptId}
+                options={promptOptions}
+                onChange={(value) => value && setRoute(language, value)}
+                disabled={isSaving}
+                className="w-full"
+              />
+            </div>
+            <Button
+              onClick={() => setRoute(language, null)}
+              variant="secondary"
+              size="md"
+              disabled={isSaving}
+            >
+              {t("settings.postProcessing.prompts.languagePrompts.remove")}
+            </Button>
+          </div>
+        ))}
+        <div className="flex items-center gap-3">
+          <div className="w-40 shrink-0">
+            <Select
+              value={draftLanguage}
+              options={languageOptions}
+              onChange={setDraftLanguage}
+              placeholder={t(
+                "settings.postProcessing.prompts.languagePrompts.language",
+              )}
+              disabled={isSaving}
+            />
+          </div>
+          <div className="flex-1">
+            <Select
+              value={null}
+              options={promptOptions}
+              onChange={handleAdd}
+              placeholder={t(
+                "settings.postProcessing.prompts.languagePrompts.prompt",
+              )}
+              disabled={isSaving || !draftLanguage}
+              className="w-full"
+            />
+          </div>
+        </div>
+      </div>
+    </SettingContainer>
+  );
+};
+
 export const PostProcessingSettingsApi = React.memo(
   PostProcessingSettingsApiComponent,
 );
@@ -426,6 +532,12 @@ export const PostProcessingSettingsPrompts = React.memo(
 );
 PostProcessingSettingsPrompts.displayName = "PostProcessingSettingsPrompts";
 
+export const PostProcessingSettingsLanguagePrompts = React.memo(
+  PostProcessingSettingsLanguagePromptsComponent,
+);
+PostProcessingSettingsLanguagePrompts.displayName =
+  "PostProcessingSettingsLanguagePrompts";
+
 export const PostProcessingSettings: React.FC = () => {
   const { t } = useTranslation();
 
@@ -451,6 +563,7 @@ export const PostProcessingSettings: React.FC = () => {
 
         <SettingsGroup title={t("settings.postProcessing.prompts.title")}>
           <PostProcessingSettingsPrompts />
+          <PostProcessingSettingsLanguagePrompts />
         </SettingsGroup>
       </div>
     </div>
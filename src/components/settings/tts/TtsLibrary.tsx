@@ -13,8 +13,9 @@ import {
     ChevronRight,
     Loader2
 } from "lucide-react";
-import { convertFileSrc } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { commands } from "../../../bindings";
+import { getRecordingUrl } from "@/lib/utils/recording";
 import { toast } from "sonner";
 import { format } from "date-fns";
 
@@ -37,7 +38,7 @@ export const TtsLibrary: React.FC = () => {
 
     const fetchHistory = async () => {
         try {
-            const result = await commands.getTtsHistory();
+            const result = await commands.getTtsHistory(null);
             if (result.status === "ok") {
                 setHistory(result.data as any);
             }
@@ -51,6 +52,15 @@ export const TtsLibrary: React.FC = () => {
 
     useEffect(() => {
         fetchHistory();
+
+        // Titles, tags and notes edited elsewhere
+        const unlistenUpdated = listen("tts-history-updated", () => {
+            fetchHistory();
+        });
+
+        return () => {
+            unlistenUpdated.then(u => u());
+        };
     }, []);
 
     const toggleFavorite = async (id: number) => {
@@ -92,35 +102,30 @@ export const TtsLibrary: React.FC = () => {
                 audio.load();
             }
 
-            const pathResult = await commands.getAudioFilePath(item.file_name as string);
-            if (pathResult.status === "ok") {
-                const assetUrl = convertFileSrc(pathResult.data as string);
-                console.log("Playing local audio from:", assetUrl);
+            const audioUrl = await getRecordingUrl(item.file_name as string);
+            const newAudio = new Audio(audioUrl);
+            setAudio(newAudio);
+            setPlayingId(item.id);
 
-                const newAudio = new Audio(assetUrl);
-                setAudio(newAudio);
-                setPlayingId(item.id);
+            newAudio.play().catch(err => {
+                console.error("Playback error:", err);
+                if (err.name !== 'AbortError') {
+                    toast.error("Playback failed");
+                }
+                setPlayingId(null);
+            });
 
-                newAudio.play().catch(err => {
-                    console.error("Playback error:", err);
-                    if (err.name !== 'AbortError') {
-                        toast.error("Playback failed");
-                    }
-                    setPlayingId(null);
-                });
+            newAudio.onended = () => {
+                setPlayingId(null);
+                setAudio(null);
+            };
 
-                newAudio.onended = () => {
-                    setPlayingId(null);
-                    setAudio(null);
-                };
-
-                newAudio.onerror = (e) => {
-                    console.error("Audio error:", e);
-                    toast.error("Audio file not found or corrupted");
-                    setPlayingId(null);
-                    setAudio(null);
-                };
-            }
+            newAudio.onerror = (e) => {
+                console.error("Audio error:", e);
+                toast.error("Audio file not found or corrupted");
+                setPlayingId(null);
+                setAudio(null);
+            };
         } catch (e) {
             toast.error("Failed to play audio");
         }
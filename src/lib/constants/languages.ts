@@ -60,3 +60,12 @@ export const LANGUAGES: Language[] = [
   { value: "br", label: "Breton" },
   { value: "mt", label: "Maltese" },
 ];
+
+// Languages as auto-detection reports them: both Chinese scripts come back
+// as "zh"
+export const DETECTED_LANGUAGES: Language[] = [
+  { value: "zh", label: "Chinese" },
+  ...LANGUAGES.filter(
+    (lang) => !["auto", "zh-Hans", "zh-Hant"].includes(lang.value),
+  ),
+];
@@ -0,0 +1,21 @@
+import { convertFileSrc } from "@tauri-apps/api/core";
+import { type } from "@tauri-apps/plugin-os";
+
+/**
+ * Get a URL an audio element can play a stored recording from.
+ * The backend's `recording` protocol serves recordings decrypted and
+ * decoded from memory. WebKitGTK can't stream media from custom protocols,
+ * so on Linux the recording is fetched into a blob instead.
+ */
+export async function getRecordingUrl(fileName: string): Promise<string> {
+  const url = convertFileSrc(fileName, "recording");
+  if (type() !== "linux") {
+    return url;
+  }
+
+  const response = await fetch(url);
+  if (!response.ok) {
+    throw new Error(`Failed to load recording: ${response.status}`);
+  }
+  return URL.createObjectURL(await response.blob());
+}
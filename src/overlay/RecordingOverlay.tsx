@@ -48,6 +48,7 @@ const RecordingOverlay: React.FC = () => {
   const [isVisible, setIsVisible] = useState(false);
   const [state, setState] = useState<OverlayState>("recording");
   const [levels, setLevels] = useState<number[]>(Array(16).fill(0));
+  const [countdown, setCountdown] = useState<number | null>(null);
   const smoothedLevelsRef = useRef<number[]>(Array(16).fill(0));
   const direction = getLanguageDirection(i18n.language);
 
@@ -65,8 +66,17 @@ const RecordingOverlay: React.FC = () => {
       // Listen for hide-overlay event from Rust
       const unlistenHide = await listen("hide-overlay", () => {
         setIsVisible(false);
+        setCountdown(null);
       });
 
+      // Listen for the seconds left before the recording stops on its own
+      const unlistenCountdown = await listen<number | null>(
+        "recording-countdown",
+        (event) => {
+          setCountdown(event.payload);
+        },
+      );
+
       // Listen for mic-level updates
       const unlistenLevel = await listen<number[]>("mic-level", (event) => {
         const newLevels = event.payload as number[];
@@ -86,6 +96,7 @@ const RecordingOverlay: React.FC = () => {
         unlistenShow();
         unlistenHide();
         unlistenLevel();
+        unlistenCountdown();
       };
     };
 
@@ -142,6 +153,9 @@ const RecordingOverlay: React.FC = () => {
 
       <div className="overlay-right">
         {/* Cancel button removed as per user request */}
+        {state === "recording" && countdown !== null && (
+          <div className="countdown-text">{countdown}</div>
+        )}
       </div>
     </div>
   );
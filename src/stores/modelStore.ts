@@ -388,11 +388,30 @@ export const useModelStore = create<ModelsStore>()(
         );
       });
 
+      listen<string>("model-download-paused", (event) => {
+        const modelId = event.payload;
+        set(
+          produce((state) => {
+            delete state.downloadingModels[modelId];
+            delete state.downloadStats[modelId];
+          }),
+        );
+        get().loadModels();
+      });
+
+      listen<string>("model-imported", () => {
+        get().loadModels();
+      });
+
       listen<string>("model-deleted", () => {
         get().loadModels();
         get().loadCurrentModel();
       });
 
+      listen("model-catalog-updated", () => {
+        get().loadModels();
+      });
+
       listen("model-state-changed", () => {
         get().loadModels();
         get().loadCurrentModel();
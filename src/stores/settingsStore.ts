@@ -1,6 +1,11 @@
 import { create } from "zustand";
 import { subscribeWithSelector } from "zustand/middleware";
-import type { AppSettings as Settings, AudioDevice } from "@/bindings";
+import type {
+  AppSettings as Settings,
+  AudioDevice,
+  RecordingFormat,
+  VadBackend,
+} from "@/bindings";
 import { commands } from "@/bindings";
 
 interface SettingsStore {
@@ -72,6 +77,20 @@ const settingUpdaters: {
 } = {
   always_on_microphone: (value) =>
     commands.updateMicrophoneMode(value as boolean),
+  hands_free_enabled: (value) =>
+    commands.changeHandsFreeSetting(value as boolean),
+  hands_free_silence_timeout_ms: (value) =>
+    commands.changeHandsFreeSilenceTimeoutSetting(value as number),
+  hands_free_wake_phrase: (value) =>
+    commands.changeHandsFreeWakePhraseSetting(value as string | null),
+  auto_stop_silence_ms: (value) =>
+    commands.changeAutoStopSilenceSetting(value as number | null),
+  max_recording_secs: (value) =>
+    commands.changeMaxRecordingDurationSetting(value as number | null),
+  vad_backend: (value) => commands.setVadBackend(value as VadBackend),
+  noise_suppression: (value) => commands.setNoiseSuppression(value as boolean),
+  auto_gain_control: (value) => commands.setAutoGainControl(value as boolean),
+  high_pass_filter: (value) => commands.setHighPassFilter(value as boolean),
   audio_feedback: (value) =>
     commands.changeAudioFeedbackSetting(value as boolean),
   audio_feedback_volume: (value) =>
@@ -105,10 +124,16 @@ const settingUpdaters: {
     ),
   recording_retention_period: (value) =>
     commands.updateRecordingRetentionPeriod(value as string),
+  recording_format: (value) =>
+    commands.updateRecordingFormat(value as RecordingFormat),
   translate_to_english: (value) =>
     commands.changeTranslateToEnglishSetting(value as boolean),
   selected_language: (value) =>
     commands.changeSelectedLanguageSetting(value as string),
+  auto_detect_languages: (value) =>
+    commands.changeAutoDetectLanguagesSetting(value as string[]),
+  auto_chinese_variant: (value) =>
+    commands.changeAutoChineseVariantSetting(value as string | null),
   overlay_position: (value) =>
     commands.changeOverlayPositionSetting(value as string),
   debug_mode: (value) => commands.changeDebugModeSetting(value as boolean),
@@ -131,6 +156,8 @@ const settingUpdaters: {
     commands.changeMuteWhileRecordingSetting(value as boolean),
   append_trailing_space: (value) =>
     commands.changeAppendTrailingSpaceSetting(value as boolean),
+  undo_reopens_recording: (value) =>
+    commands.changeUndoReopensRecordingSetting(value as boolean),
   log_level: (value) => commands.setLogLevel(value as any),
   app_language: (value) => commands.changeAppLanguageSetting(value as string),
   experimental_enabled: (value) =>
@@ -142,6 +169,10 @@ const settingUpdaters: {
     commands.changeTtsEnabledSetting(value as boolean),
   tts_voice: (value) => commands.changeTtsVoiceSetting(value as string),
   tts_speed: (value) => commands.changeTtsSpeedSetting(value as number),
+  max_concurrent_downloads: (value) =>
+    commands.setMaxConcurrentDownloads(value as number),
+  download_bandwidth_limit_kib: (value) =>
+    commands.setDownloadBandwidthLimit(value as number | null),
 };
 
 export const useSettingsStore = create<SettingsStore>()(